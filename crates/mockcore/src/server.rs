@@ -653,6 +653,10 @@ impl Api for Server {
     )
   }
 
+  fn get_raw_mempool(&self) -> Result<Vec<Txid>, jsonrpc_core::Error> {
+    Ok(self.state().mempool.iter().map(|tx| tx.txid()).collect())
+  }
+
   fn get_raw_transaction(
     &self,
     txid: Txid,
@@ -711,7 +715,11 @@ impl Api for Server {
         None => Err(Self::not_found()),
       }
     } else {
-      match state.transactions.get(&txid) {
+      match state
+        .transactions
+        .get(&txid)
+        .or_else(|| state.mempool.iter().find(|tx| tx.txid() == txid))
+      {
         Some(tx) => Ok(Value::String(hex::encode(serialize(tx)))),
         None => Err(Self::not_found()),
       }
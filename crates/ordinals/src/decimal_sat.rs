@@ -6,6 +6,16 @@ pub struct DecimalSat {
   pub offset: u64,
 }
 
+impl DecimalSat {
+  pub fn sat(&self) -> Sat {
+    self.height.starting_sat() + self.offset
+  }
+
+  pub fn satributes(&self) -> Vec<Satribute> {
+    self.sat().satributes()
+  }
+}
+
 impl From<Sat> for DecimalSat {
   fn from(sat: Sat) -> Self {
     Self {
@@ -49,4 +59,23 @@ mod tests {
       }
     );
   }
+
+  #[test]
+  fn sat() {
+    for n in [0, 1, 50 * COIN_VALUE, 2099999997689999] {
+      assert_eq!(Sat(n).decimal().sat(), Sat(n));
+    }
+  }
+
+  #[test]
+  fn satributes() {
+    assert_eq!(
+      DecimalSat {
+        height: Height(9),
+        offset: 0,
+      }
+      .satributes(),
+      [Satribute::Alpha, Satribute::Block9, Satribute::Vintage]
+    );
+  }
 }
@@ -27,10 +27,26 @@ use {
 };
 
 pub use {
-  artifact::Artifact, cenotaph::Cenotaph, charm::Charm, decimal_sat::DecimalSat, degree::Degree,
-  edict::Edict, epoch::Epoch, etching::Etching, flaw::Flaw, height::Height, pile::Pile,
-  rarity::Rarity, rune::Rune, rune_id::RuneId, runestone::Runestone, sat::Sat, sat_point::SatPoint,
-  spaced_rune::SpacedRune, terms::Terms,
+  artifact::Artifact,
+  cenotaph::Cenotaph,
+  charm::Charm,
+  decimal_sat::DecimalSat,
+  degree::Degree,
+  edict::Edict,
+  epoch::Epoch,
+  etching::Etching,
+  flaw::Flaw,
+  height::Height,
+  pile::Pile,
+  rarity::Rarity,
+  rune::Rune,
+  rune_id::RuneId,
+  runestone::Runestone,
+  sat::Sat,
+  sat_point::SatPoint,
+  satribute::{Classifier, Satribute},
+  spaced_rune::SpacedRune,
+  terms::Terms,
 };
 
 pub const CYCLE_EPOCHS: u32 = 6;
@@ -56,6 +72,7 @@ mod rune_id;
 mod runestone;
 mod sat;
 mod sat_point;
+mod satribute;
 mod spaced_rune;
 mod terms;
 pub mod varint;
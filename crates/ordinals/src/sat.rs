@@ -107,6 +107,10 @@ impl Sat {
     charms
   }
 
+  pub fn satributes(self) -> Vec<Satribute> {
+    Classifier::default().classify(self)
+  }
+
   fn from_name(s: &str) -> Result<Self, Error> {
     let mut x = 0;
     for c in s.chars() {
@@ -776,6 +780,15 @@ mod tests {
     }
   }
 
+  #[test]
+  fn satributes() {
+    assert_eq!(
+      Sat(50 * COIN_VALUE - 1).satributes(),
+      [Satribute::Black, Satribute::Omega, Satribute::Vintage]
+    );
+    assert_eq!(Sat(Height(1000).starting_sat().n() + 1).satributes(), []);
+  }
+
   #[test]
   fn error_display() {
     assert_eq!(
@@ -0,0 +1,284 @@
+use super::*;
+
+/// Sat traits that collectors trade on in addition to rarity. Most are derived
+/// from the sat number, but some, like pizza sats, are defined by the history
+/// of the chain and must be registered with a `Classifier` as sat ranges.
+#[derive(
+  Copy, Clone, Debug, Eq, Hash, Ord, PartialEq, PartialOrd, DeserializeFromStr, SerializeDisplay,
+)]
+pub enum Satribute {
+  Alpha = 0,
+  Black = 1,
+  Block9 = 2,
+  Block78 = 3,
+  Omega = 4,
+  Palindrome = 5,
+  Pizza = 6,
+  Vintage = 7,
+}
+
+impl Satribute {
+  pub const ALL: [Self; 8] = [
+    Self::Alpha,
+    Self::Black,
+    Self::Block9,
+    Self::Block78,
+    Self::Omega,
+    Self::Palindrome,
+    Self::Pizza,
+    Self::Vintage,
+  ];
+
+  /// Whether `sat` has this satribute by virtue of its number alone.
+  pub fn contains(self, sat: Sat) -> bool {
+    self.intersects(sat.n(), sat.n() + 1)
+  }
+
+  /// Whether any sat in `start..end` has this satribute by virtue of its
+  /// number alone.
+  pub fn intersects(self, start: u64, end: u64) -> bool {
+    if start >= end {
+      return false;
+    }
+
+    match self {
+      Self::Alpha => start.next_multiple_of(COIN_VALUE) < end,
+      Self::Black => (Sat(start).height() + 1).starting_sat().n() - 1 < end,
+      Self::Block9 => Self::overlaps(start, end, Height(9), Height(10)),
+      Self::Block78 => Self::overlaps(start, end, Height(78), Height(79)),
+      Self::Omega => (start + 1).next_multiple_of(COIN_VALUE) - 1 < end,
+      Self::Palindrome => Self::next_palindrome(start) < end,
+      Self::Pizza => false,
+      Self::Vintage => Self::overlaps(start, end, Height(0), Height(1000)),
+    }
+  }
+
+  fn overlaps(start: u64, end: u64, first: Height, last: Height) -> bool {
+    start < last.starting_sat().n() && first.starting_sat().n() < end
+  }
+
+  fn next_palindrome(n: u64) -> u64 {
+    let digits = n.to_string();
+
+    let mirror = |half: u64| {
+      let half = half.to_string();
+      let tail = half[..digits.len() / 2].chars().rev().collect::<String>();
+      format!("{half}{tail}").parse::<u64>().unwrap()
+    };
+
+    let half = digits[..digits.len().div_ceil(2)].parse::<u64>().unwrap();
+
+    let candidate = mirror(half);
+
+    // a half of all nines mirrors to the largest number with this many
+    // digits, so incrementing the half never adds a digit
+    if candidate >= n {
+      candidate
+    } else {
+      mirror(half + 1)
+    }
+  }
+}
+
+impl Display for Satribute {
+  fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+    write!(
+      f,
+      "{}",
+      match self {
+        Self::Alpha => "alpha",
+        Self::Black => "black",
+        Self::Block9 => "block9",
+        Self::Block78 => "block78",
+        Self::Omega => "omega",
+        Self::Palindrome => "palindrome",
+        Self::Pizza => "pizza",
+        Self::Vintage => "vintage",
+      }
+    )
+  }
+}
+
+impl FromStr for Satribute {
+  type Err = String;
+
+  fn from_str(s: &str) -> Result<Self, Self::Err> {
+    Ok(match s {
+      "alpha" => Self::Alpha,
+      "black" => Self::Black,
+      "block9" => Self::Block9,
+      "block78" => Self::Block78,
+      "omega" => Self::Omega,
+      "palindrome" => Self::Palindrome,
+      "pizza" => Self::Pizza,
+      "vintage" => Self::Vintage,
+      _ => return Err(format!("invalid satribute `{s}`")),
+    })
+  }
+}
+
+impl From<Satribute> for u8 {
+  fn from(satribute: Satribute) -> Self {
+    satribute as u8
+  }
+}
+
+impl TryFrom<u8> for Satribute {
+  type Error = u8;
+
+  fn try_from(n: u8) -> Result<Self, u8> {
+    Self::ALL.get(usize::from(n)).copied().ok_or(n)
+  }
+}
+
+/// Classifies sats by satribute. Satributes that can't be derived from the sat
+/// number are plugged in as sat ranges with `add_range`.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct Classifier {
+  ranges: Vec<(Satribute, u64, u64)>,
+}
+
+impl Classifier {
+  pub fn add_range(&mut self, satribute: Satribute, start: u64, end: u64) {
+    self.ranges.push((satribute, start, end));
+  }
+
+  pub fn ranges(&self) -> &[(Satribute, u64, u64)] {
+    &self.ranges
+  }
+
+  pub fn classify(&self, sat: Sat) -> Vec<Satribute> {
+    self.classify_range(sat.n(), sat.n() + 1)
+  }
+
+  /// Satributes of any sat in `start..end`.
+  pub fn classify_range(&self, start: u64, end: u64) -> Vec<Satribute> {
+    Satribute::ALL
+      .into_iter()
+      .filter(|satribute| {
+        satribute.intersects(start, end)
+          || self.ranges.iter().any(|(ranged, range_start, range_end)| {
+            ranged == satribute && *range_start < end && start < *range_end
+          })
+      })
+      .collect()
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  #[test]
+  fn display_and_from_str() {
+    for satribute in Satribute::ALL {
+      assert_eq!(
+        satribute.to_string().parse::<Satribute>().unwrap(),
+        satribute
+      );
+    }
+
+    assert_eq!(
+      "foo".parse::<Satribute>().unwrap_err(),
+      "invalid satribute `foo`"
+    );
+  }
+
+  #[test]
+  fn u8_round_trip() {
+    for satribute in Satribute::ALL {
+      assert_eq!(Satribute::try_from(u8::from(satribute)).unwrap(), satribute);
+    }
+
+    assert_eq!(Satribute::try_from(8), Err(8));
+  }
+
+  #[test]
+  fn alpha_and_omega() {
+    assert!(Satribute::Alpha.contains(Sat(0)));
+    assert!(Satribute::Alpha.contains(Sat(COIN_VALUE)));
+    assert!(!Satribute::Alpha.contains(Sat(1)));
+    assert!(Satribute::Omega.contains(Sat(COIN_VALUE - 1)));
+    assert!(!Satribute::Omega.contains(Sat(COIN_VALUE)));
+    assert!(Satribute::Alpha.intersects(1, COIN_VALUE + 1));
+    assert!(!Satribute::Alpha.intersects(1, COIN_VALUE));
+    assert!(Satribute::Omega.intersects(0, COIN_VALUE));
+    assert!(!Satribute::Omega.intersects(0, COIN_VALUE - 1));
+  }
+
+  #[test]
+  fn black() {
+    assert!(Satribute::Black.contains(Sat(50 * COIN_VALUE - 1)));
+    assert!(!Satribute::Black.contains(Sat(50 * COIN_VALUE)));
+    assert!(Satribute::Black.contains(Sat::LAST));
+    assert!(Satribute::Black.intersects(1, 50 * COIN_VALUE));
+    assert!(!Satribute::Black.intersects(0, 50 * COIN_VALUE - 1));
+  }
+
+  #[test]
+  fn blocks() {
+    assert!(Satribute::Block9.contains(Height(9).starting_sat()));
+    assert!(!Satribute::Block9.contains(Height(10).starting_sat()));
+    assert!(Satribute::Block78.contains(Sat(Height(79).starting_sat().n() - 1)));
+    assert!(!Satribute::Block78.contains(Height(77).starting_sat()));
+    assert!(Satribute::Vintage.contains(Sat(0)));
+    assert!(Satribute::Vintage.contains(Height(999).starting_sat()));
+    assert!(!Satribute::Vintage.contains(Height(1000).starting_sat()));
+  }
+
+  #[test]
+  fn palindrome() {
+    for n in [0, 9, 11, 121, 1221, 1234554321] {
+      assert!(Satribute::Palindrome.contains(Sat(n)), "{n}");
+    }
+
+    for n in [10, 12, 123, 1231, 2099999997689999] {
+      assert!(!Satribute::Palindrome.contains(Sat(n)), "{n}");
+    }
+
+    assert_eq!(Satribute::next_palindrome(10), 11);
+    assert_eq!(Satribute::next_palindrome(99), 99);
+    assert_eq!(Satribute::next_palindrome(100), 101);
+    assert_eq!(Satribute::next_palindrome(1291), 1331);
+    assert_eq!(Satribute::next_palindrome(12399), 12421);
+    assert!(Satribute::Palindrome.intersects(12, 23));
+    assert!(!Satribute::Palindrome.intersects(12, 22));
+  }
+
+  #[test]
+  fn pizza_is_not_derived_from_sat_number() {
+    assert!(!Satribute::Pizza.intersects(0, Sat::SUPPLY));
+  }
+
+  #[test]
+  fn classify() {
+    assert_eq!(
+      Classifier::default().classify(Sat(0)),
+      [Satribute::Alpha, Satribute::Palindrome, Satribute::Vintage]
+    );
+
+    assert_eq!(
+      Classifier::default().classify(Height(1000).starting_sat() + 1),
+      []
+    );
+  }
+
+  #[test]
+  fn classify_with_ranges() {
+    let mut classifier = Classifier::default();
+
+    classifier.add_range(Satribute::Pizza, 100, 200);
+
+    assert_eq!(
+      classifier.classify(Sat(151)),
+      [Satribute::Palindrome, Satribute::Pizza, Satribute::Vintage]
+    );
+
+    assert_eq!(classifier.classify(Sat(200)), [Satribute::Vintage]);
+
+    assert_eq!(
+      classifier.classify_range(190, 300),
+      [Satribute::Palindrome, Satribute::Pizza, Satribute::Vintage]
+    );
+  }
+}
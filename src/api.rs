@@ -8,6 +8,15 @@ pub use crate::templates::{
   TransactionHtml as Transaction,
 };
 
+#[derive(Debug, PartialEq, Serialize, Deserialize)]
+pub struct AddressInfo {
+  pub cardinal_sat_balance: u64,
+  pub inscriptions: Vec<InscriptionId>,
+  pub outputs: Vec<OutPoint>,
+  pub runes: BTreeMap<SpacedRune, Pile>,
+  pub sat_balance: u64,
+}
+
 #[derive(Debug, PartialEq, Serialize, Deserialize)]
 pub struct Block {
   pub best_height: u32,
@@ -76,20 +85,37 @@ pub struct Children {
   pub page: usize,
 }
 
+#[derive(Debug, PartialEq, Serialize, Deserialize)]
+pub struct Collection {
+  pub children: u64,
+  pub content_types: Vec<(Option<String>, u64)>,
+  pub first_child_height: Option<u32>,
+  pub grandchildren: u64,
+  pub holders: u64,
+  pub id: InscriptionId,
+  pub last_child_height: Option<u32>,
+  pub mints_per_block: Vec<(u32, u64)>,
+  pub number: i32,
+}
+
 #[derive(Debug, PartialEq, Serialize, Deserialize, Clone)]
 pub struct Inscription {
   pub address: Option<String>,
+  pub body_hash: Option<sha256::Hash>,
   pub charms: Vec<Charm>,
   pub children: Vec<InscriptionId>,
   pub content_length: Option<usize>,
   pub content_type: Option<String>,
+  pub delegator_count: u64,
   pub effective_content_type: Option<String>,
   pub fee: u64,
   pub height: u32,
   pub id: InscriptionId,
   pub next: Option<InscriptionId>,
   pub number: i32,
+  pub original: Option<InscriptionId>,
   pub parents: Vec<InscriptionId>,
+  pub pending: bool,
   pub previous: Option<InscriptionId>,
   pub rune: Option<SpacedRune>,
   pub sat: Option<ordinals::Sat>,
@@ -98,6 +124,20 @@ pub struct Inscription {
   pub value: Option<u64>,
 }
 
+#[derive(Debug, PartialEq, Serialize, Deserialize)]
+pub struct InscriptionHistory {
+  pub id: InscriptionId,
+  pub number: i32,
+  pub transfers: Vec<InscriptionTransfer>,
+}
+
+#[derive(Debug, PartialEq, Serialize, Deserialize)]
+pub struct InscriptionSales {
+  pub id: InscriptionId,
+  pub number: i32,
+  pub sales: Vec<Sale>,
+}
+
 #[derive(Debug, PartialEq, Serialize, Deserialize)]
 pub struct InscriptionRecursive {
   pub charms: Vec<Charm>,
@@ -114,6 +154,16 @@ pub struct InscriptionRecursive {
   pub value: Option<u64>,
 }
 
+#[derive(Debug, PartialEq, Serialize, Deserialize, Clone)]
+pub struct InscriptionTransfer {
+  pub from: Option<Address<NetworkUnchecked>>,
+  pub height: u32,
+  pub new_satpoint: SatPoint,
+  pub old_satpoint: Option<SatPoint>,
+  pub to: Option<Address<NetworkUnchecked>>,
+  pub txid: Txid,
+}
+
 #[derive(Debug, PartialEq, Serialize, Deserialize)]
 pub struct Inscriptions {
   pub ids: Vec<InscriptionId>,
@@ -121,11 +171,43 @@ pub struct Inscriptions {
   pub page_index: u32,
 }
 
+#[derive(Debug, PartialEq, Serialize, Deserialize, Clone)]
+pub struct MempoolInscription {
+  pub content_length: Option<usize>,
+  pub content_type: Option<String>,
+  pub id: InscriptionId,
+}
+
+#[derive(Debug, PartialEq, Serialize, Deserialize)]
+pub struct MempoolInscriptions {
+  pub inscriptions: Vec<MempoolInscription>,
+  pub transfers: Vec<MempoolTransfer>,
+}
+
+#[derive(Debug, PartialEq, Serialize, Deserialize, Clone)]
+pub struct MempoolRune {
+  pub artifact: Artifact,
+  pub txid: Txid,
+}
+
+#[derive(Debug, PartialEq, Serialize, Deserialize, Clone)]
+pub struct MempoolTransfer {
+  pub id: InscriptionId,
+  pub satpoint: SatPoint,
+  pub txid: Txid,
+}
+
+#[derive(Debug, PartialEq, Serialize, Deserialize)]
+pub struct Metaprotocols {
+  pub metaprotocols: Vec<(String, u64)>,
+}
+
 #[derive(Debug, PartialEq, Serialize, Deserialize)]
 pub struct Output {
   pub address: Option<Address<NetworkUnchecked>>,
   pub indexed: bool,
   pub inscriptions: Vec<InscriptionId>,
+  pub pending: bool,
   pub runes: BTreeMap<SpacedRune, Pile>,
   pub sat_ranges: Option<Vec<(u64, u64)>>,
   pub script_pubkey: String,
@@ -144,6 +226,7 @@ impl Output {
     runes: BTreeMap<SpacedRune, Pile>,
     sat_ranges: Option<Vec<(u64, u64)>>,
     spent: bool,
+    pending: bool,
   ) -> Self {
     Self {
       address: chain
@@ -152,6 +235,7 @@ impl Output {
         .map(|address| uncheck(&address)),
       indexed,
       inscriptions,
+      pending,
       runes,
       sat_ranges,
       script_pubkey: tx_out.script_pubkey.to_asm_string(),
@@ -162,6 +246,62 @@ impl Output {
   }
 }
 
+#[derive(Debug, PartialEq, Serialize, Deserialize)]
+pub struct RuneHistory {
+  pub blocks: Vec<RuneHistoryBlock>,
+  pub id: RuneId,
+  pub rune: SpacedRune,
+  pub supply: u128,
+}
+
+#[derive(Debug, PartialEq, Serialize, Deserialize)]
+pub struct RuneHistoryBlock {
+  pub burned: u128,
+  pub height: u32,
+  pub mints: u128,
+  pub supply: u128,
+  pub total_burned: u128,
+  pub transferred: u128,
+}
+
+#[derive(Debug, PartialEq, Serialize, Deserialize)]
+pub struct RuneHolder {
+  pub address: Option<Address<NetworkUnchecked>>,
+  pub amount: u128,
+  pub script_pubkey: ScriptBuf,
+}
+
+#[derive(Debug, PartialEq, Serialize, Deserialize)]
+pub struct RuneHolders {
+  pub holders: Vec<RuneHolder>,
+  pub more: bool,
+  pub prev: Option<usize>,
+  pub next: Option<usize>,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Sale {
+  pub buyer: Option<Address<NetworkUnchecked>>,
+  pub height: u32,
+  pub price: u64,
+  pub seller: Option<Address<NetworkUnchecked>>,
+  pub txid: Txid,
+}
+
+#[derive(Debug, PartialEq, Serialize, Deserialize)]
+pub struct SatributeOutputs {
+  pub satribute: Satribute,
+  pub outputs: Vec<OutPoint>,
+  pub more: bool,
+  pub prev: Option<usize>,
+  pub next: Option<usize>,
+}
+
+#[derive(Debug, PartialEq, Serialize, Deserialize)]
+pub struct Satributes {
+  pub ranges: Vec<(Satribute, u64, u64)>,
+}
+
 #[derive(Debug, PartialEq, Serialize, Deserialize)]
 pub struct Sat {
   pub block: u32,
@@ -178,6 +318,7 @@ pub struct Sat {
   pub period: u32,
   pub rarity: Rarity,
   pub satpoint: Option<SatPoint>,
+  pub satributes: Vec<Satribute>,
   pub timestamp: i64,
 }
 
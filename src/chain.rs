@@ -55,6 +55,17 @@ impl Chain {
     }
   }
 
+  pub(crate) fn pizza_txid(self) -> Option<Txid> {
+    match self {
+      Self::Mainnet => Some(
+        "a1075db55d416d3ca199f55b6084e2115b9345e16c5cf302fc80e9d5fbf5d48d"
+          .parse()
+          .unwrap(),
+      ),
+      Self::Regtest | Self::Signet | Self::Testnet => None,
+    }
+  }
+
   pub(crate) fn genesis_block(self) -> Block {
     bitcoin::blockdata::constants::genesis_block(self.network())
   }
@@ -1,12 +1,16 @@
 use {
   self::{
     entry::{
-      Entry, HeaderValue, InscriptionEntry, InscriptionEntryValue, InscriptionIdValue,
-      OutPointValue, RuneEntryValue, RuneIdValue, SatPointValue, SatRange, TxidValue,
+      CollectionEntry, CollectionEntryValue, Entry, HeaderValue, InscriptionEntry,
+      InscriptionEntryValue, InscriptionIdValue, OutPointValue, RuneEntryValue, RuneHistoryEntry,
+      RuneHistoryEntryValue, RuneIdValue, SaleEntry, SaleEntryValue, SatPointValue, SatRange,
+      TransferEntry, TransferEntryValue, TxidValue,
     },
     event::Event,
     lot::Lot,
+    mempool::Mempool,
     reorg::Reorg,
+    undo::{UndoLog, UndoMultimapTable, UndoRecordValue, UndoTable},
     updater::Updater,
   },
   super::*,
@@ -25,10 +29,11 @@ use {
   log::log_enabled,
   redb::{
     Database, DatabaseError, MultimapTable, MultimapTableDefinition, MultimapTableHandle,
-    ReadOnlyTable, ReadableMultimapTable, ReadableTable, ReadableTableMetadata, RepairSession,
-    StorageError, Table, TableDefinition, TableHandle, TableStats, WriteTransaction,
+    ReadOnlyTable, ReadTransaction, ReadableMultimapTable, ReadableTable, ReadableTableMetadata,
+    RepairSession, StorageError, Table, TableDefinition, TableHandle, TableStats, WriteTransaction,
   },
   std::{
+    borrow::Borrow,
     collections::HashMap,
     io::{BufWriter, Write},
     sync::Once,
@@ -39,35 +44,65 @@ pub use self::entry::RuneEntry;
 
 pub(crate) mod entry;
 pub mod event;
+mod export;
 mod fetcher;
 mod lot;
+mod mempool;
 mod reorg;
 mod rtx;
+mod snapshot;
+mod undo;
 mod updater;
+mod verify;
 
 #[cfg(test)]
 pub(crate) mod testing;
 
-const SCHEMA_VERSION: u64 = 25;
+const SCHEMA_VERSION: u64 = 47;
 
+define_multimap_table! { BODY_HASH_TO_SEQUENCE_NUMBER, &[u8; 32], u32 }
+define_multimap_table! { DELEGATE_TO_SEQUENCE_NUMBERS, InscriptionIdValue, u32 }
+define_multimap_table! { METADATA_KEY_AND_VALUE_TO_SEQUENCE_NUMBER, (&str, &str), u32 }
+define_multimap_table! { METAPROTOCOL_TO_SEQUENCE_NUMBER, &str, u32 }
+define_multimap_table! { RUNE_ID_AND_BALANCE_TO_HOLDER, (RuneIdValue, u128), &[u8] }
 define_multimap_table! { SATPOINT_TO_SEQUENCE_NUMBER, &SatPointValue, u32 }
+define_multimap_table! { SATRIBUTE_TO_OUTPOINT, u8, &OutPointValue }
 define_multimap_table! { SAT_TO_SEQUENCE_NUMBER, u64, u32 }
+define_multimap_table! { SCRIPT_PUBKEY_TO_OUTPOINT, &[u8], &OutPointValue }
 define_multimap_table! { SEQUENCE_NUMBER_TO_CHILDREN, u32, u32 }
+define_table! { COLLECTION_AND_CONTENT_TYPE_TO_COUNT, (u32, Option<&[u8]>), u64 }
+define_table! { COLLECTION_AND_HEIGHT_TO_MINTS, (u32, u32), u64 }
+define_table! { COLLECTION_AND_HOLDER_TO_COUNT, (u32, &[u8]), u64 }
+define_table! { COLLECTION_TO_ENTRY, u32, CollectionEntryValue }
+define_table! { CONTENT_TYPE_AND_SEQUENCE_NUMBER, (&[u8], u32), () }
 define_table! { CONTENT_TYPE_TO_COUNT, Option<&[u8]>, u64 }
+define_table! { EVENT_SEQUENCE_NUMBER_TO_EVENT, u64, &[u8] }
+define_table! { HEIGHT_AND_NUMBER_TO_UNDO_RECORD, (u32, u32), UndoRecordValue }
 define_table! { HEIGHT_TO_BLOCK_HEADER, u32, &HeaderValue }
+define_table! { HEIGHT_TO_FIRST_EVENT_SEQUENCE_NUMBER, u32, u64 }
 define_table! { HEIGHT_TO_LAST_SEQUENCE_NUMBER, u32, u32 }
 define_table! { HOME_INSCRIPTIONS, u32, InscriptionIdValue }
 define_table! { INSCRIPTION_ID_TO_SEQUENCE_NUMBER, InscriptionIdValue, u32 }
 define_table! { INSCRIPTION_NUMBER_TO_SEQUENCE_NUMBER, i32, u32 }
+define_table! { METAPROTOCOL_TO_COUNT, &str, u64 }
 define_table! { OUTPOINT_TO_RUNE_BALANCES, &OutPointValue, &[u8] }
 define_table! { OUTPOINT_TO_SAT_RANGES, &OutPointValue, &[u8] }
+define_table! { OUTPOINT_TO_TXOUT, &OutPointValue, (u64, &[u8]) }
 define_table! { OUTPOINT_TO_VALUE, &OutPointValue, u64}
+define_table! { RUNE_HOLDER_TO_BALANCE, (RuneIdValue, &[u8]), u128 }
+define_table! { RUNE_ID_AND_HEIGHT_TO_HISTORY_ENTRY, (RuneIdValue, u32), RuneHistoryEntryValue }
+define_table! { RUNE_ID_TO_HOLDER_COUNT, RuneIdValue, u64 }
 define_table! { RUNE_ID_TO_RUNE_ENTRY, RuneIdValue, RuneEntryValue }
 define_table! { RUNE_TO_RUNE_ID, u128, RuneIdValue }
+define_table! { SATRIBUTE_TO_SAT_RANGES, u8, &[u8] }
 define_table! { SAT_TO_SATPOINT, u64, &SatPointValue }
+define_table! { SEQUENCE_NUMBER_TO_CONTENT_TYPE, u32, &[u8] }
+define_table! { SEQUENCE_NUMBER_TO_HOLDER, u32, &[u8] }
 define_table! { SEQUENCE_NUMBER_TO_INSCRIPTION_ENTRY, u32, InscriptionEntryValue }
 define_table! { SEQUENCE_NUMBER_TO_RUNE_ID, u32, RuneIdValue }
+define_table! { SEQUENCE_NUMBER_TO_SALE_ENTRY, (u32, u32), SaleEntryValue }
 define_table! { SEQUENCE_NUMBER_TO_SATPOINT, u32, &SatPointValue }
+define_table! { SEQUENCE_NUMBER_TO_TRANSFER_ENTRY, (u32, u32), TransferEntryValue }
 define_table! { STATISTIC_TO_COUNT, u64, u64 }
 define_table! { TRANSACTION_ID_TO_RUNE, &TxidValue, u128 }
 define_table! { TRANSACTION_ID_TO_TRANSACTION, &TxidValue, &[u8] }
@@ -90,6 +125,10 @@ pub(crate) enum Statistic {
   IndexTransactions = 12,
   IndexSpentSats = 13,
   InitialSyncTime = 14,
+  IndexEvents = 15,
+  IndexAddresses = 16,
+  IndexTransfers = 17,
+  IndexMetadata = 18,
 }
 
 impl Statistic {
@@ -189,10 +228,15 @@ pub struct Index {
   genesis_block_coinbase_transaction: Transaction,
   genesis_block_coinbase_txid: Txid,
   height_limit: Option<u32>,
+  index_addresses: bool,
+  index_events: bool,
+  index_metadata: bool,
   index_runes: bool,
   index_sats: bool,
   index_spent_sats: bool,
   index_transactions: bool,
+  index_transfers: bool,
+  mempool: Mutex<Mempool>,
   settings: Settings,
   path: PathBuf,
   started: DateTime<Utc>,
@@ -296,23 +340,48 @@ impl Index {
 
         tx.set_durability(durability);
 
+        tx.open_multimap_table(BODY_HASH_TO_SEQUENCE_NUMBER)?;
+        tx.open_multimap_table(DELEGATE_TO_SEQUENCE_NUMBERS)?;
+        tx.open_multimap_table(METADATA_KEY_AND_VALUE_TO_SEQUENCE_NUMBER)?;
+        tx.open_multimap_table(METAPROTOCOL_TO_SEQUENCE_NUMBER)?;
+        tx.open_multimap_table(RUNE_ID_AND_BALANCE_TO_HOLDER)?;
         tx.open_multimap_table(SATPOINT_TO_SEQUENCE_NUMBER)?;
+        tx.open_multimap_table(SATRIBUTE_TO_OUTPOINT)?;
         tx.open_multimap_table(SAT_TO_SEQUENCE_NUMBER)?;
+        tx.open_multimap_table(SCRIPT_PUBKEY_TO_OUTPOINT)?;
         tx.open_multimap_table(SEQUENCE_NUMBER_TO_CHILDREN)?;
+        tx.open_table(COLLECTION_AND_CONTENT_TYPE_TO_COUNT)?;
+        tx.open_table(COLLECTION_AND_HEIGHT_TO_MINTS)?;
+        tx.open_table(COLLECTION_AND_HOLDER_TO_COUNT)?;
+        tx.open_table(COLLECTION_TO_ENTRY)?;
+        tx.open_table(CONTENT_TYPE_AND_SEQUENCE_NUMBER)?;
         tx.open_table(CONTENT_TYPE_TO_COUNT)?;
+        tx.open_table(EVENT_SEQUENCE_NUMBER_TO_EVENT)?;
+        tx.open_table(HEIGHT_AND_NUMBER_TO_UNDO_RECORD)?;
         tx.open_table(HEIGHT_TO_BLOCK_HEADER)?;
+        tx.open_table(HEIGHT_TO_FIRST_EVENT_SEQUENCE_NUMBER)?;
         tx.open_table(HEIGHT_TO_LAST_SEQUENCE_NUMBER)?;
         tx.open_table(HOME_INSCRIPTIONS)?;
         tx.open_table(INSCRIPTION_ID_TO_SEQUENCE_NUMBER)?;
         tx.open_table(INSCRIPTION_NUMBER_TO_SEQUENCE_NUMBER)?;
+        tx.open_table(METAPROTOCOL_TO_COUNT)?;
         tx.open_table(OUTPOINT_TO_RUNE_BALANCES)?;
+        tx.open_table(OUTPOINT_TO_TXOUT)?;
         tx.open_table(OUTPOINT_TO_VALUE)?;
+        tx.open_table(RUNE_HOLDER_TO_BALANCE)?;
+        tx.open_table(RUNE_ID_AND_HEIGHT_TO_HISTORY_ENTRY)?;
+        tx.open_table(RUNE_ID_TO_HOLDER_COUNT)?;
         tx.open_table(RUNE_ID_TO_RUNE_ENTRY)?;
         tx.open_table(RUNE_TO_RUNE_ID)?;
+        tx.open_table(SATRIBUTE_TO_SAT_RANGES)?;
         tx.open_table(SAT_TO_SATPOINT)?;
+        tx.open_table(SEQUENCE_NUMBER_TO_CONTENT_TYPE)?;
+        tx.open_table(SEQUENCE_NUMBER_TO_HOLDER)?;
         tx.open_table(SEQUENCE_NUMBER_TO_INSCRIPTION_ENTRY)?;
         tx.open_table(SEQUENCE_NUMBER_TO_RUNE_ID)?;
+        tx.open_table(SEQUENCE_NUMBER_TO_SALE_ENTRY)?;
         tx.open_table(SEQUENCE_NUMBER_TO_SATPOINT)?;
+        tx.open_table(SEQUENCE_NUMBER_TO_TRANSFER_ENTRY)?;
         tx.open_table(TRANSACTION_ID_TO_RUNE)?;
         tx.open_table(WRITE_TRANSACTION_STARTING_BLOCK_COUNT_TO_TIMESTAMP)?;
 
@@ -324,6 +393,24 @@ impl Index {
             outpoint_to_sat_ranges.insert(&OutPoint::null().store(), [].as_slice())?;
           }
 
+          Self::set_statistic(
+            &mut statistics,
+            Statistic::IndexAddresses,
+            u64::from(settings.index_addresses()),
+          )?;
+
+          Self::set_statistic(
+            &mut statistics,
+            Statistic::IndexEvents,
+            u64::from(settings.index_events()),
+          )?;
+
+          Self::set_statistic(
+            &mut statistics,
+            Statistic::IndexMetadata,
+            u64::from(settings.index_metadata()),
+          )?;
+
           Self::set_statistic(
             &mut statistics,
             Statistic::IndexRunes,
@@ -348,6 +435,12 @@ impl Index {
             u64::from(settings.index_transactions()),
           )?;
 
+          Self::set_statistic(
+            &mut statistics,
+            Statistic::IndexTransfers,
+            u64::from(settings.index_transfers()),
+          )?;
+
           Self::set_statistic(&mut statistics, Statistic::Schema, SCHEMA_VERSION)?;
         }
 
@@ -402,18 +495,26 @@ impl Index {
       Err(error) => bail!("failed to open index: {error}"),
     };
 
+    let index_addresses;
+    let index_events;
+    let index_metadata;
     let index_runes;
     let index_sats;
     let index_spent_sats;
     let index_transactions;
+    let index_transfers;
 
     {
       let tx = database.begin_read()?;
       let statistics = tx.open_table(STATISTIC_TO_COUNT)?;
+      index_addresses = Self::is_statistic_set(&statistics, Statistic::IndexAddresses)?;
+      index_events = Self::is_statistic_set(&statistics, Statistic::IndexEvents)?;
+      index_metadata = Self::is_statistic_set(&statistics, Statistic::IndexMetadata)?;
       index_runes = Self::is_statistic_set(&statistics, Statistic::IndexRunes)?;
       index_sats = Self::is_statistic_set(&statistics, Statistic::IndexSats)?;
       index_spent_sats = Self::is_statistic_set(&statistics, Statistic::IndexSpentSats)?;
       index_transactions = Self::is_statistic_set(&statistics, Statistic::IndexTransactions)?;
+      index_transfers = Self::is_statistic_set(&statistics, Statistic::IndexTransfers)?;
     }
 
     let genesis_block_coinbase_transaction =
@@ -428,10 +529,15 @@ impl Index {
       first_inscription_height: settings.first_inscription_height(),
       genesis_block_coinbase_transaction,
       height_limit: settings.height_limit(),
+      index_addresses,
+      index_events,
+      index_metadata,
       index_runes,
       index_sats,
       index_spent_sats,
       index_transactions,
+      index_transfers,
+      mempool: Default::default(),
       settings: settings.clone(),
       path,
       started: Utc::now(),
@@ -439,11 +545,6 @@ impl Index {
     })
   }
 
-  #[cfg(test)]
-  fn set_durability(&mut self, durability: redb::Durability) {
-    self.durability = durability;
-  }
-
   pub(crate) fn contains_output(&self, output: &OutPoint) -> Result<bool> {
     Ok(
       self
@@ -455,6 +556,34 @@ impl Index {
     )
   }
 
+  pub(crate) fn has_address_index(&self) -> bool {
+    self.index_addresses
+  }
+
+  /// Identifies this index across restarts. A rebuilt index at the same path
+  /// gets a new identity, since the first write transaction is timestamped
+  /// when indexing starts.
+  pub(crate) fn identity(&self) -> Result<Option<String>> {
+    Ok(
+      self
+        .database
+        .begin_read()?
+        .open_table(WRITE_TRANSACTION_STARTING_BLOCK_COUNT_TO_TIMESTAMP)?
+        .first()?
+        .map(|(_starting_block_count, timestamp)| {
+          format!("{}@{}", self.path.display(), timestamp.value())
+        }),
+    )
+  }
+
+  pub(crate) fn has_event_index(&self) -> bool {
+    self.index_events
+  }
+
+  pub(crate) fn has_metadata_index(&self) -> bool {
+    self.index_metadata
+  }
+
   pub(crate) fn has_rune_index(&self) -> bool {
     self.index_runes
   }
@@ -463,6 +592,10 @@ impl Index {
     self.index_sats
   }
 
+  pub(crate) fn has_transfer_index(&self) -> bool {
+    self.index_transfers
+  }
+
   pub(crate) fn status(&self) -> Result<StatusHtml> {
     let rtx = self.database.begin_read()?;
 
@@ -627,6 +760,7 @@ impl Index {
         outputs_traversed: 0,
         range_cache: HashMap::new(),
         sat_ranges_since_flush: 0,
+        uncommitted_blocks: Vec::new(),
       };
 
       match updater.update_index(wtx) {
@@ -651,6 +785,26 @@ impl Index {
     }
   }
 
+  pub fn update_mempool(&self) -> Result {
+    let block_count = self.block_count()?;
+
+    let txids = self.client.get_raw_mempool()?;
+
+    Mempool::update(&self.mempool, self, block_count, txids)
+  }
+
+  pub(crate) fn get_mempool_inscriptions(&self) -> api::MempoolInscriptions {
+    self.mempool.lock().unwrap().inscriptions()
+  }
+
+  pub(crate) fn get_mempool_runes(&self) -> Vec<api::MempoolRune> {
+    self.mempool.lock().unwrap().runes()
+  }
+
+  pub(crate) fn get_mempool_spender(&self, outpoint: OutPoint) -> Option<Txid> {
+    self.mempool.lock().unwrap().spender(outpoint)
+  }
+
   pub(crate) fn export(&self, filename: &String, include_addresses: bool) -> Result {
     let mut writer = BufWriter::new(fs::File::create(filename)?);
     let rtx = self.database.begin_read()?;
@@ -797,6 +951,44 @@ impl Index {
     self.begin_read()?.block_hash(height)
   }
 
+  pub fn events(&self, from: u64, take: usize) -> Result<Vec<(u64, Event)>> {
+    if !self.index_events {
+      bail!("event log requires index created with `--index-events` flag");
+    }
+
+    let mut events = Vec::new();
+
+    for result in self
+      .database
+      .begin_read()?
+      .open_table(EVENT_SEQUENCE_NUMBER_TO_EVENT)?
+      .range(from..)?
+      .take(take)
+    {
+      let (number, event) = result?;
+      events.push((number.value(), serde_json::from_slice(event.value())?));
+    }
+
+    Ok(events)
+  }
+
+  pub(crate) fn first_event_at_or_above_height(&self, height: u32) -> Result<Option<u64>> {
+    if !self.index_events {
+      bail!("event log requires index created with `--index-events` flag");
+    }
+
+    Ok(
+      self
+        .database
+        .begin_read()?
+        .open_table(HEIGHT_TO_FIRST_EVENT_SEQUENCE_NUMBER)?
+        .range(height..)?
+        .next()
+        .transpose()?
+        .map(|(_height, number)| number.value()),
+    )
+  }
+
   pub(crate) fn blocks(&self, take: usize) -> Result<Vec<(u32, BlockHash)>> {
     let rtx = self.begin_read()?;
 
@@ -844,6 +1036,64 @@ impl Index {
     )
   }
 
+  pub(crate) fn satribute_classifier(&self) -> Result<Classifier> {
+    Self::load_satribute_classifier(
+      &self
+        .database
+        .begin_read()?
+        .open_table(SATRIBUTE_TO_SAT_RANGES)?,
+    )
+  }
+
+  pub(crate) fn load_satribute_classifier(
+    satribute_to_sat_ranges: &impl ReadableTable<u8, &'static [u8]>,
+  ) -> Result<Classifier> {
+    let mut classifier = Classifier::default();
+
+    for result in satribute_to_sat_ranges.iter()? {
+      let (satribute, sat_ranges) = result?;
+
+      let satribute = Satribute::try_from(satribute.value())
+        .map_err(|n| anyhow!("invalid satribute {n} in index"))?;
+
+      for chunk in sat_ranges.value().chunks_exact(11) {
+        let (start, end) = SatRange::load(chunk.try_into().unwrap());
+        classifier.add_range(satribute, start, end);
+      }
+    }
+
+    Ok(classifier)
+  }
+
+  pub(crate) fn get_satribute_outputs_paginated(
+    &self,
+    satribute: Satribute,
+    page_size: usize,
+    page_index: usize,
+  ) -> Result<(Vec<OutPoint>, bool)> {
+    if !self.index_sats {
+      bail!("satribute queries require index created with `--index-sats` flag");
+    }
+
+    let mut outputs = self
+      .database
+      .begin_read()?
+      .open_multimap_table(SATRIBUTE_TO_OUTPOINT)?
+      .get(u8::from(satribute))?
+      .skip(page_index.saturating_mul(page_size))
+      .take(page_size.saturating_add(1))
+      .map(|result| Ok(OutPoint::load(*result?.value())))
+      .collect::<Result<Vec<OutPoint>>>()?;
+
+    let more = outputs.len() > page_size;
+
+    if more {
+      outputs.pop();
+    }
+
+    Ok((outputs, more))
+  }
+
   pub(crate) fn get_rune_by_id(&self, id: RuneId) -> Result<Option<Rune>> {
     Ok(
       self
@@ -949,6 +1199,146 @@ impl Index {
     Ok((entries, more))
   }
 
+  pub(crate) fn get_rune_holder_count(&self, id: RuneId) -> Result<u64> {
+    Ok(
+      self
+        .database
+        .begin_read()?
+        .open_table(RUNE_ID_TO_HOLDER_COUNT)?
+        .get(&id.store())?
+        .map(|count| count.value())
+        .unwrap_or_default(),
+    )
+  }
+
+  pub(crate) fn get_rune_holders_paginated(
+    &self,
+    id: RuneId,
+    page_size: usize,
+    page_index: usize,
+  ) -> Result<(Vec<(ScriptBuf, u128)>, bool)> {
+    let rtx = self.database.begin_read()?;
+
+    let id_and_balance_to_holder = rtx.open_multimap_table(RUNE_ID_AND_BALANCE_TO_HOLDER)?;
+
+    let mut holders = Vec::new();
+
+    for result in id_and_balance_to_holder
+      .range((id.store(), 0)..=(id.store(), u128::MAX))?
+      .rev()
+    {
+      let (key, scripts) = result?;
+
+      let (_id, balance) = key.value();
+
+      for script_pubkey in scripts {
+        holders.push((
+          ScriptBuf::from_bytes(script_pubkey?.value().to_vec()),
+          balance,
+        ));
+      }
+
+      if holders.len() > page_index.saturating_add(1).saturating_mul(page_size) {
+        break;
+      }
+    }
+
+    let holders = holders
+      .into_iter()
+      .skip(page_index.saturating_mul(page_size))
+      .take(page_size.saturating_add(1))
+      .collect::<Vec<(ScriptBuf, u128)>>();
+
+    let more = holders.len() > page_size;
+
+    Ok((holders.into_iter().take(page_size).collect(), more))
+  }
+
+  pub(crate) fn get_rune_history(
+    &self,
+    id: RuneId,
+    from: u32,
+    to: u32,
+  ) -> Result<Option<Vec<api::RuneHistoryBlock>>> {
+    let rtx = self.database.begin_read()?;
+
+    let Some(entry) = rtx
+      .open_table(RUNE_ID_TO_RUNE_ENTRY)?
+      .get(id.store())?
+      .map(|entry| RuneEntry::load(entry.value()))
+    else {
+      return Ok(None);
+    };
+
+    let amount = entry
+      .terms
+      .and_then(|terms| terms.amount)
+      .unwrap_or_default();
+
+    let mut blocks = Vec::new();
+
+    if from > to {
+      return Ok(Some(blocks));
+    }
+
+    for result in rtx
+      .open_table(RUNE_ID_AND_HEIGHT_TO_HISTORY_ENTRY)?
+      .range((id.store(), from)..=(id.store(), to))?
+    {
+      let (key, history) = result?;
+
+      let (_id, height) = key.value();
+
+      let history = RuneHistoryEntry::load(history.value());
+
+      blocks.push(api::RuneHistoryBlock {
+        burned: history.burned,
+        height,
+        mints: history.mints,
+        supply: Self::rune_supply(&entry, height, history.total_mints, amount),
+        total_burned: history.total_burned,
+        transferred: history.transferred,
+      });
+    }
+
+    Ok(Some(blocks))
+  }
+
+  pub(crate) fn get_rune_supply_at_height(&self, id: RuneId, height: u32) -> Result<Option<u128>> {
+    let rtx = self.database.begin_read()?;
+
+    let Some(entry) = rtx
+      .open_table(RUNE_ID_TO_RUNE_ENTRY)?
+      .get(id.store())?
+      .map(|entry| RuneEntry::load(entry.value()))
+    else {
+      return Ok(None);
+    };
+
+    let mints = rtx
+      .open_table(RUNE_ID_AND_HEIGHT_TO_HISTORY_ENTRY)?
+      .range((id.store(), 0)..=(id.store(), height))?
+      .next_back()
+      .transpose()?
+      .map(|(_key, history)| RuneHistoryEntry::load(history.value()).total_mints)
+      .unwrap_or_default();
+
+    let amount = entry
+      .terms
+      .and_then(|terms| terms.amount)
+      .unwrap_or_default();
+
+    Ok(Some(Self::rune_supply(&entry, height, mints, amount)))
+  }
+
+  fn rune_supply(entry: &RuneEntry, height: u32, mints: u128, amount: u128) -> u128 {
+    if u64::from(height) < entry.block {
+      return 0;
+    }
+
+    entry.premine + mints * amount
+  }
+
   pub(crate) fn encode_rune_balance(id: RuneId, balance: u128, buffer: &mut Vec<u8>) {
     varint::encode_to_vec(id.block.into(), buffer);
     varint::encode_to_vec(id.tx.into(), buffer);
@@ -1153,6 +1543,63 @@ impl Index {
     Ok((collections, more))
   }
 
+  pub(crate) fn get_collection(&self, parent: &InscriptionEntry) -> Result<api::Collection> {
+    let rtx = self.database.begin_read()?;
+
+    let sequence_number = parent.sequence_number;
+
+    let entry = rtx
+      .open_table(COLLECTION_TO_ENTRY)?
+      .get(sequence_number)?
+      .map(|entry| CollectionEntry::load(entry.value()));
+
+    let mut content_types = Vec::new();
+
+    for result in rtx
+      .open_table(COLLECTION_AND_CONTENT_TYPE_TO_COUNT)?
+      .range((sequence_number, None)..)?
+    {
+      let (key, count) = result?;
+      let (collection, content_type) = key.value();
+
+      if collection != sequence_number {
+        break;
+      }
+
+      content_types.push((
+        content_type.map(|content_type| String::from_utf8_lossy(content_type).into_owned()),
+        count.value(),
+      ));
+    }
+
+    content_types.sort_by_key(|(_content_type, count)| Reverse(*count));
+
+    let mints_per_block = rtx
+      .open_table(COLLECTION_AND_HEIGHT_TO_MINTS)?
+      .range((sequence_number, 0)..=(sequence_number, u32::MAX))?
+      .map(|result| {
+        result.map(|(key, mints)| {
+          let (_collection, height) = key.value();
+          (height, mints.value())
+        })
+      })
+      .collect::<Result<Vec<(u32, u64)>, StorageError>>()?;
+
+    let entry = entry.unwrap_or_default();
+
+    Ok(api::Collection {
+      children: entry.children,
+      content_types,
+      first_child_height: (entry.children > 0).then_some(entry.first_child_height),
+      grandchildren: entry.grandchildren,
+      holders: entry.holders,
+      id: parent.id,
+      last_child_height: (entry.children > 0).then_some(entry.last_child_height),
+      mints_per_block,
+      number: parent.inscription_number,
+    })
+  }
+
   #[cfg(test)]
   pub(crate) fn get_children_by_inscription_id(
     &self,
@@ -1221,34 +1668,124 @@ impl Index {
     sequence_number: u32,
     page_size: usize,
     page_index: usize,
+  ) -> Result<(Vec<InscriptionId>, bool)> {
+    self.get_inscriptions_by_key_paginated(
+      SEQUENCE_NUMBER_TO_CHILDREN,
+      sequence_number,
+      page_size,
+      page_index,
+    )
+  }
+
+  pub(crate) fn get_delegators_paginated(
+    &self,
+    delegate: InscriptionId,
+    page_size: usize,
+    page_index: usize,
+  ) -> Result<(Vec<InscriptionId>, bool)> {
+    self.get_inscriptions_by_key_paginated(
+      DELEGATE_TO_SEQUENCE_NUMBERS,
+      delegate.store(),
+      page_size,
+      page_index,
+    )
+  }
+
+  pub(crate) fn get_inscriptions_by_body_hash_paginated(
+    &self,
+    body_hash: sha256::Hash,
+    page_size: usize,
+    page_index: usize,
+  ) -> Result<(Vec<InscriptionId>, bool)> {
+    self.get_inscriptions_by_key_paginated(
+      BODY_HASH_TO_SEQUENCE_NUMBER,
+      &body_hash.to_byte_array(),
+      page_size,
+      page_index,
+    )
+  }
+
+  pub(crate) fn get_inscriptions_by_metadata_paginated(
+    &self,
+    key: &str,
+    value: &str,
+    page_size: usize,
+    page_index: usize,
+  ) -> Result<(Vec<InscriptionId>, bool)> {
+    if !self.index_metadata {
+      bail!("metadata queries require index created with `--index-metadata` flag");
+    }
+
+    self.get_inscriptions_by_key_paginated(
+      METADATA_KEY_AND_VALUE_TO_SEQUENCE_NUMBER,
+      (key, value),
+      page_size,
+      page_index,
+    )
+  }
+
+  pub(crate) fn get_inscriptions_by_metaprotocol_paginated(
+    &self,
+    metaprotocol: &str,
+    page_size: usize,
+    page_index: usize,
+  ) -> Result<(Vec<InscriptionId>, bool)> {
+    self.get_inscriptions_by_key_paginated(
+      METAPROTOCOL_TO_SEQUENCE_NUMBER,
+      metaprotocol,
+      page_size,
+      page_index,
+    )
+  }
+
+  fn get_inscriptions_by_key_paginated<'a, K: redb::Key + 'static>(
+    &self,
+    definition: MultimapTableDefinition<K, u32>,
+    key: impl Borrow<K::SelfType<'a>>,
+    page_size: usize,
+    page_index: usize,
   ) -> Result<(Vec<InscriptionId>, bool)> {
     let rtx = self.database.begin_read()?;
 
     let sequence_number_to_entry = rtx.open_table(SEQUENCE_NUMBER_TO_INSCRIPTION_ENTRY)?;
 
-    let mut children = rtx
-      .open_multimap_table(SEQUENCE_NUMBER_TO_CHILDREN)?
-      .get(sequence_number)?
-      .skip(page_index * page_size)
+    let mut inscriptions = rtx
+      .open_multimap_table(definition)?
+      .get(key)?
+      .skip(page_index.saturating_mul(page_size))
       .take(page_size.saturating_add(1))
       .map(|result| {
-        result
-          .and_then(|sequence_number| {
-            sequence_number_to_entry
-              .get(sequence_number.value())
-              .map(|entry| InscriptionEntry::load(entry.unwrap().value()).id)
-          })
-          .map_err(|err| err.into())
+        let sequence_number = result?.value();
+
+        let entry = sequence_number_to_entry
+          .get(sequence_number)?
+          .ok_or_else(|| anyhow!("no inscription entry for sequence number {sequence_number}"))?;
+
+        Ok(InscriptionEntry::load(entry.value()).id)
       })
       .collect::<Result<Vec<InscriptionId>>>()?;
 
-    let more = children.len() > page_size;
+    let more = inscriptions.len() > page_size;
 
     if more {
-      children.pop();
+      inscriptions.pop();
     }
 
-    Ok((children, more))
+    Ok((inscriptions, more))
+  }
+
+  pub(crate) fn get_metaprotocols(&self) -> Result<Vec<(String, u64)>> {
+    let mut metaprotocols = self
+      .database
+      .begin_read()?
+      .open_table(METAPROTOCOL_TO_COUNT)?
+      .iter()?
+      .map(|result| result.map(|(key, value)| (key.value().to_string(), value.value())))
+      .collect::<Result<Vec<(String, u64)>, StorageError>>()?;
+
+    metaprotocols.sort_by_key(|(_metaprotocol, count)| Reverse(*count));
+
+    Ok(metaprotocols)
   }
 
   pub(crate) fn get_parents_by_sequence_number_paginated(
@@ -1741,8 +2278,106 @@ impl Index {
     Ok((inscriptions, more))
   }
 
-  pub(crate) fn get_inscriptions_in_block(&self, block_height: u32) -> Result<Vec<InscriptionId>> {
-    let rtx = self.database.begin_read()?;
+  pub(crate) fn get_inscriptions_filtered(
+    &self,
+    query: &query::Inscriptions,
+    page_size: u32,
+    page_index: u32,
+  ) -> Result<(Vec<InscriptionId>, bool)> {
+    let rtx = self.database.begin_read()?;
+
+    let content_type_and_sequence_number = rtx.open_table(CONTENT_TYPE_AND_SEQUENCE_NUMBER)?;
+    let height_to_last_sequence_number = rtx.open_table(HEIGHT_TO_LAST_SEQUENCE_NUMBER)?;
+    let sequence_number_to_inscription_entry =
+      rtx.open_table(SEQUENCE_NUMBER_TO_INSCRIPTION_ENTRY)?;
+
+    // sequence numbers increase with height, so height bounds become sequence number bounds
+    let start = match query.min_height {
+      Some(min_height) => height_to_last_sequence_number
+        .range(..min_height)?
+        .next_back()
+        .transpose()?
+        .map(|(_height, sequence_number)| sequence_number.value())
+        .unwrap_or(0),
+      None => 0,
+    };
+
+    let end = match query.max_height {
+      Some(max_height) => height_to_last_sequence_number
+        .range(..=max_height)?
+        .next_back()
+        .transpose()?
+        .map(|(_height, sequence_number)| sequence_number.value())
+        .unwrap_or(0),
+      None => u32::MAX,
+    };
+
+    if start >= end {
+      return Ok((Vec::new(), false));
+    }
+
+    let sequence_numbers: Box<dyn DoubleEndedIterator<Item = Result<u32, StorageError>>> =
+      match &query.content_type {
+        Some(content_type) => Box::new(
+          content_type_and_sequence_number
+            .range((content_type.as_bytes(), start)..(content_type.as_bytes(), end))?
+            .map(|result| result.map(|(key, _value)| key.value().1)),
+        ),
+        None => Box::new(
+          sequence_number_to_inscription_entry
+            .range(start..end)?
+            .map(|result| result.map(|(sequence_number, _entry)| sequence_number.value())),
+        ),
+      };
+
+    let sequence_numbers: Box<dyn Iterator<Item = Result<u32, StorageError>>> = match query.order {
+      query::Order::Newest => Box::new(sequence_numbers.rev()),
+      query::Order::Oldest => Box::new(sequence_numbers),
+    };
+
+    let entry = |sequence_number: u32| -> Result<InscriptionEntry> {
+      Ok(InscriptionEntry::load(
+        sequence_number_to_inscription_entry
+          .get(sequence_number)?
+          .ok_or_else(|| anyhow!("no inscription entry for sequence number {sequence_number}"))?
+          .value(),
+      ))
+    };
+
+    let skip = usize::try_from(page_size.saturating_mul(page_index)).unwrap_or(usize::MAX);
+    let take = usize::try_from(page_size.saturating_add(1)).unwrap_or(usize::MAX);
+
+    let mut inscriptions = match query.charm {
+      // charms aren't indexed, so entries are read until the page is filled
+      Some(charm) => sequence_numbers
+        .map(|result| entry(result?))
+        .filter(|result| {
+          result
+            .as_ref()
+            .map_or(true, |entry| charm.is_set(entry.charms))
+        })
+        .skip(skip)
+        .take(take)
+        .map(|result| result.map(|entry| entry.id))
+        .collect::<Result<Vec<InscriptionId>>>()?,
+      None => sequence_numbers
+        .skip(skip)
+        .take(take)
+        .map(|result| Ok(entry(result?)?.id))
+        .collect::<Result<Vec<InscriptionId>>>()?,
+    };
+
+    let more = u32::try_from(inscriptions.len()).unwrap_or(u32::MAX) > page_size;
+
+    if more {
+      inscriptions.pop();
+    }
+
+    Ok((inscriptions, more))
+  }
+
+  pub(crate) fn get_inscriptions_in_block(&self, block_height: u32) -> Result<Vec<InscriptionId>> {
+    let rtx = self.database.begin_read()?;
 
     let height_to_last_sequence_number = rtx.open_table(HEIGHT_TO_LAST_SEQUENCE_NUMBER)?;
     let sequence_number_to_inscription_entry =
@@ -1860,24 +2495,7 @@ impl Index {
   ) -> Result<Option<(api::Inscription, Option<TxOut>, Inscription)>> {
     let rtx = self.database.begin_read()?;
 
-    let sequence_number = match query {
-      query::Inscription::Id(id) => rtx
-        .open_table(INSCRIPTION_ID_TO_SEQUENCE_NUMBER)?
-        .get(&id.store())?
-        .map(|guard| guard.value()),
-      query::Inscription::Number(inscription_number) => rtx
-        .open_table(INSCRIPTION_NUMBER_TO_SEQUENCE_NUMBER)?
-        .get(inscription_number)?
-        .map(|guard| guard.value()),
-      query::Inscription::Sat(sat) => rtx
-        .open_multimap_table(SAT_TO_SEQUENCE_NUMBER)?
-        .get(sat.n())?
-        .next()
-        .transpose()?
-        .map(|guard| guard.value()),
-    };
-
-    let Some(sequence_number) = sequence_number else {
+    let Some(sequence_number) = Self::query_sequence_number(&rtx, query)? else {
       return Ok(None);
     };
 
@@ -1992,6 +2610,32 @@ impl Index {
       Charm::Lost.set(&mut charms);
     }
 
+    let delegator_count = rtx
+      .open_multimap_table(DELEGATE_TO_SEQUENCE_NUMBERS)?
+      .get(&entry.id.store())?
+      .len();
+
+    let body_hash = inscription.body_hash();
+
+    let original = match body_hash {
+      Some(body_hash) => rtx
+        .open_multimap_table(BODY_HASH_TO_SEQUENCE_NUMBER)?
+        .get(&body_hash.to_byte_array())?
+        .next()
+        .transpose()?
+        .map(|original| original.value())
+        .filter(|original| *original != sequence_number)
+        .map(|original| -> Result<InscriptionId> {
+          let entry = sequence_number_to_inscription_entry
+            .get(original)?
+            .ok_or_else(|| anyhow!("no inscription entry for sequence number {original}"))?;
+
+          Ok(InscriptionEntry::load(entry.value()).id)
+        })
+        .transpose()?,
+      None => None,
+    };
+
     let effective_mime_type = if let Some(delegate_id) = inscription.delegate() {
       let delegate_result = self.get_inscription_by_id(delegate_id);
       if let Ok(Some(delegate)) = delegate_result {
@@ -2015,17 +2659,21 @@ impl Index {
               .ok()
           })
           .map(|address| address.to_string()),
+        body_hash,
         charms: Charm::charms(charms),
         children,
         content_length: inscription.content_length(),
         content_type: inscription.content_type().map(|s| s.to_string()),
+        delegator_count,
         effective_content_type: effective_mime_type,
         fee: entry.fee,
         height: entry.height,
         id: entry.id,
         next,
         number: entry.inscription_number,
+        original,
         parents,
+        pending: self.get_mempool_spender(satpoint.outpoint).is_some(),
         previous,
         rune,
         sat: entry.sat,
@@ -2038,6 +2686,46 @@ impl Index {
     )))
   }
 
+  fn query_sequence_number(
+    rtx: &ReadTransaction,
+    query: query::Inscription,
+  ) -> Result<Option<u32>> {
+    Ok(match query {
+      query::Inscription::Id(id) => rtx
+        .open_table(INSCRIPTION_ID_TO_SEQUENCE_NUMBER)?
+        .get(&id.store())?
+        .map(|guard| guard.value()),
+      query::Inscription::Number(inscription_number) => rtx
+        .open_table(INSCRIPTION_NUMBER_TO_SEQUENCE_NUMBER)?
+        .get(inscription_number)?
+        .map(|guard| guard.value()),
+      query::Inscription::Sat(sat) => rtx
+        .open_multimap_table(SAT_TO_SEQUENCE_NUMBER)?
+        .get(sat.n())?
+        .next()
+        .transpose()?
+        .map(|guard| guard.value()),
+    })
+  }
+
+  pub(crate) fn get_inscription_entry_by_query(
+    &self,
+    query: query::Inscription,
+  ) -> Result<Option<InscriptionEntry>> {
+    let rtx = self.database.begin_read()?;
+
+    let Some(sequence_number) = Self::query_sequence_number(&rtx, query)? else {
+      return Ok(None);
+    };
+
+    Ok(
+      rtx
+        .open_table(SEQUENCE_NUMBER_TO_INSCRIPTION_ENTRY)?
+        .get(sequence_number)?
+        .map(|value| InscriptionEntry::load(value.value())),
+    )
+  }
+
   pub(crate) fn get_inscription_entry(
     &self,
     inscription_id: InscriptionId,
@@ -2060,6 +2748,64 @@ impl Index {
     Ok(entry)
   }
 
+  pub(crate) fn get_inscription_sales(
+    &self,
+    inscription_id: InscriptionId,
+  ) -> Result<Option<Vec<SaleEntry>>> {
+    let rtx = self.database.begin_read()?;
+
+    let Some(sequence_number) = rtx
+      .open_table(INSCRIPTION_ID_TO_SEQUENCE_NUMBER)?
+      .get(&inscription_id.store())?
+      .map(|guard| guard.value())
+    else {
+      return Ok(None);
+    };
+
+    Ok(Some(
+      rtx
+        .open_table(SEQUENCE_NUMBER_TO_SALE_ENTRY)?
+        .range((sequence_number, 0)..=(sequence_number, u32::MAX))?
+        .map(|result| {
+          result
+            .map(|(_key, entry)| SaleEntry::load(entry.value()))
+            .map_err(|err| err.into())
+        })
+        .collect::<Result<Vec<SaleEntry>>>()?,
+    ))
+  }
+
+  pub(crate) fn get_inscription_history(
+    &self,
+    inscription_id: InscriptionId,
+  ) -> Result<Option<Vec<TransferEntry>>> {
+    if !self.index_transfers {
+      bail!("inscription history requires index created with `--index-transfers` flag");
+    }
+
+    let rtx = self.database.begin_read()?;
+
+    let Some(sequence_number) = rtx
+      .open_table(INSCRIPTION_ID_TO_SEQUENCE_NUMBER)?
+      .get(&inscription_id.store())?
+      .map(|guard| guard.value())
+    else {
+      return Ok(None);
+    };
+
+    Ok(Some(
+      rtx
+        .open_table(SEQUENCE_NUMBER_TO_TRANSFER_ENTRY)?
+        .range((sequence_number, 0)..=(sequence_number, u32::MAX))?
+        .map(|result| {
+          result
+            .map(|(_key, entry)| TransferEntry::load(entry.value()))
+            .map_err(|err| err.into())
+        })
+        .collect::<Result<Vec<TransferEntry>>>()?,
+    ))
+  }
+
   #[cfg(test)]
   fn assert_inscription_location(
     &self,
@@ -2226,6 +2972,8 @@ impl Index {
 
     let spent = self.is_output_spent(outpoint)?;
 
+    let pending = self.get_mempool_spender(outpoint).is_some();
+
     Ok(Some((
       api::Output::new(
         self.settings.chain(),
@@ -2236,15 +2984,79 @@ impl Index {
         runes,
         sat_ranges,
         spent,
+        pending,
       ),
       txout,
     )))
   }
+
+  pub(crate) fn get_address_info(&self, address: &Address) -> Result<api::AddressInfo> {
+    if !self.index_addresses {
+      bail!("address info requires index created with `--index-addresses` flag");
+    }
+
+    let rtx = self.database.begin_read()?;
+
+    let mut outputs = rtx
+      .open_multimap_table(SCRIPT_PUBKEY_TO_OUTPOINT)?
+      .get(address.script_pubkey().as_bytes())?
+      .map(|result| result.map(|outpoint| OutPoint::load(*outpoint.value())))
+      .collect::<Result<Vec<OutPoint>, StorageError>>()?;
+
+    outputs.sort();
+
+    let outpoint_to_txout = rtx.open_table(OUTPOINT_TO_TXOUT)?;
+
+    let mut cardinal_sat_balance = 0;
+    let mut inscriptions = Vec::new();
+    let mut runes = BTreeMap::<SpacedRune, Pile>::new();
+    let mut sat_balance = 0;
+
+    for outpoint in &outputs {
+      let value = outpoint_to_txout
+        .get(&outpoint.store())?
+        .map(|txout| txout.value().0)
+        .ok_or_else(|| anyhow!("output {outpoint} missing from address index"))?;
+
+      let output_inscriptions = self.get_inscriptions_on_output(*outpoint)?;
+      let output_runes = self.get_rune_balances_for_outpoint(*outpoint)?;
+
+      if output_inscriptions.is_empty() && output_runes.is_empty() {
+        cardinal_sat_balance += value;
+      }
+
+      sat_balance += value;
+
+      inscriptions.extend(output_inscriptions);
+
+      for (rune, pile) in output_runes {
+        runes
+          .entry(rune)
+          .and_modify(|balance| balance.amount += pile.amount)
+          .or_insert(pile);
+      }
+    }
+
+    Ok(api::AddressInfo {
+      cardinal_sat_balance,
+      inscriptions,
+      outputs,
+      runes,
+      sat_balance,
+    })
+  }
 }
 
 #[cfg(test)]
 mod tests {
-  use {super::*, crate::index::testing::Context};
+  use {
+    super::*,
+    crate::{
+      index::{event::Allocation, testing::Context},
+      subcommand::index::verify::Problem,
+    },
+    ordinals::Flaw,
+  };
 
   #[test]
   fn height_limit() {
@@ -4436,9 +5248,7 @@ mod tests {
 
   #[test]
   fn recover_from_reorg() {
-    for mut context in Context::configurations() {
-      context.index.set_durability(redb::Durability::Immediate);
-
+    for context in Context::configurations() {
       context.mine_blocks(1);
 
       let txid = context.core.broadcast_tx(TransactionTemplate {
@@ -4496,9 +5306,7 @@ mod tests {
 
   #[test]
   fn recover_from_3_block_deep_and_consecutive_reorg() {
-    for mut context in Context::configurations() {
-      context.index.set_durability(redb::Durability::Immediate);
-
+    for context in Context::configurations() {
       context.mine_blocks(1);
 
       let txid = context.core.broadcast_tx(TransactionTemplate {
@@ -4559,9 +5367,7 @@ mod tests {
 
   #[test]
   fn recover_from_very_unlikely_7_block_deep_reorg() {
-    for mut context in Context::configurations() {
-      context.index.set_durability(redb::Durability::Immediate);
-
+    for context in Context::configurations() {
       context.mine_blocks(1);
 
       let txid = context.core.broadcast_tx(TransactionTemplate {
@@ -4618,6 +5424,59 @@ mod tests {
     }
   }
 
+  #[test]
+  fn recover_from_reorg_deeper_than_twenty_blocks() {
+    for context in Context::configurations() {
+      context.mine_blocks(1);
+
+      let txid = context.core.broadcast_tx(TransactionTemplate {
+        inputs: &[(
+          1,
+          0,
+          0,
+          inscription("text/plain;charset=utf-8", "hello").to_witness(),
+        )],
+        ..default()
+      });
+      let first_id = InscriptionId { txid, index: 0 };
+      let first_location = SatPoint {
+        outpoint: OutPoint { txid, vout: 0 },
+        offset: 0,
+      };
+
+      context.mine_blocks(1);
+
+      let txid = context.core.broadcast_tx(TransactionTemplate {
+        inputs: &[(
+          2,
+          0,
+          0,
+          inscription("text/plain;charset=utf-8", "hello").to_witness(),
+        )],
+        ..default()
+      });
+      let second_id = InscriptionId { txid, index: 0 };
+
+      context.mine_blocks(30);
+
+      assert!(context.index.inscription_exists(second_id).unwrap());
+
+      for _ in 0..30 {
+        context.core.invalidate_tip();
+      }
+
+      context.mine_blocks(32);
+
+      assert!(!context.index.inscription_exists(second_id).unwrap());
+
+      context
+        .index
+        .assert_inscription_location(first_id, first_location, Some(50 * COIN_VALUE));
+
+      assert_eq!(context.index.block_count().unwrap(), 35);
+    }
+  }
+
   #[test]
   fn inscription_without_parent_tag_has_no_parent_entry() {
     for context in Context::configurations() {
@@ -6191,6 +7050,60 @@ mod tests {
     assert!(context.index.list(outpoint).unwrap().is_some());
   }
 
+  #[test]
+  fn address_index_tracks_unspent_outputs() {
+    let context = Context::builder().arg("--index-addresses").build();
+
+    context.mine_blocks(2);
+
+    let coinbase = context.core.tx(1, 0);
+
+    let miner = Chain::Regtest
+      .address_from_script(&coinbase.output[0].script_pubkey)
+      .unwrap();
+
+    let info = context.index.get_address_info(&miner).unwrap();
+
+    assert_eq!(
+      info.outputs,
+      [OutPoint {
+        txid: coinbase.txid(),
+        vout: 0,
+      }]
+    );
+    assert_eq!(info.sat_balance, 50 * COIN_VALUE);
+    assert_eq!(info.cardinal_sat_balance, 50 * COIN_VALUE);
+
+    let txid = context.core.broadcast_tx(TransactionTemplate {
+      inputs: &[(1, 0, 0, inscription("text/plain", "foo").to_witness())],
+      outputs: 2,
+      ..default()
+    });
+
+    context.mine_blocks(1);
+
+    assert!(context
+      .index
+      .get_address_info(&miner)
+      .unwrap()
+      .outputs
+      .is_empty());
+
+    let recipient = Chain::Regtest
+      .address_from_script(&ScriptBuf::new_v0_p2wpkh(&WPubkeyHash::all_zeros()))
+      .unwrap();
+
+    let info = context.index.get_address_info(&recipient).unwrap();
+
+    assert_eq!(
+      info.outputs,
+      [OutPoint { txid, vout: 0 }, OutPoint { txid, vout: 1 }]
+    );
+    assert_eq!(info.inscriptions, [InscriptionId { txid, index: 0 }]);
+    assert_eq!(info.sat_balance, 50 * COIN_VALUE);
+    assert_eq!(info.cardinal_sat_balance, 25 * COIN_VALUE);
+  }
+
   #[test]
   fn is_output_spent() {
     let context = Context::builder().build();
@@ -6295,6 +7208,15 @@ mod tests {
     }
   }
 
+  fn next_non_block_event(event_receiver: &mut tokio::sync::mpsc::Receiver<Event>) -> Event {
+    loop {
+      match event_receiver.blocking_recv().unwrap() {
+        Event::BlockStarted { .. } | Event::BlockCommitted { .. } => {}
+        event => return event,
+      }
+    }
+  }
+
   #[test]
   fn inscription_event_sender_channel() {
     let (event_sender, mut event_receiver) = tokio::sync::mpsc::channel(1024);
@@ -6316,8 +7238,13 @@ mod tests {
       txid: create_txid,
       index: 0,
     };
-    let create_event = event_receiver.blocking_recv().unwrap();
+    let create_event = next_non_block_event(&mut event_receiver);
     let expected_charms = if context.index.index_sats { 513 } else { 0 };
+    let address = uncheck(
+      &Chain::Regtest
+        .address_from_script(&ScriptBuf::new_v0_p2wpkh(&WPubkeyHash::all_zeros()))
+        .unwrap(),
+    );
     assert_eq!(
       create_event,
       Event::InscriptionCreated {
@@ -6332,7 +7259,14 @@ mod tests {
         sequence_number: 0,
         block_height: 2,
         charms: expected_charms,
+        content_type: None,
+        delegate: None,
+        fee: 0,
+        metaprotocol: None,
         parent_inscription_ids: Vec::new(),
+        receiver: Some(address.clone()),
+        txid: create_txid,
+        value: Some(50 * COIN_VALUE),
       }
     );
 
@@ -6346,7 +7280,7 @@ mod tests {
 
     context.mine_blocks(1);
 
-    let transfer_event = event_receiver.blocking_recv().unwrap();
+    let transfer_event = next_non_block_event(&mut event_receiver);
     assert_eq!(
       transfer_event,
       Event::InscriptionTransferred {
@@ -6359,6 +7293,7 @@ mod tests {
           },
           offset: 0
         },
+        new_value: Some(50 * COIN_VALUE),
         old_location: SatPoint {
           outpoint: OutPoint {
             txid: create_txid,
@@ -6366,245 +7301,2102 @@ mod tests {
           },
           offset: 0
         },
+        old_value: 50 * COIN_VALUE,
+        receiver: Some(address.clone()),
+        sender: Some(address),
         sequence_number: 0,
+        charms: expected_charms,
+        txid: transfer_txid,
       }
     );
   }
 
   #[test]
-  fn rune_event_sender_channel() {
-    const RUNE: u128 = 99246114928149462;
-
+  fn inscription_events_are_self_contained() {
     let (event_sender, mut event_receiver) = tokio::sync::mpsc::channel(1024);
     let context = Context::builder()
-      .arg("--index-runes")
+      .arg("--index-events")
       .event_sender(event_sender)
       .build();
 
-    let (txid0, id) = context.etch(
-      Runestone {
-        etching: Some(Etching {
-          rune: Some(Rune(RUNE)),
-          terms: Some(Terms {
-            amount: Some(1000),
-            cap: Some(100),
-            ..default()
-          }),
-          ..default()
-        }),
-        ..default()
-      },
-      1,
-    );
+    context.mine_blocks(1);
 
-    context.assert_runes(
-      [(
-        id,
-        RuneEntry {
-          block: id.block,
-          etching: txid0,
-          spaced_rune: SpacedRune {
-            rune: Rune(RUNE),
-            spacers: 0,
-          },
-          timestamp: id.block,
-          mints: 0,
-          terms: Some(Terms {
-            amount: Some(1000),
-            cap: Some(100),
-            ..default()
-          }),
+    let delegate = inscription_id(1);
+
+    let create_txid = context.core.broadcast_tx(TransactionTemplate {
+      inputs: &[(
+        1,
+        0,
+        0,
+        Inscription {
+          content_type: Some("text/plain".into()),
+          delegate: Some(delegate.value()),
+          metaprotocol: Some("foo".into()),
           ..default()
-        },
+        }
+        .to_witness(),
       )],
-      [],
+      fee: 100,
+      outputs: 1,
+      ..default()
+    });
+
+    context.mine_blocks(1);
+
+    let inscription_id = InscriptionId {
+      txid: create_txid,
+      index: 0,
+    };
+
+    let location = SatPoint {
+      outpoint: OutPoint {
+        txid: create_txid,
+        vout: 0,
+      },
+      offset: 0,
+    };
+
+    let creator = uncheck(
+      &Chain::Regtest
+        .address_from_script(&ScriptBuf::new_v0_p2wpkh(&WPubkeyHash::all_zeros()))
+        .unwrap(),
     );
 
     assert_eq!(
-      event_receiver.blocking_recv().unwrap(),
-      Event::RuneEtched {
-        block_height: 8,
-        txid: txid0,
-        rune_id: id,
+      next_non_block_event(&mut event_receiver),
+      Event::InscriptionCreated {
+        block_height: 2,
+        charms: 0,
+        content_type: Some("text/plain".into()),
+        delegate: Some(delegate),
+        fee: 100,
+        inscription_id,
+        location: Some(location),
+        metaprotocol: Some("foo".into()),
+        parent_inscription_ids: Vec::new(),
+        receiver: Some(creator.clone()),
+        sequence_number: 0,
+        txid: create_txid,
+        value: Some(50 * COIN_VALUE - 100),
       }
     );
 
-    let txid1 = context.core.broadcast_tx(TransactionTemplate {
-      inputs: &[(2, 0, 0, Witness::new())],
-      op_return: Some(
-        Runestone {
-          mint: Some(id),
-          ..default()
-        }
-        .encipher(),
-      ),
+    let transfer_txid = context.core.broadcast_tx(TransactionTemplate {
+      inputs: &[(2, 1, 0, Default::default())],
+      fee: 0,
+      outputs: 1,
+      p2tr: true,
       ..default()
     });
 
     context.mine_blocks(1);
 
-    context.assert_runes(
-      [(
-        id,
-        RuneEntry {
-          block: id.block,
-          etching: txid0,
-          terms: Some(Terms {
-            amount: Some(1000),
-            cap: Some(100),
-            ..default()
-          }),
-          mints: 1,
-          spaced_rune: SpacedRune {
-            rune: Rune(RUNE),
-            spacers: 0,
-          },
-          premine: 0,
-          timestamp: id.block,
-          ..default()
-        },
-      )],
-      [(
-        OutPoint {
-          txid: txid1,
-          vout: 0,
-        },
-        vec![(id, 1000)],
-      )],
+    let recipient = uncheck(
+      &Chain::Regtest
+        .address_from_script(&context.core.tx_by_id(transfer_txid).output[0].script_pubkey)
+        .unwrap(),
     );
 
     assert_eq!(
-      event_receiver.blocking_recv().unwrap(),
-      Event::RuneMinted {
-        block_height: 9,
-        txid: txid1,
-        rune_id: id,
-        amount: 1000,
+      next_non_block_event(&mut event_receiver),
+      Event::InscriptionTransferred {
+        block_height: 3,
+        charms: 0,
+        inscription_id,
+        new_location: SatPoint {
+          outpoint: OutPoint {
+            txid: transfer_txid,
+            vout: 0,
+          },
+          offset: 0,
+        },
+        new_value: Some(50 * COIN_VALUE - 100),
+        old_location: location,
+        old_value: 50 * COIN_VALUE - 100,
+        receiver: Some(recipient),
+        sender: Some(creator),
+        sequence_number: 0,
+        txid: transfer_txid,
       }
     );
+  }
 
-    let txid2 = context.core.broadcast_tx(TransactionTemplate {
-      inputs: &[(9, 1, 0, Witness::new())],
-      op_return: Some(
-        Runestone {
-          edicts: vec![Edict {
-            id,
-            amount: 1000,
-            output: 0,
-          }],
-          ..Default::default()
-        }
-        .encipher(),
-      ),
-      ..Default::default()
+  #[test]
+  fn inscriptions_spent_as_fees_are_attributed_to_spending_transaction() {
+    let (event_sender, mut event_receiver) = tokio::sync::mpsc::channel(1024);
+    let context = Context::builder()
+      .arg("--index-events")
+      .event_sender(event_sender)
+      .build();
+
+    context.mine_blocks(1);
+
+    context.core.broadcast_tx(TransactionTemplate {
+      inputs: &[(1, 0, 0, inscription("text/plain", "hello").to_witness())],
+      outputs: 1,
+      ..default()
     });
 
     context.mine_blocks(1);
 
-    context.assert_runes(
-      [(
-        id,
-        RuneEntry {
-          block: 8,
-          etching: txid0,
-          spaced_rune: SpacedRune {
-            rune: Rune(RUNE),
-            ..default()
-          },
-          terms: Some(Terms {
-            amount: Some(1000),
-            cap: Some(100),
-            ..Default::default()
-          }),
-          timestamp: 8,
-          mints: 1,
-          ..Default::default()
-        },
-      )],
-      [(
-        OutPoint {
-          txid: txid2,
-          vout: 0,
-        },
-        vec![(id, 1000)],
-      )],
-    );
+    next_non_block_event(&mut event_receiver);
 
-    event_receiver.blocking_recv().unwrap();
+    let transfer_txid = context.core.broadcast_tx(TransactionTemplate {
+      inputs: &[(2, 1, 0, Default::default())],
+      fee: 50 * COIN_VALUE,
+      outputs: 0,
+      ..default()
+    });
 
-    pretty_assert_eq!(
-      event_receiver.blocking_recv().unwrap(),
-      Event::RuneTransferred {
-        block_height: 10,
-        txid: txid2,
-        rune_id: id,
-        amount: 1000,
-        outpoint: OutPoint {
-          txid: txid2,
-          vout: 0,
-        },
-      }
+    let coinbase = context.mine_blocks(1)[0].txdata[0].clone();
+
+    let miner = uncheck(
+      &Chain::Regtest
+        .address_from_script(&coinbase.output[0].script_pubkey)
+        .unwrap(),
     );
 
-    let txid3 = context.core.broadcast_tx(TransactionTemplate {
-      inputs: &[(10, 1, 0, Witness::new())],
-      op_return: Some(
-        Runestone {
-          edicts: vec![Edict {
-            id,
-            amount: 111,
-            output: 0,
-          }],
-          ..Default::default()
-        }
-        .encipher(),
-      ),
-      op_return_index: Some(0),
-      ..Default::default()
+    assert_matches!(
+      next_non_block_event(&mut event_receiver),
+      Event::InscriptionTransferred {
+        new_location: SatPoint { outpoint, .. },
+        receiver: Some(receiver),
+        txid,
+        ..
+      } if outpoint.txid == coinbase.txid() && receiver == miner && txid == transfer_txid
+    );
+  }
+
+  #[test]
+  fn listed_inscription_transfers_are_recorded_as_sales() {
+    let (event_sender, mut event_receiver) = tokio::sync::mpsc::channel(1024);
+    let context = Context::builder().event_sender(event_sender).build();
+
+    context.mine_blocks(2);
+
+    let create_txid = context.core.broadcast_tx(TransactionTemplate {
+      inputs: &[(1, 0, 0, inscription("text/plain", "hello").to_witness())],
+      outputs: 1,
+      ..default()
     });
 
     context.mine_blocks(1);
 
-    context.assert_runes(
-      [(
-        id,
-        RuneEntry {
-          block: 8,
-          etching: txid0,
-          spaced_rune: SpacedRune {
-            rune: Rune(RUNE),
-            ..default()
-          },
-          terms: Some(Terms {
-            amount: Some(1000),
-            cap: Some(100),
-            ..Default::default()
-          }),
-          timestamp: 8,
-          mints: 1,
-          burned: 111,
-          ..Default::default()
-        },
-      )],
-      [(
-        OutPoint {
-          txid: txid3,
-          vout: 1,
-        },
-        vec![(id, 889)],
-      )],
-    );
-
-    event_receiver.blocking_recv().unwrap();
+    next_non_block_event(&mut event_receiver);
 
-    pretty_assert_eq!(
-      event_receiver.blocking_recv().unwrap(),
+    let inscription_id = InscriptionId {
+      txid: create_txid,
+      index: 0,
+    };
+
+    let mut signature = vec![0; 64];
+    signature.push(0x83);
+
+    let sale_txid = context.core.broadcast_tx(TransactionTemplate {
+      inputs: &[
+        (2, 0, 0, Default::default()),
+        (3, 1, 0, Witness::from_slice(&[signature])),
+      ],
+      outputs: 2,
+      output_values: &[60 * COIN_VALUE, 40 * COIN_VALUE],
+      p2tr: true,
+      ..default()
+    });
+
+    context.mine_blocks(1);
+
+    assert_matches!(
+      next_non_block_event(&mut event_receiver),
+      Event::InscriptionTransferred { .. }
+    );
+
+    let sale = context.core.tx_by_id(sale_txid);
+
+    let address = |vout: usize| {
+      uncheck(
+        &Chain::Regtest
+          .address_from_script(&sale.output[vout].script_pubkey)
+          .unwrap(),
+      )
+    };
+
+    assert_eq!(
+      next_non_block_event(&mut event_receiver),
+      Event::InscriptionSold {
+        block_height: 4,
+        buyer: Some(address(0)),
+        inscription_id,
+        price: 40 * COIN_VALUE,
+        seller: Some(address(1)),
+        txid: sale_txid,
+      }
+    );
+
+    assert_eq!(
+      context.index.get_inscription_sales(inscription_id).unwrap(),
+      Some(vec![SaleEntry {
+        buyer: sale.output[0].script_pubkey.clone(),
+        height: 4,
+        price: 40 * COIN_VALUE,
+        seller: sale.output[1].script_pubkey.clone(),
+        txid: sale_txid,
+      }])
+    );
+  }
+
+  #[test]
+  fn listing_price_is_split_between_inscriptions_on_input() {
+    let context = Context::builder().build();
+
+    context.mine_blocks(3);
+
+    let a = context.core.broadcast_tx(TransactionTemplate {
+      inputs: &[(1, 0, 0, inscription("text/plain", "a").to_witness())],
+      ..default()
+    });
+
+    let b = context.core.broadcast_tx(TransactionTemplate {
+      inputs: &[(2, 0, 0, inscription("text/plain", "b").to_witness())],
+      ..default()
+    });
+
+    context.mine_blocks(1);
+
+    context.core.broadcast_tx(TransactionTemplate {
+      inputs: &[(4, 1, 0, Default::default()), (4, 2, 0, Default::default())],
+      ..default()
+    });
+
+    context.mine_blocks(1);
+
+    let mut signature = vec![0; 64];
+    signature.push(0x83);
+
+    context.core.broadcast_tx(TransactionTemplate {
+      inputs: &[
+        (3, 0, 0, Default::default()),
+        (5, 1, 0, Witness::from_slice(&[signature])),
+      ],
+      outputs: 2,
+      output_values: &[120 * COIN_VALUE - 1, 30 * COIN_VALUE + 1],
+      p2tr: true,
+      ..default()
+    });
+
+    context.mine_blocks(1);
+
+    let price = |txid| {
+      context
+        .index
+        .get_inscription_sales(InscriptionId { txid, index: 0 })
+        .unwrap()
+        .unwrap()
+        .into_iter()
+        .map(|sale| sale.price)
+        .collect::<Vec<u64>>()
+    };
+
+    assert_eq!(price(a), [15 * COIN_VALUE + 1]);
+    assert_eq!(price(b), [15 * COIN_VALUE]);
+  }
+
+  #[test]
+  fn unlisted_inscription_transfers_are_not_sales() {
+    let context = Context::builder().build();
+
+    context.mine_blocks(2);
+
+    let create_txid = context.core.broadcast_tx(TransactionTemplate {
+      inputs: &[(1, 0, 0, inscription("text/plain", "hello").to_witness())],
+      outputs: 1,
+      ..default()
+    });
+
+    context.mine_blocks(1);
+
+    let inscription_id = InscriptionId {
+      txid: create_txid,
+      index: 0,
+    };
+
+    context.core.broadcast_tx(TransactionTemplate {
+      inputs: &[(2, 0, 0, Default::default()), (3, 1, 0, Default::default())],
+      outputs: 2,
+      output_values: &[60 * COIN_VALUE, 40 * COIN_VALUE],
+      p2tr: true,
+      ..default()
+    });
+
+    context.mine_blocks(1);
+
+    let mut signature = vec![0; 64];
+    signature.push(0x83);
+
+    context.core.broadcast_tx(TransactionTemplate {
+      inputs: &[(4, 1, 0, Witness::from_slice(&[signature]))],
+      outputs: 1,
+      ..default()
+    });
+
+    context.mine_blocks(1);
+
+    assert_eq!(
+      context.index.get_inscription_sales(inscription_id).unwrap(),
+      Some(Vec::new())
+    );
+
+    assert_eq!(
+      context
+        .index
+        .get_inscription_sales(InscriptionId {
+          txid: create_txid,
+          index: 1,
+        })
+        .unwrap(),
+      None
+    );
+  }
+
+  #[test]
+  fn inscription_transfers_are_recorded_in_history() {
+    let context = Context::builder().arg("--index-transfers").build();
+
+    context.mine_blocks(2);
+
+    let create_txid = context.core.broadcast_tx(TransactionTemplate {
+      inputs: &[(1, 0, 0, inscription("text/plain", "hello").to_witness())],
+      outputs: 1,
+      ..default()
+    });
+
+    context.mine_blocks(1);
+
+    let inscription_id = InscriptionId {
+      txid: create_txid,
+      index: 0,
+    };
+
+    let first_txid = context.core.broadcast_tx(TransactionTemplate {
+      inputs: &[(3, 1, 0, Default::default())],
+      p2tr: true,
+      ..default()
+    });
+
+    context.mine_blocks(1);
+
+    let second_txid = context.core.broadcast_tx(TransactionTemplate {
+      inputs: &[(4, 1, 0, Default::default())],
+      ..default()
+    });
+
+    context.mine_blocks(1);
+
+    let create = context.core.tx_by_id(create_txid);
+    let first = context.core.tx_by_id(first_txid);
+    let second = context.core.tx_by_id(second_txid);
+
+    let create_satpoint = SatPoint {
+      outpoint: OutPoint {
+        txid: create_txid,
+        vout: 0,
+      },
+      offset: 0,
+    };
+
+    let first_satpoint = SatPoint {
+      outpoint: OutPoint {
+        txid: first_txid,
+        vout: 0,
+      },
+      offset: 0,
+    };
+
+    let second_satpoint = SatPoint {
+      outpoint: OutPoint {
+        txid: second_txid,
+        vout: 0,
+      },
+      offset: 0,
+    };
+
+    assert_eq!(
+      context
+        .index
+        .get_inscription_history(inscription_id)
+        .unwrap(),
+      Some(vec![
+        TransferEntry {
+          from: None,
+          height: 3,
+          new_satpoint: create_satpoint,
+          old_satpoint: None,
+          to: Some(create.output[0].script_pubkey.clone()),
+          txid: create_txid,
+        },
+        TransferEntry {
+          from: Some(create.output[0].script_pubkey.clone()),
+          height: 4,
+          new_satpoint: first_satpoint,
+          old_satpoint: Some(create_satpoint),
+          to: Some(first.output[0].script_pubkey.clone()),
+          txid: first_txid,
+        },
+        TransferEntry {
+          from: Some(first.output[0].script_pubkey.clone()),
+          height: 5,
+          new_satpoint: second_satpoint,
+          old_satpoint: Some(first_satpoint),
+          to: Some(second.output[0].script_pubkey.clone()),
+          txid: second_txid,
+        },
+      ])
+    );
+
+    assert_eq!(
+      context
+        .index
+        .get_inscription_history(InscriptionId {
+          txid: create_txid,
+          index: 1,
+        })
+        .unwrap(),
+      None
+    );
+  }
+
+  #[test]
+  fn unbound_inscription_history_has_no_receiver() {
+    let context = Context::builder().arg("--index-transfers").build();
+
+    context.mine_blocks(1);
+
+    context.core.broadcast_tx(TransactionTemplate {
+      inputs: &[(1, 0, 0, Default::default())],
+      fee: 50 * 100_000_000,
+      ..default()
+    });
+
+    context.mine_blocks(1);
+
+    let txid = context.core.broadcast_tx(TransactionTemplate {
+      inputs: &[(2, 1, 0, inscription("text/plain", "hello").to_witness())],
+      ..default()
+    });
+
+    context.mine_blocks(1);
+
+    assert_eq!(
+      context
+        .index
+        .get_inscription_history(InscriptionId { txid, index: 0 })
+        .unwrap(),
+      Some(vec![TransferEntry {
+        from: None,
+        height: 3,
+        new_satpoint: SatPoint {
+          outpoint: unbound_outpoint(),
+          offset: 0,
+        },
+        old_satpoint: None,
+        to: None,
+        txid,
+      }])
+    );
+  }
+
+  #[test]
+  fn inscriptions_can_be_filtered() {
+    let context = Context::builder().arg("--index-sats").build();
+
+    context.mine_blocks(3);
+
+    let a = context.core.broadcast_tx(TransactionTemplate {
+      inputs: &[(1, 0, 0, inscription("image/png", "a").to_witness())],
+      ..default()
+    });
+
+    context.mine_blocks(1);
+
+    let a = InscriptionId { txid: a, index: 0 };
+
+    let b = context.core.broadcast_tx(TransactionTemplate {
+      inputs: &[(2, 0, 0, inscription("text/plain", "b").to_witness())],
+      ..default()
+    });
+
+    context.mine_blocks(1);
+
+    let b = InscriptionId { txid: b, index: 0 };
+
+    let c = context.core.broadcast_tx(TransactionTemplate {
+      inputs: &[(
+        3,
+        0,
+        0,
+        Inscription {
+          delegate: Some(a.value()),
+          ..default()
+        }
+        .to_witness(),
+      )],
+      ..default()
+    });
+
+    context.mine_blocks(1);
+
+    let c = InscriptionId { txid: c, index: 0 };
+
+    let filtered = |query: query::Inscriptions| {
+      context
+        .index
+        .get_inscriptions_filtered(&query, 100, 0)
+        .unwrap()
+        .0
+    };
+
+    assert_eq!(filtered(default()), [c, b, a]);
+
+    assert_eq!(
+      filtered(query::Inscriptions {
+        order: query::Order::Oldest,
+        ..default()
+      }),
+      [a, b, c]
+    );
+
+    assert_eq!(
+      filtered(query::Inscriptions {
+        content_type: Some("image/png".into()),
+        ..default()
+      }),
+      [c, a]
+    );
+
+    assert_eq!(
+      filtered(query::Inscriptions {
+        content_type: Some("text/plain".into()),
+        ..default()
+      }),
+      [b]
+    );
+
+    assert_eq!(
+      filtered(query::Inscriptions {
+        min_height: Some(5),
+        ..default()
+      }),
+      [c, b]
+    );
+
+    assert_eq!(
+      filtered(query::Inscriptions {
+        min_height: Some(5),
+        max_height: Some(5),
+        ..default()
+      }),
+      [b]
+    );
+
+    assert_eq!(
+      filtered(query::Inscriptions {
+        content_type: Some("image/png".into()),
+        max_height: Some(5),
+        ..default()
+      }),
+      [a]
+    );
+
+    assert_eq!(
+      filtered(query::Inscriptions {
+        max_height: Some(3),
+        ..default()
+      }),
+      []
+    );
+
+    assert_eq!(
+      filtered(query::Inscriptions {
+        charm: Some(Charm::Uncommon),
+        ..default()
+      }),
+      [c, b, a]
+    );
+
+    assert_eq!(
+      filtered(query::Inscriptions {
+        charm: Some(Charm::Cursed),
+        ..default()
+      }),
+      []
+    );
+
+    assert_eq!(
+      context
+        .index
+        .get_inscriptions_filtered(&default(), 2, 0)
+        .unwrap(),
+      (vec![c, b], true)
+    );
+
+    assert_eq!(
+      context
+        .index
+        .get_inscriptions_filtered(&default(), 2, 1)
+        .unwrap(),
+      (vec![a], false)
+    );
+  }
+
+  #[test]
+  fn delegators_are_indexed() {
+    let context = Context::builder().build();
+
+    context.mine_blocks(1);
+
+    let delegate = context.core.broadcast_tx(TransactionTemplate {
+      inputs: &[(1, 0, 0, inscription("text/plain", "foo").to_witness())],
+      ..default()
+    });
+
+    context.mine_blocks(2);
+
+    let delegate = InscriptionId {
+      txid: delegate,
+      index: 0,
+    };
+
+    let mut delegators = Vec::new();
+
+    for block in [2, 3] {
+      let txid = context.core.broadcast_tx(TransactionTemplate {
+        inputs: &[(
+          block,
+          0,
+          0,
+          Inscription {
+            delegate: Some(delegate.value()),
+            ..default()
+          }
+          .to_witness(),
+        )],
+        ..default()
+      });
+
+      context.mine_blocks(1);
+
+      delegators.push(InscriptionId { txid, index: 0 });
+    }
+
+    assert_eq!(
+      context
+        .index
+        .get_delegators_paginated(delegate, 100, 0)
+        .unwrap(),
+      (delegators.clone(), false)
+    );
+
+    assert_eq!(
+      context
+        .index
+        .get_delegators_paginated(delegate, 1, 0)
+        .unwrap(),
+      (vec![delegators[0]], true)
+    );
+
+    assert_eq!(
+      context
+        .index
+        .get_delegators_paginated(delegators[0], 100, 0)
+        .unwrap(),
+      (Vec::new(), false)
+    );
+
+    assert_eq!(
+      context
+        .index
+        .inscription_info(query::Inscription::Id(delegate))
+        .unwrap()
+        .unwrap()
+        .0
+        .delegator_count,
+      2
+    );
+  }
+
+  #[test]
+  fn inscriptions_are_indexed_by_body_hash() {
+    let context = Context::builder().build();
+
+    context.mine_blocks(3);
+
+    let mut ids = Vec::new();
+
+    for (block, body) in [(1, "foo"), (2, "bar"), (3, "foo")] {
+      let txid = context.core.broadcast_tx(TransactionTemplate {
+        inputs: &[(block, 0, 0, inscription("text/plain", body).to_witness())],
+        ..default()
+      });
+
+      context.mine_blocks(1);
+
+      ids.push(InscriptionId { txid, index: 0 });
+    }
+
+    let foo = inscription("text/plain", "foo").body_hash().unwrap();
+
+    assert_eq!(
+      context
+        .index
+        .get_inscriptions_by_body_hash_paginated(foo, 100, 0)
+        .unwrap(),
+      (vec![ids[0], ids[2]], false)
+    );
+
+    assert_eq!(
+      context
+        .index
+        .get_inscriptions_by_body_hash_paginated(foo, 1, 0)
+        .unwrap(),
+      (vec![ids[0]], true)
+    );
+
+    let original = |id| {
+      context
+        .index
+        .inscription_info(query::Inscription::Id(id))
+        .unwrap()
+        .unwrap()
+        .0
+        .original
+    };
+
+    assert_eq!(original(ids[0]), None);
+    assert_eq!(original(ids[1]), None);
+    assert_eq!(original(ids[2]), Some(ids[0]));
+  }
+
+  #[test]
+  fn inscriptions_are_indexed_by_metaprotocol() {
+    for context in Context::configurations() {
+      context.mine_blocks(3);
+
+      let mut ids = Vec::new();
+
+      for (block, metaprotocol) in [
+        (1, Some("foo")),
+        (2, Some("bar")),
+        (3, Some("foo")),
+        (4, None),
+      ] {
+        let txid = context.core.broadcast_tx(TransactionTemplate {
+          inputs: &[(
+            block,
+            0,
+            0,
+            Inscription {
+              content_type: Some("text/plain".into()),
+              body: Some("hello".into()),
+              metaprotocol: metaprotocol.map(|metaprotocol| metaprotocol.into()),
+              ..default()
+            }
+            .to_witness(),
+          )],
+          ..default()
+        });
+
+        context.mine_blocks(1);
+
+        ids.push(InscriptionId { txid, index: 0 });
+      }
+
+      assert_eq!(
+        context.index.get_metaprotocols().unwrap(),
+        [("foo".into(), 2), ("bar".into(), 1)]
+      );
+
+      assert_eq!(
+        context
+          .index
+          .get_inscriptions_by_metaprotocol_paginated("foo", 1, 0)
+          .unwrap(),
+        (vec![ids[0]], true)
+      );
+
+      assert_eq!(
+        context
+          .index
+          .get_inscriptions_by_metaprotocol_paginated("foo", 1, 1)
+          .unwrap(),
+        (vec![ids[2]], false)
+      );
+
+      assert_eq!(
+        context
+          .index
+          .get_inscriptions_by_metaprotocol_paginated("bar", 100, 0)
+          .unwrap(),
+        (vec![ids[1]], false)
+      );
+
+      assert_eq!(
+        context
+          .index
+          .get_inscriptions_by_metaprotocol_paginated("baz", 100, 0)
+          .unwrap(),
+        (Vec::new(), false)
+      );
+    }
+  }
+
+  #[test]
+  fn mempool_tracks_pending_inscriptions_and_transfers() {
+    let context = Context::builder().build();
+
+    context.mine_blocks(1);
+
+    let create_txid = context.core.broadcast_tx(TransactionTemplate {
+      inputs: &[(1, 0, 0, inscription("text/plain", "hello").to_witness())],
+      ..default()
+    });
+
+    context.mine_blocks(1);
+
+    let transfer_txid = context.core.broadcast_tx(TransactionTemplate {
+      inputs: &[(2, 1, 0, Default::default())],
+      ..default()
+    });
+
+    let pending_txid = context.core.broadcast_tx(TransactionTemplate {
+      inputs: &[(2, 0, 0, inscription("text/plain", "world").to_witness())],
+      ..default()
+    });
+
+    let outpoint = OutPoint {
+      txid: create_txid,
+      vout: 0,
+    };
+
+    assert_eq!(context.index.get_mempool_spender(outpoint), None);
+
+    context.index.update_mempool().unwrap();
+
+    assert_eq!(
+      context.index.get_mempool_inscriptions(),
+      api::MempoolInscriptions {
+        inscriptions: vec![api::MempoolInscription {
+          content_length: Some(5),
+          content_type: Some("text/plain".into()),
+          id: InscriptionId {
+            txid: pending_txid,
+            index: 0,
+          },
+        }],
+        transfers: vec![api::MempoolTransfer {
+          id: InscriptionId {
+            txid: create_txid,
+            index: 0,
+          },
+          satpoint: SatPoint {
+            outpoint,
+            offset: 0,
+          },
+          txid: transfer_txid,
+        }],
+      }
+    );
+
+    assert_eq!(
+      context.index.get_mempool_spender(outpoint),
+      Some(transfer_txid)
+    );
+
+    context.mine_blocks(1);
+
+    context.index.update_mempool().unwrap();
+
+    assert_eq!(
+      context.index.get_mempool_inscriptions(),
+      api::MempoolInscriptions {
+        inscriptions: Vec::new(),
+        transfers: Vec::new(),
+      }
+    );
+
+    assert_eq!(context.index.get_mempool_spender(outpoint), None);
+  }
+
+  #[test]
+  fn mempool_tracks_pending_runestones() {
+    let context = Context::builder().arg("--index-runes").build();
+
+    context.mine_blocks(1);
+
+    let runestone = Runestone {
+      mint: Some(RuneId { block: 1, tx: 1 }),
+      ..default()
+    };
+
+    let txid = context.core.broadcast_tx(TransactionTemplate {
+      inputs: &[(1, 0, 0, Witness::new())],
+      op_return: Some(runestone.encipher()),
+      ..default()
+    });
+
+    context.index.update_mempool().unwrap();
+
+    assert_eq!(
+      context.index.get_mempool_runes(),
+      vec![api::MempoolRune {
+        artifact: Artifact::Runestone(runestone),
+        txid,
+      }]
+    );
+  }
+
+  #[test]
+  fn rune_event_sender_channel() {
+    const RUNE: u128 = 99246114928149462;
+
+    let (event_sender, mut event_receiver) = tokio::sync::mpsc::channel(1024);
+    let context = Context::builder()
+      .arg("--index-runes")
+      .event_sender(event_sender)
+      .build();
+
+    let (txid0, id) = context.etch(
+      Runestone {
+        etching: Some(Etching {
+          rune: Some(Rune(RUNE)),
+          terms: Some(Terms {
+            amount: Some(1000),
+            cap: Some(100),
+            ..default()
+          }),
+          ..default()
+        }),
+        ..default()
+      },
+      1,
+    );
+
+    context.assert_runes(
+      [(
+        id,
+        RuneEntry {
+          block: id.block,
+          etching: txid0,
+          spaced_rune: SpacedRune {
+            rune: Rune(RUNE),
+            spacers: 0,
+          },
+          timestamp: id.block,
+          mints: 0,
+          terms: Some(Terms {
+            amount: Some(1000),
+            cap: Some(100),
+            ..default()
+          }),
+          ..default()
+        },
+      )],
+      [],
+    );
+
+    assert_eq!(
+      next_non_block_event(&mut event_receiver),
+      Event::RuneEtched {
+        block_height: 8,
+        txid: txid0,
+        rune_id: id,
+      }
+    );
+
+    let txid1 = context.core.broadcast_tx(TransactionTemplate {
+      inputs: &[(2, 0, 0, Witness::new())],
+      op_return: Some(
+        Runestone {
+          mint: Some(id),
+          ..default()
+        }
+        .encipher(),
+      ),
+      ..default()
+    });
+
+    context.mine_blocks(1);
+
+    context.assert_runes(
+      [(
+        id,
+        RuneEntry {
+          block: id.block,
+          etching: txid0,
+          terms: Some(Terms {
+            amount: Some(1000),
+            cap: Some(100),
+            ..default()
+          }),
+          mints: 1,
+          spaced_rune: SpacedRune {
+            rune: Rune(RUNE),
+            spacers: 0,
+          },
+          premine: 0,
+          timestamp: id.block,
+          ..default()
+        },
+      )],
+      [(
+        OutPoint {
+          txid: txid1,
+          vout: 0,
+        },
+        vec![(id, 1000)],
+      )],
+    );
+
+    assert_eq!(
+      next_non_block_event(&mut event_receiver),
+      Event::RuneMinted {
+        block_height: 9,
+        txid: txid1,
+        rune_id: id,
+        amount: 1000,
+      }
+    );
+
+    let txid2 = context.core.broadcast_tx(TransactionTemplate {
+      inputs: &[(9, 1, 0, Witness::new())],
+      op_return: Some(
+        Runestone {
+          edicts: vec![Edict {
+            id,
+            amount: 1000,
+            output: 0,
+          }],
+          ..Default::default()
+        }
+        .encipher(),
+      ),
+      ..Default::default()
+    });
+
+    context.mine_blocks(1);
+
+    context.assert_runes(
+      [(
+        id,
+        RuneEntry {
+          block: 8,
+          etching: txid0,
+          spaced_rune: SpacedRune {
+            rune: Rune(RUNE),
+            ..default()
+          },
+          terms: Some(Terms {
+            amount: Some(1000),
+            cap: Some(100),
+            ..Default::default()
+          }),
+          timestamp: 8,
+          mints: 1,
+          ..Default::default()
+        },
+      )],
+      [(
+        OutPoint {
+          txid: txid2,
+          vout: 0,
+        },
+        vec![(id, 1000)],
+      )],
+    );
+
+    next_non_block_event(&mut event_receiver);
+
+    pretty_assert_eq!(
+      next_non_block_event(&mut event_receiver),
+      Event::RuneTransferred {
+        allocation: Allocation::Edict(0),
+        amount: 1000,
+        block_height: 10,
+        inputs: vec![OutPoint {
+          txid: txid1,
+          vout: 0,
+        }],
+        outpoint: OutPoint {
+          txid: txid2,
+          vout: 0,
+        },
+        receiver: Some(uncheck(
+          &Chain::Regtest
+            .address_from_script(&ScriptBuf::new_v0_p2wpkh(&WPubkeyHash::all_zeros()))
+            .unwrap(),
+        )),
+        rune_id: id,
+        sender: Some(uncheck(
+          &Chain::Regtest
+            .address_from_script(&ScriptBuf::new_v0_p2wpkh(&WPubkeyHash::all_zeros()))
+            .unwrap(),
+        )),
+        txid: txid2,
+      }
+    );
+
+    let txid3 = context.core.broadcast_tx(TransactionTemplate {
+      inputs: &[(10, 1, 0, Witness::new())],
+      op_return: Some(
+        Runestone {
+          edicts: vec![Edict {
+            id,
+            amount: 111,
+            output: 0,
+          }],
+          ..Default::default()
+        }
+        .encipher(),
+      ),
+      op_return_index: Some(0),
+      ..Default::default()
+    });
+
+    context.mine_blocks(1);
+
+    context.assert_runes(
+      [(
+        id,
+        RuneEntry {
+          block: 8,
+          etching: txid0,
+          spaced_rune: SpacedRune {
+            rune: Rune(RUNE),
+            ..default()
+          },
+          terms: Some(Terms {
+            amount: Some(1000),
+            cap: Some(100),
+            ..Default::default()
+          }),
+          timestamp: 8,
+          mints: 1,
+          burned: 111,
+          ..Default::default()
+        },
+      )],
+      [(
+        OutPoint {
+          txid: txid3,
+          vout: 1,
+        },
+        vec![(id, 889)],
+      )],
+    );
+
+    next_non_block_event(&mut event_receiver);
+
+    pretty_assert_eq!(
+      next_non_block_event(&mut event_receiver),
+      Event::RuneBurned {
+        block_height: 11,
+        txid: txid3,
+        amount: 111,
+        rune_id: id,
+      }
+    );
+  }
+
+  #[test]
+  fn rune_transfer_events_include_inputs_sender_and_allocation() {
+    const RUNE: u128 = 99246114928149462;
+
+    let (event_sender, mut event_receiver) = tokio::sync::mpsc::channel(1024);
+    let context = Context::builder()
+      .args(["--index-runes", "--index-events"])
+      .event_sender(event_sender)
+      .build();
+
+    let (txid0, id) = context.etch(
+      Runestone {
+        etching: Some(Etching {
+          rune: Some(Rune(RUNE)),
+          premine: Some(1000),
+          ..default()
+        }),
+        ..default()
+      },
+      1,
+    );
+
+    assert_matches!(
+      next_non_block_event(&mut event_receiver),
+      Event::RuneEtched { rune_id, .. } if rune_id == id
+    );
+
+    pretty_assert_eq!(
+      next_non_block_event(&mut event_receiver),
+      Event::RuneTransferred {
+        allocation: Allocation::Default,
+        amount: 1000,
+        block_height: 8,
+        inputs: Vec::new(),
+        outpoint: OutPoint {
+          txid: txid0,
+          vout: 0,
+        },
+        receiver: Some(uncheck(
+          &Chain::Regtest
+            .address_from_script(&ScriptBuf::new_v0_p2wpkh(&WPubkeyHash::all_zeros()))
+            .unwrap(),
+        )),
+        rune_id: id,
+        sender: None,
+        txid: txid0,
+      }
+    );
+
+    let txid1 = context.core.broadcast_tx(TransactionTemplate {
+      inputs: &[(8, 1, 0, Witness::new())],
+      op_return: Some(
+        Runestone {
+          pointer: Some(1),
+          ..default()
+        }
+        .encipher(),
+      ),
+      outputs: 2,
+      ..default()
+    });
+
+    context.mine_blocks(1);
+
+    pretty_assert_eq!(
+      next_non_block_event(&mut event_receiver),
+      Event::RuneTransferred {
+        allocation: Allocation::Pointer,
+        amount: 1000,
+        block_height: 9,
+        inputs: vec![OutPoint {
+          txid: txid0,
+          vout: 0,
+        }],
+        outpoint: OutPoint {
+          txid: txid1,
+          vout: 1,
+        },
+        receiver: Some(uncheck(
+          &Chain::Regtest
+            .address_from_script(&ScriptBuf::new_v0_p2wpkh(&WPubkeyHash::all_zeros()))
+            .unwrap(),
+        )),
+        rune_id: id,
+        sender: Some(uncheck(
+          &Chain::Regtest
+            .address_from_script(&ScriptBuf::new_v0_p2wpkh(&WPubkeyHash::all_zeros()))
+            .unwrap(),
+        )),
+        txid: txid1,
+      }
+    );
+  }
+
+  #[test]
+  fn rune_transfer_sender_is_resolved_without_event_or_address_index() {
+    const RUNE: u128 = 99246114928149462;
+
+    let (event_sender, mut event_receiver) = tokio::sync::mpsc::channel(1024);
+    let context = Context::builder()
+      .arg("--index-runes")
+      .event_sender(event_sender)
+      .build();
+
+    let (txid0, id) = context.etch(
+      Runestone {
+        etching: Some(Etching {
+          rune: Some(Rune(RUNE)),
+          premine: Some(1000),
+          ..default()
+        }),
+        ..default()
+      },
+      1,
+    );
+
+    let txid1 = context.core.broadcast_tx(TransactionTemplate {
+      inputs: &[(8, 1, 0, Witness::new())],
+      op_return: Some(
+        Runestone {
+          pointer: Some(1),
+          ..default()
+        }
+        .encipher(),
+      ),
+      outputs: 2,
+      ..default()
+    });
+
+    context.mine_blocks(1);
+
+    let (inputs, sender) = iter::from_fn(|| event_receiver.try_recv().ok())
+      .find_map(|event| match event {
+        Event::RuneTransferred {
+          inputs,
+          rune_id,
+          sender,
+          txid,
+          ..
+        } if rune_id == id && txid == txid1 => Some((inputs, sender)),
+        _ => None,
+      })
+      .unwrap();
+
+    assert_eq!(
+      inputs,
+      [OutPoint {
+        txid: txid0,
+        vout: 0,
+      }]
+    );
+
+    assert_eq!(
+      sender,
+      Some(uncheck(
+        &Chain::Regtest
+          .address_from_script(&ScriptBuf::new_v0_p2wpkh(&WPubkeyHash::all_zeros()))
+          .unwrap(),
+      )),
+    );
+  }
+
+  #[test]
+  fn cenotaph_event_lists_flaws_and_burned_runes() {
+    const RUNE: u128 = 99246114928149462;
+
+    let (event_sender, mut event_receiver) = tokio::sync::mpsc::channel(1024);
+    let context = Context::builder()
+      .arg("--index-runes")
+      .event_sender(event_sender)
+      .build();
+
+    let (_txid, id) = context.etch(
+      Runestone {
+        etching: Some(Etching {
+          rune: Some(Rune(RUNE)),
+          premine: Some(1000),
+          ..default()
+        }),
+        ..default()
+      },
+      1,
+    );
+
+    next_non_block_event(&mut event_receiver);
+    next_non_block_event(&mut event_receiver);
+
+    let txid = context.core.broadcast_tx(TransactionTemplate {
+      inputs: &[(8, 1, 0, Witness::new())],
+      op_return: Some(
+        Runestone {
+          edicts: vec![Edict {
+            id,
+            amount: 0,
+            output: 5,
+          }],
+          ..default()
+        }
+        .encipher(),
+      ),
+      ..default()
+    });
+
+    context.mine_blocks(1);
+
+    pretty_assert_eq!(
+      next_non_block_event(&mut event_receiver),
+      Event::RuneCenotaph {
+        block_height: 9,
+        burned: [(id, 1000)].into_iter().collect(),
+        flaws: vec![Flaw::EdictOutput],
+        txid,
+      }
+    );
+
+    pretty_assert_eq!(
+      next_non_block_event(&mut event_receiver),
       Event::RuneBurned {
-        block_height: 11,
-        txid: txid3,
-        amount: 111,
+        amount: 1000,
+        block_height: 9,
         rune_id: id,
+        txid,
+      }
+    );
+  }
+
+  #[test]
+  fn rune_holders_track_balances() {
+    const RUNE: u128 = 99246114928149462;
+
+    let context = Context::builder().arg("--index-runes").build();
+
+    let (_txid, id) = context.etch(
+      Runestone {
+        etching: Some(Etching {
+          rune: Some(Rune(RUNE)),
+          premine: Some(1000),
+          ..default()
+        }),
+        ..default()
+      },
+      1,
+    );
+
+    assert_eq!(context.index.get_rune_holder_count(id).unwrap(), 1);
+
+    assert_eq!(
+      context.index.get_rune_holders_paginated(id, 10, 0).unwrap(),
+      (
+        vec![(ScriptBuf::new_v0_p2wpkh(&WPubkeyHash::all_zeros()), 1000)],
+        false
+      )
+    );
+
+    let txid = context.core.broadcast_tx(TransactionTemplate {
+      inputs: &[(8, 1, 0, Witness::new())],
+      op_return: Some(
+        Runestone {
+          edicts: vec![Edict {
+            id,
+            amount: 400,
+            output: 0,
+          }],
+          pointer: Some(1),
+          ..default()
+        }
+        .encipher(),
+      ),
+      outputs: 2,
+      p2tr: true,
+      ..default()
+    });
+
+    context.mine_blocks(1);
+
+    let tx = context.core.tx_by_id(txid);
+
+    assert_eq!(context.index.get_rune_holder_count(id).unwrap(), 2);
+
+    assert_eq!(
+      context.index.get_rune_holders_paginated(id, 10, 0).unwrap(),
+      (
+        vec![
+          (tx.output[1].script_pubkey.clone(), 600),
+          (tx.output[0].script_pubkey.clone(), 400),
+        ],
+        false
+      )
+    );
+
+    assert_eq!(
+      context.index.get_rune_holders_paginated(id, 1, 0).unwrap(),
+      (vec![(tx.output[1].script_pubkey.clone(), 600)], true)
+    );
+
+    assert_eq!(
+      context.index.get_rune_holders_paginated(id, 1, 1).unwrap(),
+      (vec![(tx.output[0].script_pubkey.clone(), 400)], false)
+    );
+  }
+
+  #[test]
+  fn rune_history_records_per_block_deltas() {
+    const RUNE: u128 = 99246114928149462;
+
+    let context = Context::builder().arg("--index-runes").build();
+
+    let (_txid, id) = context.etch(
+      Runestone {
+        etching: Some(Etching {
+          rune: Some(Rune(RUNE)),
+          premine: Some(500),
+          terms: Some(Terms {
+            amount: Some(100),
+            cap: Some(10),
+            ..default()
+          }),
+          ..default()
+        }),
+        ..default()
+      },
+      1,
+    );
+
+    for block in [3, 4] {
+      context.core.broadcast_tx(TransactionTemplate {
+        inputs: &[(block, 0, 0, Witness::new())],
+        op_return: Some(
+          Runestone {
+            mint: Some(id),
+            ..default()
+          }
+          .encipher(),
+        ),
+        ..default()
+      });
+    }
+
+    context.mine_blocks(1);
+
+    context.core.broadcast_tx(TransactionTemplate {
+      inputs: &[(9, 1, 0, Witness::new())],
+      op_return: Some(
+        Runestone {
+          edicts: vec![Edict {
+            id,
+            amount: 100,
+            output: 0,
+          }],
+          ..default()
+        }
+        .encipher(),
+      ),
+      op_return_index: Some(0),
+      ..default()
+    });
+
+    context.mine_blocks(1);
+
+    pretty_assert_eq!(
+      context
+        .index
+        .get_rune_history(id, 0, u32::MAX)
+        .unwrap()
+        .unwrap(),
+      [
+        api::RuneHistoryBlock {
+          burned: 0,
+          height: 8,
+          mints: 0,
+          supply: 500,
+          total_burned: 0,
+          transferred: 0,
+        },
+        api::RuneHistoryBlock {
+          burned: 0,
+          height: 9,
+          mints: 2,
+          supply: 700,
+          total_burned: 0,
+          transferred: 0,
+        },
+        api::RuneHistoryBlock {
+          burned: 100,
+          height: 10,
+          mints: 0,
+          supply: 700,
+          total_burned: 100,
+          transferred: 100,
+        },
+      ]
+    );
+
+    pretty_assert_eq!(
+      context.index.get_rune_history(id, 9, 9).unwrap().unwrap(),
+      [api::RuneHistoryBlock {
+        burned: 0,
+        height: 9,
+        mints: 2,
+        supply: 700,
+        total_burned: 0,
+        transferred: 0,
+      }]
+    );
+
+    assert_eq!(
+      context.index.get_rune_supply_at_height(id, 7).unwrap(),
+      Some(0)
+    );
+    assert_eq!(
+      context.index.get_rune_supply_at_height(id, 8).unwrap(),
+      Some(500)
+    );
+    assert_eq!(
+      context.index.get_rune_supply_at_height(id, 9).unwrap(),
+      Some(700)
+    );
+    assert_eq!(
+      context.index.get_rune_supply_at_height(id, 100).unwrap(),
+      Some(700)
+    );
+    assert_eq!(
+      context
+        .index
+        .get_rune_supply_at_height(RuneId { block: 1, tx: 1 }, 100)
+        .unwrap(),
+      None
+    );
+  }
+
+  #[test]
+  fn rune_history_is_rolled_back_on_reorg() {
+    const RUNE: u128 = 99246114928149462;
+
+    let context = Context::builder().arg("--index-runes").build();
+
+    let (_txid, id) = context.etch(
+      Runestone {
+        etching: Some(Etching {
+          rune: Some(Rune(RUNE)),
+          premine: Some(500),
+          terms: Some(Terms {
+            amount: Some(100),
+            cap: Some(10),
+            ..default()
+          }),
+          ..default()
+        }),
+        ..default()
+      },
+      1,
+    );
+
+    context.core.broadcast_tx(TransactionTemplate {
+      inputs: &[(3, 0, 0, Witness::new())],
+      op_return: Some(
+        Runestone {
+          mint: Some(id),
+          ..default()
+        }
+        .encipher(),
+      ),
+      ..default()
+    });
+
+    context.mine_blocks(1);
+
+    assert_eq!(
+      context.index.get_rune_supply_at_height(id, 9).unwrap(),
+      Some(600)
+    );
+
+    context.core.invalidate_tip();
+    context.mine_blocks(2);
+
+    assert_eq!(
+      context.index.get_rune_supply_at_height(id, 9).unwrap(),
+      Some(500)
+    );
+
+    assert_eq!(
+      context
+        .index
+        .get_rune_history(id, 0, u32::MAX)
+        .unwrap()
+        .unwrap()
+        .len(),
+      1
+    );
+  }
+
+  #[test]
+  fn block_event_sender_channel() {
+    let (event_sender, mut event_receiver) = tokio::sync::mpsc::channel(1024);
+    let context = Context::builder().event_sender(event_sender).build();
+
+    assert_matches!(
+      event_receiver.blocking_recv().unwrap(),
+      Event::BlockStarted {
+        block_height: 0,
+        ..
+      }
+    );
+
+    assert_matches!(
+      event_receiver.blocking_recv().unwrap(),
+      Event::BlockCommitted {
+        block_height: 0,
+        inscription_count: 0,
+        rune_count: 0,
+        ..
+      }
+    );
+
+    context.mine_blocks(1);
+
+    context.core.broadcast_tx(TransactionTemplate {
+      inputs: &[(1, 0, 0, inscription("text/plain", "hello").to_witness())],
+      ..default()
+    });
+
+    let block = context.mine_blocks(1)[0].clone();
+
+    let events = iter::from_fn(|| event_receiver.try_recv().ok()).collect::<Vec<Event>>();
+
+    assert_eq!(events.len(), 5);
+
+    assert_eq!(
+      events[2],
+      Event::BlockStarted {
+        block_hash: block.block_hash(),
+        block_height: 2,
+        timestamp: block.header.time,
+      }
+    );
+
+    assert_matches!(events[3].clone(), Event::InscriptionCreated { .. });
+
+    assert_eq!(
+      events[4],
+      Event::BlockCommitted {
+        block_hash: block.block_hash(),
+        block_height: 2,
+        inscription_count: 1,
+        rune_count: 0,
+        timestamp: block.header.time,
+      }
+    );
+  }
+
+  #[test]
+  fn reorg_event_sender_channel() {
+    let (event_sender, mut event_receiver) = tokio::sync::mpsc::channel(1024);
+    let context = Context::builder().event_sender(event_sender).build();
+
+    context.mine_blocks(1);
+    context.mine_blocks(6);
+    context.mine_blocks(1);
+
+    context.core.invalidate_tip();
+    context.mine_blocks(2);
+
+    let events = iter::from_fn(|| event_receiver.try_recv().ok())
+      .skip_while(|event| !matches!(event, Event::ReorgDetected { .. }))
+      .filter(|event| !matches!(event, Event::BlockStarted { .. }))
+      .collect::<Vec<Event>>();
+
+    assert_eq!(events.len(), 4);
+
+    assert_eq!(
+      events[0],
+      Event::ReorgDetected {
+        depth: 2,
+        height: 9
+      }
+    );
+
+    assert_eq!(events[1], Event::RolledBack { to_height: 8 });
+
+    assert_matches!(
+      events[2].clone(),
+      Event::BlockCommitted {
+        block_height: 8,
+        ..
+      }
+    );
+
+    assert_matches!(
+      events[3].clone(),
+      Event::BlockCommitted {
+        block_height: 9,
+        ..
+      }
+    );
+  }
+
+  #[test]
+  fn event_log_requires_index_events() {
+    let context = Context::builder().build();
+
+    assert_eq!(
+      context.index.events(0, usize::MAX).unwrap_err().to_string(),
+      "event log requires index created with `--index-events` flag"
+    );
+  }
+
+  #[test]
+  fn event_log_stores_events() {
+    let context = Context::builder().arg("--index-events").build();
+
+    context.mine_blocks(1);
+
+    let txid = context.core.broadcast_tx(TransactionTemplate {
+      inputs: &[(1, 0, 0, inscription("text/plain", "hello").to_witness())],
+      ..default()
+    });
+
+    context.mine_blocks(1);
+
+    let events = context.index.events(0, usize::MAX).unwrap();
+
+    assert_eq!(
+      events
+        .iter()
+        .map(|(number, _)| *number)
+        .collect::<Vec<u64>>(),
+      (0..7).collect::<Vec<u64>>(),
+    );
+
+    assert_matches!(
+      events[4].1.clone(),
+      Event::BlockStarted {
+        block_height: 2,
+        ..
+      }
+    );
+
+    assert_eq!(
+      events[5].1,
+      Event::InscriptionCreated {
+        block_height: 2,
+        charms: 0,
+        content_type: Some("text/plain".into()),
+        delegate: None,
+        fee: 0,
+        inscription_id: InscriptionId { txid, index: 0 },
+        location: Some(SatPoint {
+          outpoint: OutPoint { txid, vout: 0 },
+          offset: 0
+        }),
+        metaprotocol: None,
+        parent_inscription_ids: Vec::new(),
+        receiver: Some(uncheck(
+          &Chain::Regtest
+            .address_from_script(&ScriptBuf::new_v0_p2wpkh(&WPubkeyHash::all_zeros()))
+            .unwrap()
+        )),
+        sequence_number: 0,
+        txid,
+        value: Some(50 * COIN_VALUE),
+      }
+    );
+
+    assert_matches!(
+      events[6].1.clone(),
+      Event::BlockCommitted {
+        block_height: 2,
+        inscription_count: 1,
+        ..
+      }
+    );
+
+    assert_eq!(context.index.events(5, 1).unwrap(), &events[5..6]);
+
+    assert_eq!(
+      context.index.first_event_at_or_above_height(0).unwrap(),
+      Some(0)
+    );
+    assert_eq!(
+      context.index.first_event_at_or_above_height(2).unwrap(),
+      Some(4)
+    );
+    assert_eq!(
+      context.index.first_event_at_or_above_height(3).unwrap(),
+      None
+    );
+  }
+
+  #[test]
+  fn event_log_numbers_are_contiguous_across_reorgs() {
+    let context = Context::builder().arg("--index-events").build();
+
+    context.mine_blocks(1);
+    context.mine_blocks(6);
+    context.mine_blocks(1);
+
+    let before = context.index.events(0, usize::MAX).unwrap();
+
+    assert_eq!(before.len(), 18);
+
+    context.core.invalidate_tip();
+    context.mine_blocks(2);
+
+    let after = context.index.events(0, usize::MAX).unwrap();
+
+    let reorg = after
+      .iter()
+      .position(|(_, event)| matches!(event, Event::ReorgDetected { .. }))
+      .unwrap();
+
+    assert_eq!(after[reorg].0, 16);
+
+    assert_eq!(
+      after[reorg].1,
+      Event::ReorgDetected {
+        depth: 2,
+        height: 9
+      }
+    );
+
+    assert_eq!(after[reorg + 1], (17, Event::RolledBack { to_height: 8 }));
+
+    assert_eq!(after[..reorg], before[..reorg]);
+
+    assert!(after
+      .windows(2)
+      .all(|window| window[0].0 + 1 == window[1].0));
+
+    assert_matches!(
+      after.last().unwrap().1.clone(),
+      Event::BlockCommitted {
+        block_height: 9,
+        ..
       }
     );
   }
+
+  #[test]
+  fn verify_finds_no_problems_in_consistent_index() {
+    for context in Context::configurations() {
+      context.mine_blocks(1);
+
+      context.core.broadcast_tx(TransactionTemplate {
+        inputs: &[(1, 0, 0, inscription("text/plain", "hello").to_witness())],
+        ..default()
+      });
+
+      context.mine_blocks(1);
+
+      assert_eq!(context.index.verify().unwrap(), (Some(2), Vec::new()));
+    }
+  }
+
+  #[test]
+  fn verify_detects_inconsistent_inscription_tables() {
+    let context = Context::builder().build();
+
+    context.mine_blocks(1);
+
+    context.core.broadcast_tx(TransactionTemplate {
+      inputs: &[(1, 0, 0, inscription("text/plain", "hello").to_witness())],
+      ..default()
+    });
+
+    context.mine_blocks(1);
+
+    let inscription_id = InscriptionId {
+      txid: Txid::all_zeros(),
+      index: 0,
+    };
+
+    let wtx = context.index.database.begin_write().unwrap();
+
+    wtx
+      .open_table(SEQUENCE_NUMBER_TO_SATPOINT)
+      .unwrap()
+      .remove(0)
+      .unwrap();
+
+    wtx
+      .open_table(INSCRIPTION_ID_TO_SEQUENCE_NUMBER)
+      .unwrap()
+      .insert(&inscription_id.store(), 0)
+      .unwrap();
+
+    wtx
+      .open_table(INSCRIPTION_NUMBER_TO_SEQUENCE_NUMBER)
+      .unwrap()
+      .insert(1, 0)
+      .unwrap();
+
+    wtx.commit().unwrap();
+
+    assert_eq!(
+      context.index.verify().unwrap().1,
+      [
+        Problem::MissingSatpoint { sequence_number: 0 },
+        Problem::InscriptionIdMismatch {
+          inscription_id,
+          sequence_number: 0,
+        },
+        Problem::InscriptionNumberMismatch {
+          inscription_number: 1,
+          sequence_number: 0,
+        },
+      ]
+    );
+  }
+
+  #[test]
+  fn verify_detects_rune_supply_mismatch() {
+    const RUNE: u128 = 99246114928149462;
+
+    let context = Context::builder().arg("--index-runes").build();
+
+    context.etch(
+      Runestone {
+        etching: Some(Etching {
+          rune: Some(Rune(RUNE)),
+          premine: Some(1000),
+          ..default()
+        }),
+        ..default()
+      },
+      1,
+    );
+
+    assert_eq!(context.index.verify().unwrap().1, Vec::new());
+
+    let wtx = context.index.database.begin_write().unwrap();
+
+    wtx
+      .open_table(OUTPOINT_TO_RUNE_BALANCES)
+      .unwrap()
+      .retain(|_, _| false)
+      .unwrap();
+
+    wtx.commit().unwrap();
+
+    assert_eq!(
+      context.index.verify().unwrap().1,
+      [Problem::RuneSupplyMismatch {
+        rune: SpacedRune {
+          rune: Rune(RUNE),
+          spacers: 0,
+        },
+        supply: 1000,
+        balances: 0,
+        burned: 0,
+      }]
+    );
+  }
+
+  #[test]
+  fn verify_detects_overlapping_sat_ranges() {
+    let context = Context::builder().arg("--index-sats").build();
+
+    context.mine_blocks(1);
+
+    assert_eq!(context.index.verify().unwrap().1, Vec::new());
+
+    let outpoint = OutPoint {
+      txid: Txid::all_zeros(),
+      vout: 1,
+    };
+
+    let mut sat_ranges = Vec::new();
+    sat_ranges.extend_from_slice(&(5 * COIN_VALUE, 6 * COIN_VALUE).store());
+
+    let wtx = context.index.database.begin_write().unwrap();
+
+    wtx
+      .open_table(OUTPOINT_TO_SAT_RANGES)
+      .unwrap()
+      .insert(&outpoint.store(), sat_ranges.as_slice())
+      .unwrap();
+
+    wtx.commit().unwrap();
+
+    assert_eq!(
+      context.index.verify().unwrap().1,
+      [Problem::OverlappingSatRanges {
+        first: (
+          OutPoint {
+            txid: context.index.genesis_block_coinbase_txid,
+            vout: 0,
+          },
+          0,
+          50 * COIN_VALUE,
+        ),
+        second: (outpoint, 5 * COIN_VALUE, 6 * COIN_VALUE),
+      }]
+    );
+  }
 }
@@ -265,6 +265,47 @@ impl Entry for RuneEntry {
   }
 }
 
+#[derive(Debug, Default, Eq, PartialEq, Copy, Clone)]
+pub(crate) struct RuneHistoryEntry {
+  pub(crate) burned: u128,
+  pub(crate) mints: u128,
+  pub(crate) total_burned: u128,
+  pub(crate) total_mints: u128,
+  pub(crate) transferred: u128,
+}
+
+pub(crate) type RuneHistoryEntryValue = (
+  u128, // burned
+  u128, // mints
+  u128, // total burned
+  u128, // total mints
+  u128, // transferred
+);
+
+impl Entry for RuneHistoryEntry {
+  type Value = RuneHistoryEntryValue;
+
+  fn load((burned, mints, total_burned, total_mints, transferred): RuneHistoryEntryValue) -> Self {
+    Self {
+      burned,
+      mints,
+      total_burned,
+      total_mints,
+      transferred,
+    }
+  }
+
+  fn store(self) -> Self::Value {
+    (
+      self.burned,
+      self.mints,
+      self.total_burned,
+      self.total_mints,
+      self.transferred,
+    )
+  }
+}
+
 pub(super) type RuneIdValue = (u64, u32);
 
 impl Entry for RuneId {
@@ -279,6 +320,49 @@ impl Entry for RuneId {
   }
 }
 
+#[derive(Debug, Default, Eq, PartialEq, Copy, Clone)]
+pub(crate) struct CollectionEntry {
+  pub(crate) children: u64,
+  pub(crate) first_child_height: u32,
+  pub(crate) grandchildren: u64,
+  pub(crate) holders: u64,
+  pub(crate) last_child_height: u32,
+}
+
+pub(crate) type CollectionEntryValue = (
+  u64, // children
+  u32, // first child height
+  u64, // grandchildren
+  u64, // holders
+  u32, // last child height
+);
+
+impl Entry for CollectionEntry {
+  type Value = CollectionEntryValue;
+
+  fn load(
+    (children, first_child_height, grandchildren, holders, last_child_height): CollectionEntryValue,
+  ) -> Self {
+    Self {
+      children,
+      first_child_height,
+      grandchildren,
+      holders,
+      last_child_height,
+    }
+  }
+
+  fn store(self) -> Self::Value {
+    (
+      self.children,
+      self.first_child_height,
+      self.grandchildren,
+      self.holders,
+      self.last_child_height,
+    )
+  }
+}
+
 #[derive(Debug, Eq, PartialEq, Clone)]
 pub(crate) struct InscriptionEntry {
   pub(crate) charms: u16,
@@ -423,6 +507,47 @@ impl Entry for OutPoint {
   }
 }
 
+#[derive(Debug, Eq, PartialEq, Clone)]
+pub(crate) struct SaleEntry {
+  pub(crate) buyer: ScriptBuf,
+  pub(crate) height: u32,
+  pub(crate) price: u64,
+  pub(crate) seller: ScriptBuf,
+  pub(crate) txid: Txid,
+}
+
+pub(crate) type SaleEntryValue = (
+  Vec<u8>,   // buyer
+  u32,       // height
+  u64,       // price
+  Vec<u8>,   // seller
+  TxidValue, // txid
+);
+
+impl Entry for SaleEntry {
+  type Value = SaleEntryValue;
+
+  fn load((buyer, height, price, seller, txid): SaleEntryValue) -> Self {
+    Self {
+      buyer: buyer.into(),
+      height,
+      price,
+      seller: seller.into(),
+      txid: Txid::load(txid),
+    }
+  }
+
+  fn store(self) -> Self::Value {
+    (
+      self.buyer.into_bytes(),
+      self.height,
+      self.price,
+      self.seller.into_bytes(),
+      self.txid.store(),
+    )
+  }
+}
+
 pub(super) type SatPointValue = [u8; 44];
 
 impl Entry for SatPoint {
@@ -466,6 +591,51 @@ impl Entry for SatRange {
   }
 }
 
+#[derive(Debug, Eq, PartialEq, Clone)]
+pub(crate) struct TransferEntry {
+  pub(crate) from: Option<ScriptBuf>,
+  pub(crate) height: u32,
+  pub(crate) new_satpoint: SatPoint,
+  pub(crate) old_satpoint: Option<SatPoint>,
+  pub(crate) to: Option<ScriptBuf>,
+  pub(crate) txid: Txid,
+}
+
+pub(crate) type TransferEntryValue = (
+  Option<Vec<u8>>,       // from
+  u32,                   // height
+  SatPointValue,         // new satpoint
+  Option<SatPointValue>, // old satpoint
+  Option<Vec<u8>>,       // to
+  TxidValue,             // txid
+);
+
+impl Entry for TransferEntry {
+  type Value = TransferEntryValue;
+
+  fn load((from, height, new_satpoint, old_satpoint, to, txid): TransferEntryValue) -> Self {
+    Self {
+      from: from.map(ScriptBuf::from),
+      height,
+      new_satpoint: SatPoint::load(new_satpoint),
+      old_satpoint: old_satpoint.map(SatPoint::load),
+      to: to.map(ScriptBuf::from),
+      txid: Txid::load(txid),
+    }
+  }
+
+  fn store(self) -> Self::Value {
+    (
+      self.from.map(ScriptBuf::into_bytes),
+      self.height,
+      self.new_satpoint.store(),
+      self.old_satpoint.map(SatPoint::store),
+      self.to.map(ScriptBuf::into_bytes),
+      self.txid.store(),
+    )
+  }
+}
+
 pub(super) type TxidValue = [u8; 32];
 
 impl Entry for Txid {
@@ -508,6 +678,110 @@ mod tests {
     assert_eq!(InscriptionEntry::load(value), entry);
   }
 
+  #[test]
+  fn rune_history_entry() {
+    let entry = RuneHistoryEntry {
+      burned: 1,
+      mints: 2,
+      total_burned: 3,
+      total_mints: 4,
+      transferred: 5,
+    };
+
+    assert_eq!(entry.store(), (1, 2, 3, 4, 5));
+    assert_eq!(RuneHistoryEntry::load((1, 2, 3, 4, 5)), entry);
+  }
+
+  #[test]
+  fn collection_entry() {
+    let entry = CollectionEntry {
+      children: 1,
+      first_child_height: 2,
+      grandchildren: 3,
+      holders: 4,
+      last_child_height: 5,
+    };
+
+    assert_eq!(entry.store(), (1, 2, 3, 4, 5));
+    assert_eq!(CollectionEntry::load((1, 2, 3, 4, 5)), entry);
+  }
+
+  #[test]
+  fn sale_entry() {
+    let txid = Txid::from_byte_array([
+      0x00, 0x01, 0x02, 0x03, 0x04, 0x05, 0x06, 0x07, 0x08, 0x09, 0x0A, 0x0B, 0x0C, 0x0D, 0x0E,
+      0x0F, 0x10, 0x11, 0x12, 0x13, 0x14, 0x15, 0x16, 0x17, 0x18, 0x19, 0x1A, 0x1B, 0x1C, 0x1D,
+      0x1E, 0x1F,
+    ]);
+
+    let entry = SaleEntry {
+      buyer: ScriptBuf::from_bytes(vec![1, 2]),
+      height: 3,
+      price: 4,
+      seller: ScriptBuf::from_bytes(vec![5, 6]),
+      txid,
+    };
+
+    let value = (vec![1, 2], 3, 4, vec![5, 6], txid.to_byte_array());
+
+    assert_eq!(entry.clone().store(), value);
+    assert_eq!(SaleEntry::load(value), entry);
+  }
+
+  #[test]
+  fn transfer_entry() {
+    let txid = Txid::from_byte_array([
+      0x00, 0x01, 0x02, 0x03, 0x04, 0x05, 0x06, 0x07, 0x08, 0x09, 0x0A, 0x0B, 0x0C, 0x0D, 0x0E,
+      0x0F, 0x10, 0x11, 0x12, 0x13, 0x14, 0x15, 0x16, 0x17, 0x18, 0x19, 0x1A, 0x1B, 0x1C, 0x1D,
+      0x1E, 0x1F,
+    ]);
+
+    let new_satpoint = SatPoint {
+      outpoint: OutPoint { txid, vout: 1 },
+      offset: 2,
+    };
+
+    let entry = TransferEntry {
+      from: None,
+      height: 3,
+      new_satpoint,
+      old_satpoint: None,
+      to: Some(ScriptBuf::from_bytes(vec![4, 5])),
+      txid,
+    };
+
+    let value = (
+      None,
+      3,
+      new_satpoint.store(),
+      None,
+      Some(vec![4, 5]),
+      txid.to_byte_array(),
+    );
+
+    assert_eq!(entry.clone().store(), value);
+    assert_eq!(TransferEntry::load(value), entry);
+
+    let entry = TransferEntry {
+      from: Some(ScriptBuf::from_bytes(vec![4, 5])),
+      old_satpoint: Some(new_satpoint),
+      to: None,
+      ..entry
+    };
+
+    let value = (
+      Some(vec![4, 5]),
+      3,
+      new_satpoint.store(),
+      Some(new_satpoint.store()),
+      None,
+      txid.to_byte_array(),
+    );
+
+    assert_eq!(entry.clone().store(), value);
+    assert_eq!(TransferEntry::load(value), entry);
+  }
+
   #[test]
   fn inscription_id_entry() {
     let inscription_id = "0123456789abcdef0123456789abcdef0123456789abcdef0123456789abcdefi0"
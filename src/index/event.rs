@@ -1,21 +1,66 @@
-use super::*;
+use {super::*, ordinals::Flaw};
 
-#[derive(Debug, Clone, PartialEq)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub enum Event {
+  /// Sent to live subscribers only once the write transaction containing the
+  /// block is durable. Blocks are committed in batches, so several
+  /// `BlockStarted` events may be delivered before the `BlockCommitted` events
+  /// for those blocks, which then arrive in block order. The durable event log
+  /// records each `BlockCommitted` directly after its block's other events.
+  BlockCommitted {
+    block_hash: BlockHash,
+    block_height: u32,
+    inscription_count: u32,
+    rune_count: u64,
+    timestamp: u32,
+  },
+  BlockStarted {
+    block_hash: BlockHash,
+    block_height: u32,
+    timestamp: u32,
+  },
   InscriptionCreated {
     block_height: u32,
     charms: u16,
+    content_type: Option<String>,
+    delegate: Option<InscriptionId>,
+    fee: u64,
     inscription_id: InscriptionId,
     location: Option<SatPoint>,
+    metaprotocol: Option<String>,
     parent_inscription_ids: Vec<InscriptionId>,
+    receiver: Option<Address<NetworkUnchecked>>,
     sequence_number: u32,
+    txid: Txid,
+    value: Option<u64>,
+  },
+  InscriptionSold {
+    block_height: u32,
+    buyer: Option<Address<NetworkUnchecked>>,
+    inscription_id: InscriptionId,
+    price: u64,
+    seller: Option<Address<NetworkUnchecked>>,
+    txid: Txid,
   },
   InscriptionTransferred {
     block_height: u32,
+    charms: u16,
     inscription_id: InscriptionId,
     new_location: SatPoint,
+    new_value: Option<u64>,
     old_location: SatPoint,
+    old_value: u64,
+    receiver: Option<Address<NetworkUnchecked>>,
+    sender: Option<Address<NetworkUnchecked>>,
     sequence_number: u32,
+    txid: Txid,
+  },
+  ReorgDetected {
+    depth: u32,
+    height: u32,
+  },
+  RolledBack {
+    to_height: u32,
   },
   RuneBurned {
     amount: u128,
@@ -23,6 +68,12 @@ pub enum Event {
     rune_id: RuneId,
     txid: Txid,
   },
+  RuneCenotaph {
+    block_height: u32,
+    burned: BTreeMap<RuneId, u128>,
+    flaws: Vec<Flaw>,
+    txid: Txid,
+  },
   RuneEtched {
     block_height: u32,
     rune_id: RuneId,
@@ -35,10 +86,22 @@ pub enum Event {
     txid: Txid,
   },
   RuneTransferred {
+    allocation: Allocation,
     amount: u128,
     block_height: u32,
+    inputs: Vec<OutPoint>,
     outpoint: OutPoint,
+    receiver: Option<Address<NetworkUnchecked>>,
     rune_id: RuneId,
+    sender: Option<Address<NetworkUnchecked>>,
     txid: Txid,
   },
 }
+
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Allocation {
+  Default,
+  Edict(usize),
+  Pointer,
+}
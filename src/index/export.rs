@@ -0,0 +1,547 @@
+use {
+  super::*,
+  crate::subcommand::index::export::{Format, Table},
+  std::io::{BufRead, BufReader, Seek},
+};
+
+trait Row: Serialize {
+  const COLUMNS: &'static [&'static str];
+
+  fn fields(&self) -> Vec<String>;
+}
+
+#[derive(Serialize)]
+struct InscriptionRow {
+  sequence_number: u32,
+  inscription_number: i32,
+  id: InscriptionId,
+  satpoint: Option<SatPoint>,
+  sat: Option<Sat>,
+  height: u32,
+  timestamp: u32,
+  fee: u64,
+  charms: Vec<Charm>,
+  parents: Vec<u32>,
+}
+
+impl Row for InscriptionRow {
+  const COLUMNS: &'static [&'static str] = &[
+    "sequence_number",
+    "inscription_number",
+    "id",
+    "satpoint",
+    "sat",
+    "height",
+    "timestamp",
+    "fee",
+    "charms",
+    "parents",
+  ];
+
+  fn fields(&self) -> Vec<String> {
+    vec![
+      self.sequence_number.to_string(),
+      self.inscription_number.to_string(),
+      self.id.to_string(),
+      optional(self.satpoint),
+      optional(self.sat),
+      self.height.to_string(),
+      self.timestamp.to_string(),
+      self.fee.to_string(),
+      list(&self.charms),
+      list(&self.parents),
+    ]
+  }
+}
+
+#[derive(Serialize)]
+struct RuneRow {
+  id: RuneId,
+  rune: SpacedRune,
+  number: u64,
+  block: u64,
+  etching: Txid,
+  divisibility: u8,
+  premine: u128,
+  mints: u128,
+  burned: u128,
+  symbol: Option<char>,
+  amount: Option<u128>,
+  cap: Option<u128>,
+  height_start: Option<u64>,
+  height_end: Option<u64>,
+  offset_start: Option<u64>,
+  offset_end: Option<u64>,
+  timestamp: u64,
+  turbo: bool,
+}
+
+impl Row for RuneRow {
+  const COLUMNS: &'static [&'static str] = &[
+    "id",
+    "rune",
+    "number",
+    "block",
+    "etching",
+    "divisibility",
+    "premine",
+    "mints",
+    "burned",
+    "symbol",
+    "amount",
+    "cap",
+    "height_start",
+    "height_end",
+    "offset_start",
+    "offset_end",
+    "timestamp",
+    "turbo",
+  ];
+
+  fn fields(&self) -> Vec<String> {
+    vec![
+      self.id.to_string(),
+      self.rune.to_string(),
+      self.number.to_string(),
+      self.block.to_string(),
+      self.etching.to_string(),
+      self.divisibility.to_string(),
+      self.premine.to_string(),
+      self.mints.to_string(),
+      self.burned.to_string(),
+      optional(self.symbol),
+      optional(self.amount),
+      optional(self.cap),
+      optional(self.height_start),
+      optional(self.height_end),
+      optional(self.offset_start),
+      optional(self.offset_end),
+      self.timestamp.to_string(),
+      self.turbo.to_string(),
+    ]
+  }
+}
+
+#[derive(Serialize)]
+struct RuneBalanceRow {
+  outpoint: OutPoint,
+  rune: RuneId,
+  amount: u128,
+}
+
+impl Row for RuneBalanceRow {
+  const COLUMNS: &'static [&'static str] = &["outpoint", "rune", "amount"];
+
+  fn fields(&self) -> Vec<String> {
+    vec![
+      self.outpoint.to_string(),
+      self.rune.to_string(),
+      self.amount.to_string(),
+    ]
+  }
+}
+
+#[derive(Serialize)]
+struct SatRangeRow {
+  outpoint: OutPoint,
+  start: u64,
+  end: u64,
+}
+
+impl Row for SatRangeRow {
+  const COLUMNS: &'static [&'static str] = &["outpoint", "start", "end"];
+
+  fn fields(&self) -> Vec<String> {
+    vec![
+      self.outpoint.to_string(),
+      self.start.to_string(),
+      self.end.to_string(),
+    ]
+  }
+}
+
+#[derive(Serialize)]
+struct ChildRow {
+  parent: InscriptionId,
+  child: InscriptionId,
+}
+
+impl Row for ChildRow {
+  const COLUMNS: &'static [&'static str] = &["parent", "child"];
+
+  fn fields(&self) -> Vec<String> {
+    vec![self.parent.to_string(), self.child.to_string()]
+  }
+}
+
+fn optional(value: Option<impl ToString>) -> String {
+  value.map(|value| value.to_string()).unwrap_or_default()
+}
+
+fn list(values: &[impl ToString]) -> String {
+  values
+    .iter()
+    .map(ToString::to_string)
+    .collect::<Vec<String>>()
+    .join(" ")
+}
+
+fn csv_field(field: &str) -> String {
+  if field.contains([',', '"', '\n', '\r']) {
+    format!("\"{}\"", field.replace('"', "\"\""))
+  } else {
+    field.into()
+  }
+}
+
+struct TableWriter {
+  format: Format,
+  path: PathBuf,
+  skip: u64,
+  writer: BufWriter<fs::File>,
+}
+
+impl TableWriter {
+  /// Open the export file at `path`. When resuming, rows already written
+  /// under the same stamp are kept and skipped, and an incomplete last row is
+  /// discarded.
+  fn open(
+    path: PathBuf,
+    format: Format,
+    columns: &[&str],
+    stamp: &str,
+    resume: bool,
+  ) -> Result<Self> {
+    let mut preamble = vec![stamp.to_string()];
+
+    if format == Format::Csv {
+      preamble.push(columns.join(","));
+    }
+
+    let mut skip = 0;
+
+    let file = if resume && path.exists() {
+      let mut reader = BufReader::new(fs::File::open(&path)?);
+      let mut line = Vec::new();
+      let mut offset = 0;
+      let mut end = 0;
+      let mut records = 0;
+      let mut in_quotes = false;
+
+      loop {
+        line.clear();
+
+        let len = reader.read_until(b'\n', &mut line)?;
+
+        if len == 0 || line.last() != Some(&b'\n') {
+          break;
+        }
+
+        offset += len;
+
+        // quoted CSV fields may contain newlines
+        if format == Format::Csv {
+          in_quotes ^= line.iter().filter(|byte| **byte == b'"').count() % 2 == 1;
+
+          if in_quotes {
+            continue;
+          }
+        }
+
+        if let Some(expected) = preamble.get(records) {
+          if line[..len - 1] != *expected.as_bytes() {
+            bail!(
+              "`{}` was not exported at the current block, remove it to start over",
+              path.display()
+            );
+          }
+        }
+
+        records += 1;
+        end = offset;
+      }
+
+      let mut file = fs::OpenOptions::new().write(true).open(&path)?;
+
+      if records < preamble.len() {
+        file.set_len(0)?;
+      } else {
+        file.set_len(end.try_into().unwrap())?;
+        skip = (records - preamble.len()).try_into().unwrap();
+      }
+
+      file.seek(io::SeekFrom::End(0))?;
+
+      file
+    } else {
+      fs::File::create(&path)?
+    };
+
+    let mut writer = BufWriter::new(file);
+
+    if skip == 0 && writer.get_ref().metadata()?.len() == 0 {
+      for line in preamble {
+        writeln!(writer, "{line}")?;
+      }
+    }
+
+    Ok(Self {
+      format,
+      path,
+      skip,
+      writer,
+    })
+  }
+
+  fn write(&mut self, row: &impl Row) -> Result {
+    if self.skip > 0 {
+      self.skip -= 1;
+      return Ok(());
+    }
+
+    match self.format {
+      Format::Csv => writeln!(
+        self.writer,
+        "{}",
+        row
+          .fields()
+          .iter()
+          .map(|field| csv_field(field))
+          .collect::<Vec<String>>()
+          .join(",")
+      )?,
+      Format::Jsonl => {
+        serde_json::to_writer(&mut self.writer, row)?;
+        writeln!(self.writer)?;
+      }
+    }
+
+    if SHUTTING_DOWN.load(atomic::Ordering::Relaxed) {
+      self.writer.flush()?;
+      bail!(
+        "export of `{}` interrupted, rerun with `--resume` to continue",
+        self.path.display()
+      );
+    }
+
+    Ok(())
+  }
+
+  fn finish(mut self) -> Result<PathBuf> {
+    self.writer.flush()?;
+    Ok(self.path)
+  }
+}
+
+impl Index {
+  /// Export `tables` to one file per table in `dir`, all read from the same
+  /// read transaction and stamped with the height and hash of its last block.
+  pub(crate) fn export_tables(
+    &self,
+    tables: &[Table],
+    format: Format,
+    dir: &Path,
+    resume: bool,
+  ) -> Result<(u32, BlockHash, Vec<PathBuf>)> {
+    let rtx = self.database.begin_read()?;
+
+    let (height, hash) = Self::snapshot_tip(&rtx)?;
+
+    let stamp = match format {
+      Format::Csv => format!("# export at block height {height} hash {hash}"),
+      Format::Jsonl => serde_json::to_string(&serde_json::json!({
+        "height": height,
+        "hash": hash,
+      }))?,
+    };
+
+    let mut files = Vec::new();
+
+    for &table in tables {
+      let path = dir.join(format!("{}.{}", table.name(), format.extension()));
+
+      log::info!("exporting {} to {}", table.name(), path.display());
+
+      let path = match table {
+        Table::Inscriptions => {
+          let mut writer =
+            TableWriter::open(path, format, InscriptionRow::COLUMNS, &stamp, resume)?;
+          self.export_inscriptions(&rtx, &mut writer)?;
+          writer.finish()?
+        }
+        Table::Runes => {
+          let mut writer = TableWriter::open(path, format, RuneRow::COLUMNS, &stamp, resume)?;
+          self.export_runes(&rtx, &mut writer)?;
+          writer.finish()?
+        }
+        Table::RuneBalances => {
+          let mut writer =
+            TableWriter::open(path, format, RuneBalanceRow::COLUMNS, &stamp, resume)?;
+          self.export_rune_balances(&rtx, &mut writer)?;
+          writer.finish()?
+        }
+        Table::SatRanges => {
+          let mut writer = TableWriter::open(path, format, SatRangeRow::COLUMNS, &stamp, resume)?;
+          self.export_sat_ranges(&rtx, &mut writer)?;
+          writer.finish()?
+        }
+        Table::Children => {
+          let mut writer = TableWriter::open(path, format, ChildRow::COLUMNS, &stamp, resume)?;
+          Self::export_children(&rtx, &mut writer)?;
+          writer.finish()?
+        }
+      };
+
+      files.push(path);
+    }
+
+    Ok((height, hash, files))
+  }
+
+  fn export_inscriptions(&self, rtx: &ReadTransaction, writer: &mut TableWriter) -> Result {
+    let sequence_number_to_satpoint = rtx.open_table(SEQUENCE_NUMBER_TO_SATPOINT)?;
+
+    for result in rtx
+      .open_table(SEQUENCE_NUMBER_TO_INSCRIPTION_ENTRY)?
+      .iter()?
+    {
+      let (_sequence_number, entry) = result?;
+      let entry = InscriptionEntry::load(entry.value());
+
+      writer.write(&InscriptionRow {
+        sequence_number: entry.sequence_number,
+        inscription_number: entry.inscription_number,
+        id: entry.id,
+        satpoint: sequence_number_to_satpoint
+          .get(entry.sequence_number)?
+          .map(|satpoint| SatPoint::load(*satpoint.value())),
+        sat: entry.sat,
+        height: entry.height,
+        timestamp: entry.timestamp,
+        fee: entry.fee,
+        charms: Charm::charms(entry.charms),
+        parents: entry.parents,
+      })?;
+    }
+
+    Ok(())
+  }
+
+  fn export_runes(&self, rtx: &ReadTransaction, writer: &mut TableWriter) -> Result {
+    if !self.index_runes {
+      bail!("exporting runes requires index created with `--index-runes` flag");
+    }
+
+    for result in rtx.open_table(RUNE_ID_TO_RUNE_ENTRY)?.iter()? {
+      let (id, entry) = result?;
+      let entry = RuneEntry::load(entry.value());
+      let terms = entry.terms.unwrap_or_default();
+
+      writer.write(&RuneRow {
+        id: RuneId::load(id.value()),
+        rune: entry.spaced_rune,
+        number: entry.number,
+        block: entry.block,
+        etching: entry.etching,
+        divisibility: entry.divisibility,
+        premine: entry.premine,
+        mints: entry.mints,
+        burned: entry.burned,
+        symbol: entry.symbol,
+        amount: terms.amount,
+        cap: terms.cap,
+        height_start: terms.height.0,
+        height_end: terms.height.1,
+        offset_start: terms.offset.0,
+        offset_end: terms.offset.1,
+        timestamp: entry.timestamp,
+        turbo: entry.turbo,
+      })?;
+    }
+
+    Ok(())
+  }
+
+  fn export_rune_balances(&self, rtx: &ReadTransaction, writer: &mut TableWriter) -> Result {
+    if !self.index_runes {
+      bail!("exporting rune balances requires index created with `--index-runes` flag");
+    }
+
+    for result in rtx.open_table(OUTPOINT_TO_RUNE_BALANCES)?.iter()? {
+      let (outpoint, balances) = result?;
+      let outpoint = OutPoint::load(*outpoint.value());
+      let balances = balances.value();
+
+      let mut i = 0;
+      while i < balances.len() {
+        let ((rune, amount), len) = Self::decode_rune_balance(&balances[i..])?;
+        i += len;
+
+        writer.write(&RuneBalanceRow {
+          outpoint,
+          rune,
+          amount,
+        })?;
+      }
+    }
+
+    Ok(())
+  }
+
+  fn export_sat_ranges(&self, rtx: &ReadTransaction, writer: &mut TableWriter) -> Result {
+    if !self.index_sats {
+      bail!("exporting sat ranges requires index created with `--index-sats` flag");
+    }
+
+    for result in rtx.open_table(OUTPOINT_TO_SAT_RANGES)?.iter()? {
+      let (outpoint, sat_ranges) = result?;
+      let outpoint = OutPoint::load(*outpoint.value());
+
+      for chunk in sat_ranges.value().chunks_exact(11) {
+        let (start, end) = SatRange::load(chunk.try_into().unwrap());
+
+        writer.write(&SatRangeRow {
+          outpoint,
+          start,
+          end,
+        })?;
+      }
+    }
+
+    Ok(())
+  }
+
+  fn export_children(rtx: &ReadTransaction, writer: &mut TableWriter) -> Result {
+    let sequence_number_to_inscription_entry =
+      rtx.open_table(SEQUENCE_NUMBER_TO_INSCRIPTION_ENTRY)?;
+
+    let id = |sequence_number: u32| -> Result<InscriptionId> {
+      Ok(
+        InscriptionEntry::load(
+          sequence_number_to_inscription_entry
+            .get(sequence_number)?
+            .ok_or_else(|| anyhow!("missing entry for sequence number {sequence_number}"))?
+            .value(),
+        )
+        .id,
+      )
+    };
+
+    for result in rtx
+      .open_multimap_table(SEQUENCE_NUMBER_TO_CHILDREN)?
+      .iter()?
+    {
+      let (parent, children) = result?;
+      let parent = id(parent.value())?;
+
+      for child in children {
+        writer.write(&ChildRow {
+          parent,
+          child: id(child?.value())?,
+        })?;
+      }
+    }
+
+    Ok(())
+  }
+}
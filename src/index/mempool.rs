@@ -0,0 +1,200 @@
+use super::*;
+
+#[derive(Default)]
+pub(crate) struct Mempool {
+  spent: HashMap<OutPoint, Txid>,
+  stale: HashMap<Txid, u32>,
+  transactions: BTreeMap<Txid, MempoolTransaction>,
+}
+
+struct MempoolTransaction {
+  artifact: Option<Artifact>,
+  inscriptions: Vec<api::MempoolInscription>,
+  transaction: Transaction,
+  transfers: Vec<api::MempoolTransfer>,
+}
+
+impl MempoolTransaction {
+  fn new(index: &Index, txid: Txid, transaction: Transaction) -> Result<Self> {
+    let inscriptions = ParsedEnvelope::from_transaction(&transaction)
+      .into_iter()
+      .enumerate()
+      .map(|(i, envelope)| api::MempoolInscription {
+        content_length: envelope.payload.content_length(),
+        content_type: envelope.payload.content_type().map(str::to_string),
+        id: InscriptionId {
+          txid,
+          index: i.try_into().unwrap(),
+        },
+      })
+      .collect();
+
+    Ok(Self {
+      artifact: Runestone::decipher(&transaction),
+      inscriptions,
+      transfers: Self::transfers(index, txid, &transaction)?,
+      transaction,
+    })
+  }
+
+  fn transfers(
+    index: &Index,
+    txid: Txid,
+    transaction: &Transaction,
+  ) -> Result<Vec<api::MempoolTransfer>> {
+    let mut transfers = Vec::new();
+
+    for input in &transaction.input {
+      for (satpoint, id) in
+        index.get_inscriptions_on_output_with_satpoints(input.previous_output)?
+      {
+        transfers.push(api::MempoolTransfer { id, satpoint, txid });
+      }
+    }
+
+    Ok(transfers)
+  }
+}
+
+impl Mempool {
+  /// Applies the difference between the tracked transactions and `txids`.
+  /// Only new transactions are fetched. Transfers depend on confirmed
+  /// outputs, so a transaction whose parent leaves the mempool is marked
+  /// stale, and its transfers are rebuilt until the index has moved past the
+  /// block count at which the parent left.
+  pub(super) fn update(
+    mempool: &Mutex<Self>,
+    index: &Index,
+    block_count: u32,
+    txids: Vec<Txid>,
+  ) -> Result {
+    let txids = txids.into_iter().collect::<HashSet<Txid>>();
+
+    let (added, removed) = {
+      let mempool = mempool.lock().unwrap();
+
+      (
+        txids
+          .iter()
+          .filter(|txid| !mempool.transactions.contains_key(*txid))
+          .copied()
+          .collect::<Vec<Txid>>(),
+        mempool
+          .transactions
+          .keys()
+          .filter(|txid| !txids.contains(*txid))
+          .copied()
+          .collect::<Vec<Txid>>(),
+      )
+    };
+
+    let mut transactions = Vec::new();
+
+    for txid in added {
+      let Some(transaction) = index
+        .client
+        .get_raw_transaction(&txid, None)
+        .into_option()?
+      else {
+        continue;
+      };
+
+      transactions.push((txid, MempoolTransaction::new(index, txid, transaction)?));
+    }
+
+    let mut mempool = mempool.lock().unwrap();
+
+    // insert before removing, so that children of removed transactions are
+    // marked stale
+    for (txid, transaction) in transactions {
+      mempool.insert(txid, transaction);
+    }
+
+    for txid in removed {
+      mempool.remove(txid, block_count);
+    }
+
+    mempool.refresh(index, block_count)
+  }
+
+  fn insert(&mut self, txid: Txid, transaction: MempoolTransaction) {
+    for input in &transaction.transaction.input {
+      self.spent.insert(input.previous_output, txid);
+    }
+
+    self.transactions.insert(txid, transaction);
+  }
+
+  fn remove(&mut self, txid: Txid, block_count: u32) {
+    let Some(transaction) = self.transactions.remove(&txid) else {
+      return;
+    };
+
+    for input in &transaction.transaction.input {
+      if self.spent.get(&input.previous_output) == Some(&txid) {
+        self.spent.remove(&input.previous_output);
+      }
+    }
+
+    self.stale.remove(&txid);
+
+    for vout in 0..transaction.transaction.output.len() {
+      let outpoint = OutPoint {
+        txid,
+        vout: vout.try_into().unwrap(),
+      };
+
+      if let Some(spender) = self.spent.get(&outpoint) {
+        self.stale.insert(*spender, block_count);
+      }
+    }
+  }
+
+  fn refresh(&mut self, index: &Index, block_count: u32) -> Result {
+    for txid in self.stale.keys() {
+      if let Some(transaction) = self.transactions.get_mut(txid) {
+        transaction.transfers =
+          MempoolTransaction::transfers(index, *txid, &transaction.transaction)?;
+      }
+    }
+
+    self.stale.retain(|_txid, stale| *stale >= block_count);
+
+    Ok(())
+  }
+
+  pub(super) fn inscriptions(&self) -> api::MempoolInscriptions {
+    api::MempoolInscriptions {
+      inscriptions: self
+        .transactions
+        .values()
+        .flat_map(|transaction| transaction.inscriptions.iter().cloned())
+        .collect(),
+      transfers: self
+        .transactions
+        .values()
+        .flat_map(|transaction| transaction.transfers.iter().cloned())
+        .collect(),
+    }
+  }
+
+  pub(super) fn runes(&self) -> Vec<api::MempoolRune> {
+    self
+      .transactions
+      .iter()
+      .filter_map(|(txid, transaction)| {
+        transaction
+          .artifact
+          .clone()
+          .map(|artifact| api::MempoolRune {
+            artifact,
+            txid: *txid,
+          })
+      })
+      .collect()
+  }
+
+  pub(super) fn spender(&self, outpoint: OutPoint) -> Option<Txid> {
+    self.spent.get(&outpoint).copied()
+  }
+}
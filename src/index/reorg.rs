@@ -19,10 +19,6 @@ impl Display for Error {
 
 impl std::error::Error for Error {}
 
-const MAX_SAVEPOINTS: u32 = 2;
-const SAVEPOINT_INTERVAL: u32 = 10;
-const CHAIN_TIP_DISTANCE: u32 = 21;
-
 pub(crate) struct Reorg {}
 
 impl Reorg {
@@ -32,18 +28,19 @@ impl Reorg {
     match index.block_hash(height.checked_sub(1))? {
       Some(index_prev_blockhash) if index_prev_blockhash == bitcoind_prev_blockhash => Ok(()),
       Some(index_prev_blockhash) if index_prev_blockhash != bitcoind_prev_blockhash => {
-        let max_recoverable_reorg_depth =
-          (MAX_SAVEPOINTS - 1) * SAVEPOINT_INTERVAL + height % SAVEPOINT_INTERVAL;
-
-        for depth in 1..max_recoverable_reorg_depth {
-          let index_block_hash = index.block_hash(height.checked_sub(depth))?;
+        for depth in 1..=index.settings.max_reorg_depth().min(height) {
+          let index_block_hash = index.block_hash(Some(height - depth))?;
           let bitcoind_block_hash = index
             .client
-            .get_block_hash(u64::from(height.saturating_sub(depth)))
+            .get_block_hash(u64::from(height - depth))
             .into_option()?;
 
           if index_block_hash == bitcoind_block_hash {
-            return Err(anyhow!(reorg::Error::Recoverable { height, depth }));
+            if UndoLog::can_roll_back(index, height - depth + 1)? {
+              return Err(anyhow!(reorg::Error::Recoverable { height, depth }));
+            }
+
+            break;
           }
         }
 
@@ -56,63 +53,46 @@ impl Reorg {
   pub(crate) fn handle_reorg(index: &Index, height: u32, depth: u32) -> Result {
     log::info!("rolling back database after reorg of depth {depth} at height {height}");
 
-    if let redb::Durability::None = index.durability {
-      panic!("set index durability to `Durability::Immediate` to test reorg handling");
-    }
-
-    let mut wtx = index.begin_write()?;
-
-    let oldest_savepoint =
-      wtx.get_persistent_savepoint(wtx.list_persistent_savepoints()?.min().unwrap())?;
+    let wtx = index.begin_write()?;
 
-    wtx.restore_savepoint(&oldest_savepoint)?;
+    UndoLog::roll_back(&wtx, height - depth + 1)?;
 
-    Index::increment_statistic(&wtx, Statistic::Commits, 1)?;
-    wtx.commit()?;
+    // events from rolled back blocks are removed by the roll back, so numbering
+    // continues from the last event that survived it
+    let next_event = wtx
+      .open_table(EVENT_SEQUENCE_NUMBER_TO_EVENT)?
+      .last()?
+      .map(|(number, _event)| number.value() + 1)
+      .unwrap_or(0);
 
-    log::info!(
-      "successfully rolled back database to height {}",
-      index.begin_read()?.block_count()?
-    );
+    let to_height = wtx
+      .open_table(HEIGHT_TO_BLOCK_HEADER)?
+      .last()?
+      .map(|(height, _header)| height.value() + 1)
+      .unwrap_or(0);
 
-    Ok(())
-  }
+    let events = [
+      Event::ReorgDetected { depth, height },
+      Event::RolledBack { to_height },
+    ];
 
-  pub(crate) fn update_savepoints(index: &Index, height: u32) -> Result {
-    if let redb::Durability::None = index.durability {
-      return Ok(());
-    }
+    if index.index_events {
+      let mut event_log = wtx.open_table(EVENT_SEQUENCE_NUMBER_TO_EVENT)?;
 
-    if (height < SAVEPOINT_INTERVAL || height % SAVEPOINT_INTERVAL == 0)
-      && u32::try_from(
-        index
-          .settings
-          .bitcoin_rpc_client(None)?
-          .get_blockchain_info()?
-          .headers,
-      )
-      .unwrap()
-      .saturating_sub(height)
-        <= CHAIN_TIP_DISTANCE
-    {
-      let wtx = index.begin_write()?;
-
-      let savepoints = wtx.list_persistent_savepoints()?.collect::<Vec<u64>>();
-
-      if savepoints.len() >= usize::try_from(MAX_SAVEPOINTS).unwrap() {
-        wtx.delete_persistent_savepoint(savepoints.into_iter().min().unwrap())?;
+      for (number, event) in (next_event..).zip(&events) {
+        event_log.insert(number, serde_json::to_vec(event)?.as_slice())?;
       }
+    }
 
-      Index::increment_statistic(&wtx, Statistic::Commits, 1)?;
-      wtx.commit()?;
-
-      let wtx = index.begin_write()?;
+    Index::increment_statistic(&wtx, Statistic::Commits, 1)?;
+    wtx.commit()?;
 
-      log::debug!("creating savepoint at height {}", height);
-      wtx.persistent_savepoint()?;
+    log::info!("successfully rolled back database to height {to_height}");
 
-      Index::increment_statistic(&wtx, Statistic::Commits, 1)?;
-      wtx.commit()?;
+    if let Some(sender) = index.event_sender.as_ref() {
+      for event in events {
+        sender.blocking_send(event)?;
+      }
     }
 
     Ok(())
@@ -0,0 +1,267 @@
+use super::*;
+
+impl Index {
+  /// Write a copy of the index as of the last committed block to `path`. The
+  /// copy is read from a single read transaction, so the index may continue to
+  /// be updated while it is being taken.
+  pub(crate) fn snapshot(&self, path: &Path) -> Result<(u32, BlockHash)> {
+    if path.exists() {
+      bail!("snapshot destination `{}` already exists", path.display());
+    }
+
+    let rtx = self.database.begin_read()?;
+
+    let (height, hash) = Self::snapshot_tip(&rtx)?;
+
+    log::info!("writing snapshot at height {height} to {}", path.display());
+
+    let result = Self::copy_tables(&rtx, &Database::create(path)?);
+
+    if result.is_err() {
+      fs::remove_file(path).ok();
+    }
+
+    result?;
+
+    Ok((height, hash))
+  }
+
+  /// Height and hash of the last block in the snapshot at `path`.
+  pub(crate) fn read_snapshot(path: &Path) -> Result<(u32, BlockHash)> {
+    Self::snapshot_tip(&Self::open_snapshot(path)?.begin_read()?)
+  }
+
+  /// Check that the snapshot at `path` was taken from an index with the
+  /// current schema version, chain and indexed features, and install it as
+  /// the index.
+  pub(crate) fn restore(settings: &Settings, path: &Path) -> Result<(u32, BlockHash)> {
+    let index_path = settings.index();
+
+    if index_path.exists() {
+      bail!(
+        "index `{}` already exists, remove it before restoring a snapshot",
+        index_path.display()
+      );
+    }
+
+    let (height, hash) = {
+      let rtx = Self::open_snapshot(path)?.begin_read()?;
+
+      Self::check_snapshot(settings, &rtx)?;
+
+      Self::snapshot_tip(&rtx)?
+    };
+
+    let parent = index_path.parent().unwrap();
+
+    fs::create_dir_all(parent)
+      .with_context(|| format!("failed to create data dir `{}`", parent.display()))?;
+
+    // copy next to the index and rename, so that an interrupted restore never
+    // leaves a partial index behind
+    let partial = tempfile::NamedTempFile::new_in(parent)?;
+
+    fs::copy(path, partial.path())?;
+
+    partial.persist(index_path)?;
+
+    Ok((height, hash))
+  }
+
+  fn open_snapshot(path: &Path) -> Result<Database> {
+    Database::open(path).with_context(|| format!("failed to open snapshot `{}`", path.display()))
+  }
+
+  fn check_snapshot(settings: &Settings, rtx: &ReadTransaction) -> Result {
+    let statistics = rtx.open_table(STATISTIC_TO_COUNT)?;
+
+    let schema_version = statistics
+      .get(&Statistic::Schema.key())?
+      .map(|x| x.value())
+      .unwrap_or(0);
+
+    if schema_version != SCHEMA_VERSION {
+      bail!(
+        "snapshot schema {schema_version} does not match ord schema {SCHEMA_VERSION}, snapshots can only be restored by the same version of ord"
+      );
+    }
+
+    let genesis = rtx
+      .open_table(HEIGHT_TO_BLOCK_HEADER)?
+      .get(0)?
+      .map(|header| Header::load(*header.value()).block_hash());
+
+    if genesis != Some(settings.chain().genesis_block().block_hash()) {
+      bail!("snapshot was not taken from a {} index", settings.chain());
+    }
+
+    let features = [
+      (
+        "--index-addresses",
+        Statistic::IndexAddresses,
+        settings.index_addresses(),
+      ),
+      (
+        "--index-events",
+        Statistic::IndexEvents,
+        settings.index_events(),
+      ),
+      (
+        "--index-metadata",
+        Statistic::IndexMetadata,
+        settings.index_metadata(),
+      ),
+      (
+        "--index-runes",
+        Statistic::IndexRunes,
+        settings.index_runes(),
+      ),
+      (
+        "--index-sats",
+        Statistic::IndexSats,
+        settings.index_sats() || settings.index_spent_sats(),
+      ),
+      (
+        "--index-spent-sats",
+        Statistic::IndexSpentSats,
+        settings.index_spent_sats(),
+      ),
+      (
+        "--index-transactions",
+        Statistic::IndexTransactions,
+        settings.index_transactions(),
+      ),
+      (
+        "--index-transfers",
+        Statistic::IndexTransfers,
+        settings.index_transfers(),
+      ),
+    ];
+
+    let mut mismatches = Vec::new();
+
+    for (flag, statistic, enabled) in features {
+      if Self::is_statistic_set(&statistics, statistic)? != enabled {
+        mismatches.push(format!(
+          "{flag} is {} but the snapshot was taken {} it",
+          if enabled { "set" } else { "not set" },
+          if enabled { "without" } else { "with" },
+        ));
+      }
+    }
+
+    if !mismatches.is_empty() {
+      bail!(
+        "snapshot indexed features do not match settings: {}",
+        mismatches.join(", ")
+      );
+    }
+
+    Ok(())
+  }
+
+  pub(super) fn snapshot_tip(rtx: &ReadTransaction) -> Result<(u32, BlockHash)> {
+    rtx
+      .open_table(HEIGHT_TO_BLOCK_HEADER)?
+      .last()?
+      .map(|(height, header)| (height.value(), Header::load(*header.value()).block_hash()))
+      .ok_or_else(|| anyhow!("index does not contain any blocks"))
+  }
+
+  fn copy_tables(rtx: &ReadTransaction, database: &Database) -> Result {
+    macro_rules! copy {
+      (tables: $($table:ident),*; multimap tables: $($multimap:ident),* $(,)?) => {
+        $(
+          {
+            let wtx = database.begin_write()?;
+
+            {
+              let mut destination = wtx.open_table($table)?;
+
+              for result in rtx.open_table($table)?.iter()? {
+                let (key, value) = result?;
+                destination.insert(key.value(), value.value())?;
+              }
+            }
+
+            wtx.commit()?;
+          }
+        )*
+
+        $(
+          {
+            let wtx = database.begin_write()?;
+
+            {
+              let mut destination = wtx.open_multimap_table($multimap)?;
+
+              for result in rtx.open_multimap_table($multimap)?.iter()? {
+                let (key, values) = result?;
+
+                for value in values {
+                  destination.insert(key.value(), value?.value())?;
+                }
+              }
+            }
+
+            wtx.commit()?;
+          }
+        )*
+      };
+    }
+
+    copy! {
+      tables:
+        COLLECTION_AND_CONTENT_TYPE_TO_COUNT,
+        COLLECTION_AND_HEIGHT_TO_MINTS,
+        COLLECTION_AND_HOLDER_TO_COUNT,
+        COLLECTION_TO_ENTRY,
+        CONTENT_TYPE_AND_SEQUENCE_NUMBER,
+        CONTENT_TYPE_TO_COUNT,
+        EVENT_SEQUENCE_NUMBER_TO_EVENT,
+        HEIGHT_AND_NUMBER_TO_UNDO_RECORD,
+        HEIGHT_TO_BLOCK_HEADER,
+        HEIGHT_TO_FIRST_EVENT_SEQUENCE_NUMBER,
+        HEIGHT_TO_LAST_SEQUENCE_NUMBER,
+        HOME_INSCRIPTIONS,
+        INSCRIPTION_ID_TO_SEQUENCE_NUMBER,
+        INSCRIPTION_NUMBER_TO_SEQUENCE_NUMBER,
+        METAPROTOCOL_TO_COUNT,
+        OUTPOINT_TO_RUNE_BALANCES,
+        OUTPOINT_TO_SAT_RANGES,
+        OUTPOINT_TO_TXOUT,
+        OUTPOINT_TO_VALUE,
+        RUNE_HOLDER_TO_BALANCE,
+        RUNE_ID_AND_HEIGHT_TO_HISTORY_ENTRY,
+        RUNE_ID_TO_HOLDER_COUNT,
+        RUNE_ID_TO_RUNE_ENTRY,
+        RUNE_TO_RUNE_ID,
+        SATRIBUTE_TO_SAT_RANGES,
+        SAT_TO_SATPOINT,
+        SEQUENCE_NUMBER_TO_CONTENT_TYPE,
+        SEQUENCE_NUMBER_TO_HOLDER,
+        SEQUENCE_NUMBER_TO_INSCRIPTION_ENTRY,
+        SEQUENCE_NUMBER_TO_RUNE_ID,
+        SEQUENCE_NUMBER_TO_SALE_ENTRY,
+        SEQUENCE_NUMBER_TO_SATPOINT,
+        SEQUENCE_NUMBER_TO_TRANSFER_ENTRY,
+        STATISTIC_TO_COUNT,
+        TRANSACTION_ID_TO_RUNE,
+        TRANSACTION_ID_TO_TRANSACTION,
+        WRITE_TRANSACTION_STARTING_BLOCK_COUNT_TO_TIMESTAMP;
+      multimap tables:
+        BODY_HASH_TO_SEQUENCE_NUMBER,
+        DELEGATE_TO_SEQUENCE_NUMBERS,
+        METADATA_KEY_AND_VALUE_TO_SEQUENCE_NUMBER,
+        METAPROTOCOL_TO_SEQUENCE_NUMBER,
+        RUNE_ID_AND_BALANCE_TO_HOLDER,
+        SATPOINT_TO_SEQUENCE_NUMBER,
+        SATRIBUTE_TO_OUTPOINT,
+        SAT_TO_SEQUENCE_NUMBER,
+        SCRIPT_PUBKEY_TO_OUTPOINT,
+        SEQUENCE_NUMBER_TO_CHILDREN,
+    }
+
+    Ok(())
+  }
+}
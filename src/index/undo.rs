@@ -0,0 +1,460 @@
+use {
+  super::*,
+  redb::{AccessGuard, Key, TableError, Value},
+  std::{cell::RefCell, ops::Deref, rc::Rc},
+};
+
+pub(crate) type UndoRecordValue = (&'static str, u8, &'static [u8], Option<&'static [u8]>);
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+enum Undo {
+  // insert a value that was removed from a multimap table
+  Insert,
+  // remove a value that was inserted into a multimap table
+  Remove,
+  // restore the previous value of a table entry, removing it if there was none
+  Restore,
+}
+
+impl From<Undo> for u8 {
+  fn from(undo: Undo) -> Self {
+    match undo {
+      Undo::Insert => 0,
+      Undo::Remove => 1,
+      Undo::Restore => 2,
+    }
+  }
+}
+
+impl TryFrom<u8> for Undo {
+  type Error = Error;
+
+  fn try_from(n: u8) -> Result<Self> {
+    match n {
+      0 => Ok(Self::Insert),
+      1 => Ok(Self::Remove),
+      2 => Ok(Self::Restore),
+      _ => Err(anyhow!("invalid undo record kind {n}")),
+    }
+  }
+}
+
+struct UndoRecord {
+  key: Vec<u8>,
+  table: String,
+  undo: Undo,
+  value: Option<Vec<u8>>,
+}
+
+/// Mutations made to the index while indexing a block. They are stored as
+/// undo records keyed by block height, and applied in reverse to roll the
+/// block back after a reorg.
+#[derive(Clone, Default)]
+pub(crate) struct UndoLog {
+  records: Option<Rc<RefCell<Vec<UndoRecord>>>>,
+}
+
+impl UndoLog {
+  pub(crate) fn recording() -> Self {
+    Self {
+      records: Some(Rc::new(RefCell::new(Vec::new()))),
+    }
+  }
+
+  pub(crate) fn is_recording(&self) -> bool {
+    self.records.is_some()
+  }
+
+  pub(crate) fn open_table<'tx, K: Key + 'static, V: Value + 'static>(
+    &self,
+    wtx: &'tx WriteTransaction,
+    definition: TableDefinition<K, V>,
+  ) -> Result<UndoTable<'tx, K, V>, TableError> {
+    Ok(UndoTable {
+      log: self.clone(),
+      name: definition.name().into(),
+      table: wtx.open_table(definition)?,
+    })
+  }
+
+  pub(crate) fn open_multimap_table<'tx, K: Key + 'static, V: Key + 'static>(
+    &self,
+    wtx: &'tx WriteTransaction,
+    definition: MultimapTableDefinition<K, V>,
+  ) -> Result<UndoMultimapTable<'tx, K, V>, TableError> {
+    Ok(UndoMultimapTable {
+      log: self.clone(),
+      name: definition.name().into(),
+      table: wtx.open_multimap_table(definition)?,
+    })
+  }
+
+  fn record(&self, table: &str, undo: Undo, key: &[u8], value: Option<&[u8]>) {
+    if let Some(records) = &self.records {
+      records.borrow_mut().push(UndoRecord {
+        key: key.into(),
+        table: table.into(),
+        undo,
+        value: value.map(Into::into),
+      });
+    }
+  }
+
+  /// Store the undo records of the block at `height`, and prune those of
+  /// blocks more than `depth` blocks below it.
+  pub(crate) fn commit(&self, wtx: &WriteTransaction, height: u32, depth: u32) -> Result {
+    let Some(records) = &self.records else {
+      return Ok(());
+    };
+
+    let mut undo_log = wtx.open_table(HEIGHT_AND_NUMBER_TO_UNDO_RECORD)?;
+
+    if let Some(oldest) = height.checked_sub(depth) {
+      undo_log.retain_in(..=(oldest, u32::MAX), |_, _| false)?;
+    }
+
+    for (number, record) in (0..).zip(records.borrow_mut().drain(..)) {
+      undo_log.insert(
+        (height, number),
+        (
+          record.table.as_str(),
+          u8::from(record.undo),
+          record.key.as_slice(),
+          record.value.as_deref(),
+        ),
+      )?;
+    }
+
+    Ok(())
+  }
+
+  /// Whether every block from `height` up to the index tip can be rolled back.
+  pub(crate) fn can_roll_back(index: &Index, height: u32) -> Result<bool> {
+    let rtx = index.database.begin_read()?;
+
+    let Some(tip) = rtx
+      .open_table(HEIGHT_TO_BLOCK_HEADER)?
+      .last()?
+      .map(|(height, _header)| height.value())
+    else {
+      return Ok(false);
+    };
+
+    let undo_log = rtx.open_table(HEIGHT_AND_NUMBER_TO_UNDO_RECORD)?;
+
+    for height in height..=tip {
+      if undo_log
+        .range((height, 0)..=(height, u32::MAX))?
+        .next()
+        .is_none()
+      {
+        return Ok(false);
+      }
+    }
+
+    Ok(true)
+  }
+
+  /// Roll back every block from `height` up to the index tip.
+  pub(crate) fn roll_back(wtx: &WriteTransaction, height: u32) -> Result {
+    let mut undo_log = wtx.open_table(HEIGHT_AND_NUMBER_TO_UNDO_RECORD)?;
+
+    for result in undo_log.range((height, 0)..)?.rev() {
+      let (_key, record) = result?;
+
+      let (table, undo, key, value) = record.value();
+
+      Self::undo(wtx, table, Undo::try_from(undo)?, key, value)?;
+    }
+
+    undo_log.retain_in((height, 0).., |_, _| false)?;
+
+    Ok(())
+  }
+
+  fn undo(
+    wtx: &WriteTransaction,
+    table: &str,
+    undo: Undo,
+    key: &[u8],
+    value: Option<&[u8]>,
+  ) -> Result {
+    macro_rules! undo {
+      (tables: $($table:ident),*; multimap tables: $($multimap:ident),* $(,)?) => {
+        $(
+          if table == $table.name() {
+            return Self::undo_table(wtx, $table, undo, key, value);
+          }
+        )*
+
+        $(
+          if table == $multimap.name() {
+            return Self::undo_multimap_table(wtx, $multimap, undo, key, value);
+          }
+        )*
+
+        bail!("no undo handler for table `{table}`")
+      };
+    }
+
+    undo! {
+      tables:
+        COLLECTION_AND_CONTENT_TYPE_TO_COUNT,
+        COLLECTION_AND_HEIGHT_TO_MINTS,
+        COLLECTION_AND_HOLDER_TO_COUNT,
+        COLLECTION_TO_ENTRY,
+        CONTENT_TYPE_AND_SEQUENCE_NUMBER,
+        CONTENT_TYPE_TO_COUNT,
+        EVENT_SEQUENCE_NUMBER_TO_EVENT,
+        HEIGHT_TO_BLOCK_HEADER,
+        HEIGHT_TO_FIRST_EVENT_SEQUENCE_NUMBER,
+        HEIGHT_TO_LAST_SEQUENCE_NUMBER,
+        HOME_INSCRIPTIONS,
+        INSCRIPTION_ID_TO_SEQUENCE_NUMBER,
+        INSCRIPTION_NUMBER_TO_SEQUENCE_NUMBER,
+        METAPROTOCOL_TO_COUNT,
+        OUTPOINT_TO_RUNE_BALANCES,
+        OUTPOINT_TO_SAT_RANGES,
+        OUTPOINT_TO_TXOUT,
+        OUTPOINT_TO_VALUE,
+        RUNE_HOLDER_TO_BALANCE,
+        RUNE_ID_AND_HEIGHT_TO_HISTORY_ENTRY,
+        RUNE_ID_TO_HOLDER_COUNT,
+        RUNE_ID_TO_RUNE_ENTRY,
+        RUNE_TO_RUNE_ID,
+        SATRIBUTE_TO_SAT_RANGES,
+        SAT_TO_SATPOINT,
+        SEQUENCE_NUMBER_TO_CONTENT_TYPE,
+        SEQUENCE_NUMBER_TO_HOLDER,
+        SEQUENCE_NUMBER_TO_INSCRIPTION_ENTRY,
+        SEQUENCE_NUMBER_TO_RUNE_ID,
+        SEQUENCE_NUMBER_TO_SALE_ENTRY,
+        SEQUENCE_NUMBER_TO_SATPOINT,
+        SEQUENCE_NUMBER_TO_TRANSFER_ENTRY,
+        STATISTIC_TO_COUNT,
+        TRANSACTION_ID_TO_RUNE,
+        TRANSACTION_ID_TO_TRANSACTION;
+      multimap tables:
+        BODY_HASH_TO_SEQUENCE_NUMBER,
+        DELEGATE_TO_SEQUENCE_NUMBERS,
+        METADATA_KEY_AND_VALUE_TO_SEQUENCE_NUMBER,
+        METAPROTOCOL_TO_SEQUENCE_NUMBER,
+        RUNE_ID_AND_BALANCE_TO_HOLDER,
+        SATPOINT_TO_SEQUENCE_NUMBER,
+        SATRIBUTE_TO_OUTPOINT,
+        SAT_TO_SEQUENCE_NUMBER,
+        SCRIPT_PUBKEY_TO_OUTPOINT,
+        SEQUENCE_NUMBER_TO_CHILDREN,
+    }
+  }
+
+  fn undo_table<K: Key + 'static, V: Value + 'static>(
+    wtx: &WriteTransaction,
+    definition: TableDefinition<K, V>,
+    undo: Undo,
+    key: &[u8],
+    value: Option<&[u8]>,
+  ) -> Result {
+    ensure!(
+      undo == Undo::Restore,
+      "invalid undo record for table `{}`",
+      definition.name()
+    );
+
+    let mut table = wtx.open_table(definition)?;
+
+    match value {
+      Some(value) => table.insert(K::from_bytes(key), V::from_bytes(value))?,
+      None => table.remove(K::from_bytes(key))?,
+    };
+
+    Ok(())
+  }
+
+  fn undo_multimap_table<K: Key + 'static, V: Key + 'static>(
+    wtx: &WriteTransaction,
+    definition: MultimapTableDefinition<K, V>,
+    undo: Undo,
+    key: &[u8],
+    value: Option<&[u8]>,
+  ) -> Result {
+    let value = value.ok_or_else(|| {
+      anyhow!(
+        "undo record for multimap table `{}` has no value",
+        definition.name()
+      )
+    })?;
+
+    let mut table = wtx.open_multimap_table(definition)?;
+
+    match undo {
+      Undo::Insert => table.insert(K::from_bytes(key), V::from_bytes(value))?,
+      Undo::Remove => table.remove(K::from_bytes(key), V::from_bytes(value))?,
+      Undo::Restore => bail!(
+        "invalid undo record for multimap table `{}`",
+        definition.name()
+      ),
+    };
+
+    Ok(())
+  }
+}
+
+/// A table whose mutations are recorded in an `UndoLog`. Reads go through to
+/// the underlying table.
+pub(crate) struct UndoTable<'tx, K: Key + 'static, V: Value + 'static> {
+  log: UndoLog,
+  name: String,
+  table: Table<'tx, K, V>,
+}
+
+impl<'tx, K: Key + 'static, V: Value + 'static> UndoTable<'tx, K, V> {
+  pub(crate) fn insert<'k, 'v>(
+    &mut self,
+    key: impl Borrow<K::SelfType<'k>>,
+    value: impl Borrow<V::SelfType<'v>>,
+  ) -> Result<Option<AccessGuard<'_, V>>, StorageError> {
+    let previous = self.table.insert(key.borrow(), value)?;
+
+    self.log.record(
+      &self.name,
+      Undo::Restore,
+      K::as_bytes(key.borrow()).as_ref(),
+      previous
+        .as_ref()
+        .map(|previous| V::as_bytes(&previous.value()).as_ref().to_vec())
+        .as_deref(),
+    );
+
+    Ok(previous)
+  }
+
+  pub(crate) fn remove<'k>(
+    &mut self,
+    key: impl Borrow<K::SelfType<'k>>,
+  ) -> Result<Option<AccessGuard<'_, V>>, StorageError> {
+    let previous = self.table.remove(key.borrow())?;
+
+    if let Some(previous) = &previous {
+      self.log.record(
+        &self.name,
+        Undo::Restore,
+        K::as_bytes(key.borrow()).as_ref(),
+        Some(V::as_bytes(&previous.value()).as_ref()),
+      );
+    }
+
+    Ok(previous)
+  }
+
+  pub(crate) fn pop_first(
+    &mut self,
+  ) -> Result<Option<(AccessGuard<'_, K>, AccessGuard<'_, V>)>, StorageError> {
+    let first = self.table.pop_first()?;
+
+    if let Some((key, value)) = &first {
+      self.log.record(
+        &self.name,
+        Undo::Restore,
+        K::as_bytes(&key.value()).as_ref(),
+        Some(V::as_bytes(&value.value()).as_ref()),
+      );
+    }
+
+    Ok(first)
+  }
+}
+
+impl<'tx, K: Key + 'static, V: Value + 'static> Deref for UndoTable<'tx, K, V> {
+  type Target = Table<'tx, K, V>;
+
+  fn deref(&self) -> &Self::Target {
+    &self.table
+  }
+}
+
+/// A multimap table whose mutations are recorded in an `UndoLog`. Reads go
+/// through to the underlying table.
+pub(crate) struct UndoMultimapTable<'tx, K: Key + 'static, V: Key + 'static> {
+  log: UndoLog,
+  name: String,
+  table: MultimapTable<'tx, K, V>,
+}
+
+impl<'tx, K: Key + 'static, V: Key + 'static> UndoMultimapTable<'tx, K, V> {
+  pub(crate) fn insert<'k, 'v>(
+    &mut self,
+    key: impl Borrow<K::SelfType<'k>>,
+    value: impl Borrow<V::SelfType<'v>>,
+  ) -> Result<bool, StorageError> {
+    let existed = self.table.insert(key.borrow(), value.borrow())?;
+
+    if !existed {
+      self.log.record(
+        &self.name,
+        Undo::Remove,
+        K::as_bytes(key.borrow()).as_ref(),
+        Some(V::as_bytes(value.borrow()).as_ref()),
+      );
+    }
+
+    Ok(existed)
+  }
+
+  pub(crate) fn remove<'k, 'v>(
+    &mut self,
+    key: impl Borrow<K::SelfType<'k>>,
+    value: impl Borrow<V::SelfType<'v>>,
+  ) -> Result<bool, StorageError> {
+    let existed = self.table.remove(key.borrow(), value.borrow())?;
+
+    if existed {
+      self.log.record(
+        &self.name,
+        Undo::Insert,
+        K::as_bytes(key.borrow()).as_ref(),
+        Some(V::as_bytes(value.borrow()).as_ref()),
+      );
+    }
+
+    Ok(existed)
+  }
+
+  pub(crate) fn remove_all<'k>(&mut self, key: impl Borrow<K::SelfType<'k>>) -> Result {
+    for value in self.table.remove_all(key.borrow())? {
+      self.log.record(
+        &self.name,
+        Undo::Insert,
+        K::as_bytes(key.borrow()).as_ref(),
+        Some(V::as_bytes(&value?.value()).as_ref()),
+      );
+    }
+
+    Ok(())
+  }
+}
+
+impl<'tx, K: Key + 'static, V: Key + 'static> Deref for UndoMultimapTable<'tx, K, V> {
+  type Target = MultimapTable<'tx, K, V>;
+
+  fn deref(&self) -> &Self::Target {
+    &self.table
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  #[test]
+  fn undo_kind_round_trips() {
+    for undo in [Undo::Insert, Undo::Remove, Undo::Restore] {
+      assert_eq!(Undo::try_from(u8::from(undo)).unwrap(), undo);
+    }
+
+    assert_eq!(
+      Undo::try_from(3).unwrap_err().to_string(),
+      "invalid undo record kind 3"
+    );
+  }
+}
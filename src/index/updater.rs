@@ -1,11 +1,14 @@
 use {
-  self::{inscription_updater::InscriptionUpdater, rune_updater::RuneUpdater},
+  self::{
+    event_emitter::EventEmitter, inscription_updater::InscriptionUpdater, rune_updater::RuneUpdater,
+  },
   super::{fetcher::Fetcher, *},
   futures::future::try_join_all,
   std::sync::mpsc,
   tokio::sync::mpsc::{error::TryRecvError, Receiver, Sender},
 };
 
+mod event_emitter;
 mod inscription_updater;
 mod rune_updater;
 
@@ -38,6 +41,7 @@ pub(crate) struct Updater<'index> {
   pub(super) outputs_traversed: u64,
   pub(super) range_cache: HashMap<OutPointValue, Vec<u8>>,
   pub(super) sat_ranges_since_flush: u64,
+  pub(super) uncommitted_blocks: Vec<Event>,
 }
 
 impl<'index> Updater<'index> {
@@ -46,6 +50,10 @@ impl<'index> Updater<'index> {
     let starting_height = u32::try_from(self.index.client.get_block_count()?).unwrap() + 1;
     let starting_index_height = self.height;
 
+    // blocks this close to the chain tip may be reorged out, so undo records
+    // are kept for them
+    let undo_height = starting_height.saturating_sub(self.index.settings.max_reorg_depth());
+
     wtx
       .open_table(WRITE_TRANSACTION_STARTING_BLOCK_COUNT_TO_TIMESTAMP)?
       .insert(
@@ -84,6 +92,7 @@ impl<'index> Updater<'index> {
         &mut wtx,
         block,
         &mut value_cache,
+        undo_height,
       )?;
 
       if let Some(progress_bar) = &mut progress_bar {
@@ -311,9 +320,62 @@ impl<'index> Updater<'index> {
     wtx: &mut WriteTransaction,
     block: BlockData,
     value_cache: &mut HashMap<OutPoint, u64>,
+    undo_height: u32,
   ) -> Result<()> {
     Reorg::detect_reorg(&block, self.height, self.index)?;
 
+    let undo_log = if self.height >= undo_height {
+      UndoLog::recording()
+    } else {
+      UndoLog::default()
+    };
+
+    let block_hash = block.header.block_hash();
+
+    // values and scripts of outputs holding inscriptions or runes, or of all
+    // outputs when addresses are indexed, so that senders can be resolved when
+    // they're spent
+    let mut outpoint_to_txout = undo_log.open_table(wtx, OUTPOINT_TO_TXOUT)?;
+
+    if self.index.index_addresses {
+      Self::index_addresses(wtx, &undo_log, &mut outpoint_to_txout, &block.txdata)?;
+    }
+
+    let event_log = if self.index.index_events {
+      Some(undo_log.open_table(wtx, EVENT_SEQUENCE_NUMBER_TO_EVENT)?)
+    } else {
+      None
+    };
+
+    let next_event = event_log
+      .as_ref()
+      .map(|event_log| {
+        event_log
+          .last()
+          .map(|last| last.map(|(number, _event)| number.value() + 1))
+      })
+      .transpose()?
+      .flatten()
+      .unwrap_or(0);
+
+    if event_log.is_some() {
+      undo_log
+        .open_table(wtx, HEIGHT_TO_FIRST_EVENT_SEQUENCE_NUMBER)?
+        .insert(self.height, next_event)?;
+    }
+
+    let mut event_emitter = EventEmitter {
+      event_log,
+      event_sender: self.index.event_sender.clone(),
+      next_event,
+    };
+
+    event_emitter.emit(Event::BlockStarted {
+      block_hash,
+      block_height: self.height,
+      timestamp: block.header.time,
+    })?;
+
     let start = Instant::now();
     let mut sat_ranges_written = 0;
     let mut outputs_in_block = 0;
@@ -331,7 +393,7 @@ impl<'index> Updater<'index> {
       return Err(anyhow!("Previous block did not consume all input values"));
     };
 
-    let mut outpoint_to_value = wtx.open_table(OUTPOINT_TO_VALUE)?;
+    let mut outpoint_to_value = undo_log.open_table(wtx, OUTPOINT_TO_VALUE)?;
 
     let index_inscriptions = self.height >= self.index.first_inscription_height
       && self.index.settings.index_inscriptions();
@@ -370,22 +432,51 @@ impl<'index> Updater<'index> {
       }
     }
 
-    let mut content_type_to_count = wtx.open_table(CONTENT_TYPE_TO_COUNT)?;
-    let mut height_to_block_header = wtx.open_table(HEIGHT_TO_BLOCK_HEADER)?;
-    let mut height_to_last_sequence_number = wtx.open_table(HEIGHT_TO_LAST_SEQUENCE_NUMBER)?;
-    let mut home_inscriptions = wtx.open_table(HOME_INSCRIPTIONS)?;
+    let mut body_hash_to_sequence_number =
+      undo_log.open_multimap_table(wtx, BODY_HASH_TO_SEQUENCE_NUMBER)?;
+    let mut collection_and_content_type_to_count =
+      undo_log.open_table(wtx, COLLECTION_AND_CONTENT_TYPE_TO_COUNT)?;
+    let mut collection_and_height_to_mints =
+      undo_log.open_table(wtx, COLLECTION_AND_HEIGHT_TO_MINTS)?;
+    let mut collection_and_holder_to_count =
+      undo_log.open_table(wtx, COLLECTION_AND_HOLDER_TO_COUNT)?;
+    let mut collection_to_entry = undo_log.open_table(wtx, COLLECTION_TO_ENTRY)?;
+    let mut content_type_and_sequence_number =
+      undo_log.open_table(wtx, CONTENT_TYPE_AND_SEQUENCE_NUMBER)?;
+    let mut content_type_to_count = undo_log.open_table(wtx, CONTENT_TYPE_TO_COUNT)?;
+    let mut delegate_to_sequence_numbers =
+      undo_log.open_multimap_table(wtx, DELEGATE_TO_SEQUENCE_NUMBERS)?;
+    let mut height_to_block_header = undo_log.open_table(wtx, HEIGHT_TO_BLOCK_HEADER)?;
+    let mut height_to_last_sequence_number =
+      undo_log.open_table(wtx, HEIGHT_TO_LAST_SEQUENCE_NUMBER)?;
+    let mut home_inscriptions = undo_log.open_table(wtx, HOME_INSCRIPTIONS)?;
     let mut inscription_id_to_sequence_number =
-      wtx.open_table(INSCRIPTION_ID_TO_SEQUENCE_NUMBER)?;
+      undo_log.open_table(wtx, INSCRIPTION_ID_TO_SEQUENCE_NUMBER)?;
     let mut inscription_number_to_sequence_number =
-      wtx.open_table(INSCRIPTION_NUMBER_TO_SEQUENCE_NUMBER)?;
-    let mut sat_to_sequence_number = wtx.open_multimap_table(SAT_TO_SEQUENCE_NUMBER)?;
-    let mut satpoint_to_sequence_number = wtx.open_multimap_table(SATPOINT_TO_SEQUENCE_NUMBER)?;
-    let mut sequence_number_to_children = wtx.open_multimap_table(SEQUENCE_NUMBER_TO_CHILDREN)?;
+      undo_log.open_table(wtx, INSCRIPTION_NUMBER_TO_SEQUENCE_NUMBER)?;
+    let mut metadata_key_and_value_to_sequence_number =
+      undo_log.open_multimap_table(wtx, METADATA_KEY_AND_VALUE_TO_SEQUENCE_NUMBER)?;
+    let mut metaprotocol_to_count = undo_log.open_table(wtx, METAPROTOCOL_TO_COUNT)?;
+    let mut metaprotocol_to_sequence_number =
+      undo_log.open_multimap_table(wtx, METAPROTOCOL_TO_SEQUENCE_NUMBER)?;
+    let mut sat_to_sequence_number = undo_log.open_multimap_table(wtx, SAT_TO_SEQUENCE_NUMBER)?;
+    let mut satpoint_to_sequence_number =
+      undo_log.open_multimap_table(wtx, SATPOINT_TO_SEQUENCE_NUMBER)?;
+    let mut sequence_number_to_children =
+      undo_log.open_multimap_table(wtx, SEQUENCE_NUMBER_TO_CHILDREN)?;
+    let mut sequence_number_to_content_type =
+      undo_log.open_table(wtx, SEQUENCE_NUMBER_TO_CONTENT_TYPE)?;
+    let mut sequence_number_to_holder = undo_log.open_table(wtx, SEQUENCE_NUMBER_TO_HOLDER)?;
     let mut sequence_number_to_inscription_entry =
-      wtx.open_table(SEQUENCE_NUMBER_TO_INSCRIPTION_ENTRY)?;
-    let mut sequence_number_to_satpoint = wtx.open_table(SEQUENCE_NUMBER_TO_SATPOINT)?;
-    let mut statistic_to_count = wtx.open_table(STATISTIC_TO_COUNT)?;
-    let mut transaction_id_to_transaction = wtx.open_table(TRANSACTION_ID_TO_TRANSACTION)?;
+      undo_log.open_table(wtx, SEQUENCE_NUMBER_TO_INSCRIPTION_ENTRY)?;
+    let mut sequence_number_to_sale_entry =
+      undo_log.open_table(wtx, SEQUENCE_NUMBER_TO_SALE_ENTRY)?;
+    let mut sequence_number_to_satpoint = undo_log.open_table(wtx, SEQUENCE_NUMBER_TO_SATPOINT)?;
+    let mut sequence_number_to_transfer_entry =
+      undo_log.open_table(wtx, SEQUENCE_NUMBER_TO_TRANSFER_ENTRY)?;
+    let mut statistic_to_count = undo_log.open_table(wtx, STATISTIC_TO_COUNT)?;
+    let mut transaction_id_to_transaction =
+      undo_log.open_table(wtx, TRANSACTION_ID_TO_TRANSACTION)?;
 
     let mut lost_sats = statistic_to_count
       .get(&Statistic::LostSats.key())?
@@ -418,26 +509,43 @@ impl<'index> Updater<'index> {
 
     let mut inscription_updater = InscriptionUpdater {
       blessed_inscription_count,
+      body_hash_to_sequence_number: &mut body_hash_to_sequence_number,
       chain: self.index.settings.chain(),
+      collection_and_content_type_to_count: &mut collection_and_content_type_to_count,
+      collection_and_height_to_mints: &mut collection_and_height_to_mints,
+      collection_and_holder_to_count: &mut collection_and_holder_to_count,
+      collection_to_entry: &mut collection_to_entry,
+      content_type_and_sequence_number: &mut content_type_and_sequence_number,
       content_type_to_count: &mut content_type_to_count,
       cursed_inscription_count,
-      event_sender: self.index.event_sender.as_ref(),
+      delegate_to_sequence_numbers: &mut delegate_to_sequence_numbers,
+      event_emitter: &mut event_emitter,
       flotsam: Vec::new(),
       height: self.height,
       home_inscription_count,
       home_inscriptions: &mut home_inscriptions,
       id_to_sequence_number: &mut inscription_id_to_sequence_number,
+      index_metadata: self.index.index_metadata,
       index_transactions: self.index.index_transactions,
+      index_transfers: self.index.index_transfers,
       inscription_number_to_sequence_number: &mut inscription_number_to_sequence_number,
       lost_sats,
+      metadata_key_and_value_to_sequence_number: &mut metadata_key_and_value_to_sequence_number,
+      metaprotocol_to_count: &mut metaprotocol_to_count,
+      metaprotocol_to_sequence_number: &mut metaprotocol_to_sequence_number,
       next_sequence_number,
+      outpoint_to_txout: &mut outpoint_to_txout,
       outpoint_to_value: &mut outpoint_to_value,
       reward: Height(self.height).subsidy(),
       sat_to_sequence_number: &mut sat_to_sequence_number,
       satpoint_to_sequence_number: &mut satpoint_to_sequence_number,
       sequence_number_to_children: &mut sequence_number_to_children,
+      sequence_number_to_content_type: &mut sequence_number_to_content_type,
       sequence_number_to_entry: &mut sequence_number_to_inscription_entry,
+      sequence_number_to_holder: &mut sequence_number_to_holder,
+      sequence_number_to_sale_entry: &mut sequence_number_to_sale_entry,
       sequence_number_to_satpoint: &mut sequence_number_to_satpoint,
+      sequence_number_to_transfer_entry: &mut sequence_number_to_transfer_entry,
       timestamp: block.header.time,
       transaction_buffer: Vec::new(),
       transaction_id_to_transaction: &mut transaction_id_to_transaction,
@@ -447,8 +555,12 @@ impl<'index> Updater<'index> {
     };
 
     if self.index.index_sats {
-      let mut sat_to_satpoint = wtx.open_table(SAT_TO_SATPOINT)?;
-      let mut outpoint_to_sat_ranges = wtx.open_table(OUTPOINT_TO_SAT_RANGES)?;
+      let mut sat_to_satpoint = undo_log.open_table(wtx, SAT_TO_SATPOINT)?;
+      let mut outpoint_to_sat_ranges = undo_log.open_table(wtx, OUTPOINT_TO_SAT_RANGES)?;
+      let mut satribute_to_outpoint = undo_log.open_multimap_table(wtx, SATRIBUTE_TO_OUTPOINT)?;
+      let mut satribute_to_sat_ranges = undo_log.open_table(wtx, SATRIBUTE_TO_SAT_RANGES)?;
+
+      let mut classifier = Index::load_satribute_classifier(&*satribute_to_sat_ranges)?;
 
       let mut coinbase_inputs = VecDeque::new();
 
@@ -486,15 +598,37 @@ impl<'index> Updater<'index> {
             .to_vec(),
           };
 
+          let mut satributes = BTreeSet::new();
+
           for chunk in sat_ranges.chunks_exact(11) {
-            input_sat_ranges.push_back(SatRange::load(chunk.try_into().unwrap()));
+            let (start, end) = SatRange::load(chunk.try_into().unwrap());
+            satributes.extend(classifier.classify_range(start, end));
+            input_sat_ranges.push_back((start, end));
+          }
+
+          for satribute in satributes {
+            satribute_to_outpoint.remove(u8::from(satribute), &key)?;
+          }
+        }
+
+        if Some(*txid) == self.index.settings.chain().pizza_txid() {
+          let mut pizza_sat_ranges = Vec::new();
+
+          for (start, end) in &input_sat_ranges {
+            classifier.add_range(Satribute::Pizza, *start, *end);
+            pizza_sat_ranges.extend_from_slice(&(*start, *end).store());
           }
+
+          satribute_to_sat_ranges
+            .insert(u8::from(Satribute::Pizza), pizza_sat_ranges.as_slice())?;
         }
 
         self.index_transaction_sats(
           tx,
           *txid,
+          &classifier,
           &mut sat_to_satpoint,
+          &mut satribute_to_outpoint,
           &mut input_sat_ranges,
           &mut sat_ranges_written,
           &mut outputs_in_block,
@@ -509,7 +643,9 @@ impl<'index> Updater<'index> {
         self.index_transaction_sats(
           tx,
           *txid,
+          &classifier,
           &mut sat_to_satpoint,
+          &mut satribute_to_outpoint,
           &mut coinbase_inputs,
           &mut sat_ranges_written,
           &mut outputs_in_block,
@@ -536,6 +672,10 @@ impl<'index> Updater<'index> {
             )?;
           }
 
+          for satribute in classifier.classify_range(start, end) {
+            satribute_to_outpoint.insert(u8::from(satribute), &OutPoint::null().store())?;
+          }
+
           lost_sat_ranges.extend_from_slice(&(start, end).store());
 
           lost_sats += end - start;
@@ -543,6 +683,14 @@ impl<'index> Updater<'index> {
 
         outpoint_to_sat_ranges.insert(&OutPoint::null().store(), lost_sat_ranges.as_slice())?;
       }
+
+      // undo records are kept per block, so sat ranges of blocks that may be
+      // rolled back are written immediately instead of being cached
+      if undo_log.is_recording() {
+        for (outpoint, sat_ranges) in self.range_cache.drain() {
+          outpoint_to_sat_ranges.insert(&outpoint, sat_ranges.as_slice())?;
+        }
+      }
     } else if index_inscriptions {
       for (tx, txid) in block.txdata.iter().skip(1).chain(block.txdata.first()) {
         inscription_updater.index_inscriptions(tx, *txid, None)?;
@@ -554,6 +702,8 @@ impl<'index> Updater<'index> {
         .insert(&self.height, inscription_updater.next_sequence_number)?;
     }
 
+    let inscription_count = inscription_updater.next_sequence_number - next_sequence_number;
+
     statistic_to_count.insert(
       &Statistic::LostSats.key(),
       &if self.index.index_sats {
@@ -578,12 +728,20 @@ impl<'index> Updater<'index> {
       &inscription_updater.unbound_inscriptions,
     )?;
 
+    let mut rune_count = 0;
+
     if self.index.index_runes && self.height >= self.index.settings.first_rune_height() {
-      let mut outpoint_to_rune_balances = wtx.open_table(OUTPOINT_TO_RUNE_BALANCES)?;
-      let mut rune_id_to_rune_entry = wtx.open_table(RUNE_ID_TO_RUNE_ENTRY)?;
-      let mut rune_to_rune_id = wtx.open_table(RUNE_TO_RUNE_ID)?;
-      let mut sequence_number_to_rune_id = wtx.open_table(SEQUENCE_NUMBER_TO_RUNE_ID)?;
-      let mut transaction_id_to_rune = wtx.open_table(TRANSACTION_ID_TO_RUNE)?;
+      let mut outpoint_to_rune_balances = undo_log.open_table(wtx, OUTPOINT_TO_RUNE_BALANCES)?;
+      let mut rune_holder_to_balance = undo_log.open_table(wtx, RUNE_HOLDER_TO_BALANCE)?;
+      let mut rune_id_and_balance_to_holder =
+        undo_log.open_multimap_table(wtx, RUNE_ID_AND_BALANCE_TO_HOLDER)?;
+      let mut rune_id_and_height_to_history =
+        undo_log.open_table(wtx, RUNE_ID_AND_HEIGHT_TO_HISTORY_ENTRY)?;
+      let mut rune_id_to_holder_count = undo_log.open_table(wtx, RUNE_ID_TO_HOLDER_COUNT)?;
+      let mut rune_id_to_rune_entry = undo_log.open_table(wtx, RUNE_ID_TO_RUNE_ENTRY)?;
+      let mut rune_to_rune_id = undo_log.open_table(wtx, RUNE_TO_RUNE_ID)?;
+      let mut sequence_number_to_rune_id = undo_log.open_table(wtx, SEQUENCE_NUMBER_TO_RUNE_ID)?;
+      let mut transaction_id_to_rune = undo_log.open_table(wtx, TRANSACTION_ID_TO_RUNE)?;
 
       let runes = statistic_to_count
         .get(&Statistic::Runes.into())?
@@ -591,18 +749,25 @@ impl<'index> Updater<'index> {
         .unwrap_or(0);
 
       let mut rune_updater = RuneUpdater {
-        event_sender: self.index.event_sender.as_ref(),
         block_time: block.header.time,
         burned: HashMap::new(),
+        chain: self.index.settings.chain(),
         client: &self.index.client,
+        event_emitter: &mut event_emitter,
         height: self.height,
+        history: HashMap::new(),
+        holder_to_balance: &mut rune_holder_to_balance,
+        id_and_balance_to_holder: &mut rune_id_and_balance_to_holder,
+        id_and_height_to_history: &mut rune_id_and_height_to_history,
         id_to_entry: &mut rune_id_to_rune_entry,
+        id_to_holder_count: &mut rune_id_to_holder_count,
         inscription_id_to_sequence_number: &mut inscription_id_to_sequence_number,
         minimum: Rune::minimum_at_height(
           self.index.settings.chain().network(),
           Height(self.height),
         ),
         outpoint_to_balances: &mut outpoint_to_rune_balances,
+        outpoint_to_txout: &mut outpoint_to_txout,
         rune_to_id: &mut rune_to_rune_id,
         runes,
         sequence_number_to_rune_id: &mut sequence_number_to_rune_id,
@@ -614,11 +779,39 @@ impl<'index> Updater<'index> {
         rune_updater.index_runes(u32::try_from(i).unwrap(), tx, *txid)?;
       }
 
+      rune_count = rune_updater.runes - runes;
+
       rune_updater.update()?;
     }
 
+    if undo_log.is_recording() {
+      for (outpoint, value) in value_cache.drain() {
+        outpoint_to_value.insert(&outpoint.store(), &value)?;
+      }
+    }
+
     height_to_block_header.insert(&self.height, &block.header.store())?;
 
+    for (tx, _txid) in &block.txdata {
+      for input in &tx.input {
+        outpoint_to_txout.remove(&input.previous_output.store())?;
+      }
+    }
+
+    let block_committed = Event::BlockCommitted {
+      block_hash,
+      block_height: self.height,
+      inscription_count,
+      rune_count,
+      timestamp: block.header.time,
+    };
+
+    event_emitter.log(&block_committed)?;
+
+    undo_log.commit(wtx, self.height, self.index.settings.max_reorg_depth())?;
+
+    self.uncommitted_blocks.push(block_committed);
+
     self.height += 1;
     self.outputs_traversed += outputs_in_block;
 
@@ -630,11 +823,52 @@ impl<'index> Updater<'index> {
     Ok(())
   }
 
+  // spent scripts are left in place so that senders can be resolved, and are
+  // removed once the block has been indexed
+  fn index_addresses(
+    wtx: &WriteTransaction,
+    undo_log: &UndoLog,
+    outpoint_to_txout: &mut UndoTable<&OutPointValue, (u64, &[u8])>,
+    txdata: &[(Transaction, Txid)],
+  ) -> Result {
+    let mut script_pubkey_to_outpoint =
+      undo_log.open_multimap_table(wtx, SCRIPT_PUBKEY_TO_OUTPOINT)?;
+
+    for (tx, txid) in txdata {
+      for input in &tx.input {
+        let outpoint = input.previous_output.store();
+
+        if let Some(txout) = outpoint_to_txout.get(&outpoint)? {
+          script_pubkey_to_outpoint.remove(txout.value().1, &outpoint)?;
+        }
+      }
+
+      for (vout, output) in tx.output.iter().enumerate() {
+        if output.script_pubkey.is_op_return() {
+          continue;
+        }
+
+        let outpoint = OutPoint {
+          txid: *txid,
+          vout: vout.try_into().unwrap(),
+        }
+        .store();
+
+        outpoint_to_txout.insert(&outpoint, (output.value, output.script_pubkey.as_bytes()))?;
+        script_pubkey_to_outpoint.insert(output.script_pubkey.as_bytes(), &outpoint)?;
+      }
+    }
+
+    Ok(())
+  }
+
   fn index_transaction_sats(
     &mut self,
     tx: &Transaction,
     txid: Txid,
-    sat_to_satpoint: &mut Table<u64, &SatPointValue>,
+    classifier: &Classifier,
+    sat_to_satpoint: &mut UndoTable<u64, &SatPointValue>,
+    satribute_to_outpoint: &mut UndoMultimapTable<u8, &OutPointValue>,
     input_sat_ranges: &mut VecDeque<(u64, u64)>,
     sat_ranges_written: &mut u64,
     outputs_traversed: &mut u64,
@@ -651,6 +885,7 @@ impl<'index> Updater<'index> {
         txid,
       };
       let mut sats = Vec::new();
+      let mut satributes = BTreeSet::new();
 
       let mut remaining = output.value;
       while remaining > 0 {
@@ -682,6 +917,8 @@ impl<'index> Updater<'index> {
 
         sats.extend_from_slice(&assigned.store());
 
+        satributes.extend(classifier.classify_range(assigned.0, assigned.1));
+
         remaining -= assigned.1 - assigned.0;
 
         *sat_ranges_written += 1;
@@ -689,6 +926,10 @@ impl<'index> Updater<'index> {
 
       *outputs_traversed += 1;
 
+      for satribute in satributes {
+        satribute_to_outpoint.insert(u8::from(satribute), &outpoint.store())?;
+      }
+
       self.range_cache.insert(outpoint.store(), sats);
       self.outputs_inserted_since_flush += 1;
     }
@@ -737,7 +978,11 @@ impl<'index> Updater<'index> {
     Index::increment_statistic(&wtx, Statistic::Commits, 1)?;
     wtx.commit()?;
 
-    Reorg::update_savepoints(self.index, self.height)?;
+    for event in self.uncommitted_blocks.drain(..) {
+      if let Some(sender) = self.index.event_sender.as_ref() {
+        sender.blocking_send(event)?;
+      }
+    }
 
     Ok(())
   }
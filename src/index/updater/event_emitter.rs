@@ -0,0 +1,28 @@
+use super::*;
+
+pub(super) struct EventEmitter<'tx> {
+  pub(super) event_log: Option<UndoTable<'tx, u64, &'static [u8]>>,
+  pub(super) event_sender: Option<Sender<Event>>,
+  pub(super) next_event: u64,
+}
+
+impl<'tx> EventEmitter<'tx> {
+  pub(super) fn emit(&mut self, event: Event) -> Result {
+    self.log(&event)?;
+
+    if let Some(sender) = &self.event_sender {
+      sender.blocking_send(event)?;
+    }
+
+    Ok(())
+  }
+
+  pub(super) fn log(&mut self, event: &Event) -> Result {
+    if let Some(event_log) = &mut self.event_log {
+      event_log.insert(self.next_event, serde_json::to_vec(event)?.as_slice())?;
+      self.next_event += 1;
+    }
+
+    Ok(())
+  }
+}
@@ -1,5 +1,7 @@
 use super::*;
 
+const SIGHASH_SINGLE_ANYONECANPAY: u8 = 0x83;
+
 #[derive(Debug, PartialEq, Copy, Clone)]
 enum Curse {
   DuplicateField,
@@ -18,14 +20,20 @@ pub(super) struct Flotsam {
   inscription_id: InscriptionId,
   offset: u64,
   origin: Origin,
+  txid: Txid,
 }
 
 #[derive(Debug, Clone)]
 enum Origin {
   New {
+    body_hash: Option<sha256::Hash>,
+    content_type: Option<String>,
     cursed: bool,
+    delegate: Option<InscriptionId>,
     fee: u64,
     hidden: bool,
+    metadata: Vec<(String, String)>,
+    metaprotocol: Option<String>,
     parents: Vec<InscriptionId>,
     pointer: Option<u64>,
     reinscription: bool,
@@ -34,33 +42,59 @@ enum Origin {
   },
   Old {
     old_satpoint: SatPoint,
+    old_value: u64,
+    sale: Option<TxOut>,
+    sender: Option<Address<NetworkUnchecked>>,
   },
 }
 
 pub(super) struct InscriptionUpdater<'a, 'tx> {
   pub(super) blessed_inscription_count: u64,
+  pub(super) body_hash_to_sequence_number: &'a mut UndoMultimapTable<'tx, &'static [u8; 32], u32>,
   pub(super) chain: Chain,
-  pub(super) content_type_to_count: &'a mut Table<'tx, Option<&'static [u8]>, u64>,
+  pub(super) collection_and_content_type_to_count:
+    &'a mut UndoTable<'tx, (u32, Option<&'static [u8]>), u64>,
+  pub(super) collection_and_height_to_mints: &'a mut UndoTable<'tx, (u32, u32), u64>,
+  pub(super) collection_and_holder_to_count: &'a mut UndoTable<'tx, (u32, &'static [u8]), u64>,
+  pub(super) collection_to_entry: &'a mut UndoTable<'tx, u32, CollectionEntryValue>,
+  pub(super) content_type_and_sequence_number: &'a mut UndoTable<'tx, (&'static [u8], u32), ()>,
+  pub(super) content_type_to_count: &'a mut UndoTable<'tx, Option<&'static [u8]>, u64>,
   pub(super) cursed_inscription_count: u64,
-  pub(super) event_sender: Option<&'a Sender<Event>>,
+  pub(super) delegate_to_sequence_numbers: &'a mut UndoMultimapTable<'tx, InscriptionIdValue, u32>,
+  pub(super) event_emitter: &'a mut EventEmitter<'tx>,
   pub(super) flotsam: Vec<Flotsam>,
   pub(super) height: u32,
   pub(super) home_inscription_count: u64,
-  pub(super) home_inscriptions: &'a mut Table<'tx, u32, InscriptionIdValue>,
-  pub(super) id_to_sequence_number: &'a mut Table<'tx, InscriptionIdValue, u32>,
+  pub(super) home_inscriptions: &'a mut UndoTable<'tx, u32, InscriptionIdValue>,
+  pub(super) id_to_sequence_number: &'a mut UndoTable<'tx, InscriptionIdValue, u32>,
+  pub(super) index_metadata: bool,
   pub(super) index_transactions: bool,
-  pub(super) inscription_number_to_sequence_number: &'a mut Table<'tx, i32, u32>,
+  pub(super) index_transfers: bool,
+  pub(super) inscription_number_to_sequence_number: &'a mut UndoTable<'tx, i32, u32>,
   pub(super) lost_sats: u64,
+  pub(super) metadata_key_and_value_to_sequence_number:
+    &'a mut UndoMultimapTable<'tx, (&'static str, &'static str), u32>,
+  pub(super) metaprotocol_to_count: &'a mut UndoTable<'tx, &'static str, u64>,
+  pub(super) metaprotocol_to_sequence_number: &'a mut UndoMultimapTable<'tx, &'static str, u32>,
   pub(super) next_sequence_number: u32,
-  pub(super) outpoint_to_value: &'a mut Table<'tx, &'static OutPointValue, u64>,
+  pub(super) outpoint_to_txout:
+    &'a mut UndoTable<'tx, &'static OutPointValue, (u64, &'static [u8])>,
+  pub(super) outpoint_to_value: &'a mut UndoTable<'tx, &'static OutPointValue, u64>,
   pub(super) reward: u64,
   pub(super) transaction_buffer: Vec<u8>,
-  pub(super) transaction_id_to_transaction: &'a mut Table<'tx, &'static TxidValue, &'static [u8]>,
-  pub(super) sat_to_sequence_number: &'a mut MultimapTable<'tx, u64, u32>,
-  pub(super) satpoint_to_sequence_number: &'a mut MultimapTable<'tx, &'static SatPointValue, u32>,
-  pub(super) sequence_number_to_children: &'a mut MultimapTable<'tx, u32, u32>,
-  pub(super) sequence_number_to_entry: &'a mut Table<'tx, u32, InscriptionEntryValue>,
-  pub(super) sequence_number_to_satpoint: &'a mut Table<'tx, u32, &'static SatPointValue>,
+  pub(super) transaction_id_to_transaction:
+    &'a mut UndoTable<'tx, &'static TxidValue, &'static [u8]>,
+  pub(super) sat_to_sequence_number: &'a mut UndoMultimapTable<'tx, u64, u32>,
+  pub(super) satpoint_to_sequence_number:
+    &'a mut UndoMultimapTable<'tx, &'static SatPointValue, u32>,
+  pub(super) sequence_number_to_children: &'a mut UndoMultimapTable<'tx, u32, u32>,
+  pub(super) sequence_number_to_content_type: &'a mut UndoTable<'tx, u32, &'static [u8]>,
+  pub(super) sequence_number_to_entry: &'a mut UndoTable<'tx, u32, InscriptionEntryValue>,
+  pub(super) sequence_number_to_holder: &'a mut UndoTable<'tx, u32, &'static [u8]>,
+  pub(super) sequence_number_to_sale_entry: &'a mut UndoTable<'tx, (u32, u32), SaleEntryValue>,
+  pub(super) sequence_number_to_satpoint: &'a mut UndoTable<'tx, u32, &'static SatPointValue>,
+  pub(super) sequence_number_to_transfer_entry:
+    &'a mut UndoTable<'tx, (u32, u32), TransferEntryValue>,
   pub(super) timestamp: u32,
   pub(super) unbound_inscriptions: u64,
   pub(super) value_cache: &'a mut HashMap<OutPoint, u64>,
@@ -92,25 +126,6 @@ impl<'a, 'tx> InscriptionUpdater<'a, 'tx> {
         continue;
       }
 
-      // find existing inscriptions on input (transfers of inscriptions)
-      for (old_satpoint, inscription_id) in Index::inscriptions_on_output(
-        self.satpoint_to_sequence_number,
-        self.sequence_number_to_entry,
-        tx_in.previous_output,
-      )? {
-        let offset = total_input_value + old_satpoint.offset;
-        floating_inscriptions.push(Flotsam {
-          offset,
-          inscription_id,
-          origin: Origin::Old { old_satpoint },
-        });
-
-        inscribed_offsets
-          .entry(offset)
-          .or_insert((inscription_id, 0))
-          .1 += 1;
-      }
-
       let offset = total_input_value;
 
       // multi-level cache for UTXO set to get to the input amount
@@ -131,6 +146,68 @@ impl<'a, 'tx> InscriptionUpdater<'a, 'tx> {
         })?
       };
 
+      // find existing inscriptions on input (transfers of inscriptions)
+      let old_inscriptions = Index::inscriptions_on_output(
+        &**self.satpoint_to_sequence_number,
+        &**self.sequence_number_to_entry,
+        tx_in.previous_output,
+      )?;
+
+      let sender = if old_inscriptions.is_empty() {
+        None
+      } else {
+        self
+          .outpoint_to_txout
+          .get(&tx_in.previous_output.store())?
+          .and_then(|txout| {
+            self
+              .chain
+              .address_from_script(Script::from_bytes(txout.value().1))
+              .ok()
+          })
+          .map(|address| uncheck(&address))
+      };
+
+      // a seller input signed SIGHASH_SINGLE|ANYONECANPAY commits only to the
+      // payment output at the same index, as in PSBT marketplace listings
+      let sale = if !old_inscriptions.is_empty() && Self::is_listing(tx_in) {
+        tx.output.get(input_index).cloned()
+      } else {
+        None
+      };
+
+      // every inscription on a listed input is sold for the one payment, so the
+      // price is split between them, with any remainder going to the first
+      let inscription_count = u64::try_from(old_inscriptions.len()).unwrap();
+
+      for (i, (old_satpoint, inscription_id)) in old_inscriptions.into_iter().enumerate() {
+        let offset = offset + old_satpoint.offset;
+        floating_inscriptions.push(Flotsam {
+          offset,
+          inscription_id,
+          origin: Origin::Old {
+            old_satpoint,
+            old_value: current_input_value,
+            sale: sale.as_ref().map(|payment| TxOut {
+              value: payment.value / inscription_count
+                + if i == 0 {
+                  payment.value % inscription_count
+                } else {
+                  0
+                },
+              script_pubkey: payment.script_pubkey.clone(),
+            }),
+            sender: sender.clone(),
+          },
+          txid,
+        });
+
+        inscribed_offsets
+          .entry(offset)
+          .or_insert((inscription_id, 0))
+          .1 += 1;
+      }
+
       total_input_value += current_input_value;
 
       // go through all inscriptions in this input
@@ -210,9 +287,18 @@ impl<'a, 'tx> InscriptionUpdater<'a, 'tx> {
           inscription_id,
           offset,
           origin: Origin::New {
+            body_hash: inscription.payload.body_hash(),
+            content_type: inscription.payload.content_type().map(Into::into),
             cursed: curse.is_some() && !jubilant,
+            delegate: inscription.payload.delegate(),
             fee: 0,
             hidden: inscription.payload.hidden(),
+            metadata: if self.index_metadata {
+              inscription.payload.metadata_fields()
+            } else {
+              Vec::new()
+            },
+            metaprotocol: inscription.payload.metaprotocol().map(Into::into),
             parents: inscription.payload.parents(),
             pointer: inscription.payload.pointer(),
             reinscription: inscribed_offsets.contains_key(&offset),
@@ -221,6 +307,7 @@ impl<'a, 'tx> InscriptionUpdater<'a, 'tx> {
               || inscription.payload.unrecognized_even_field,
             vindicated: curse.is_some() && jubilant,
           },
+          txid,
         });
 
         inscribed_offsets
@@ -345,7 +432,9 @@ impl<'a, 'tx> InscriptionUpdater<'a, 'tx> {
         _ => new_satpoint,
       };
 
-      self.update_inscription_location(input_sat_ranges, flotsam, new_satpoint)?;
+      let output = &tx.output[usize::try_from(new_satpoint.outpoint.vout).unwrap()];
+
+      self.update_inscription_location(input_sat_ranges, flotsam, new_satpoint, Some(output))?;
     }
 
     if is_coinbase {
@@ -354,7 +443,7 @@ impl<'a, 'tx> InscriptionUpdater<'a, 'tx> {
           outpoint: OutPoint::null(),
           offset: self.lost_sats + flotsam.offset - output_value,
         };
-        self.update_inscription_location(input_sat_ranges, flotsam, new_satpoint)?;
+        self.update_inscription_location(input_sat_ranges, flotsam, new_satpoint, None)?;
       }
       self.lost_sats += self.reward - output_value;
       Ok(())
@@ -368,6 +457,99 @@ impl<'a, 'tx> InscriptionUpdater<'a, 'tx> {
     }
   }
 
+  fn record_sale(
+    &mut self,
+    inscription_id: InscriptionId,
+    sequence_number: u32,
+    payment: TxOut,
+    output: &TxOut,
+    buyer: Option<Address<NetworkUnchecked>>,
+    txid: Txid,
+  ) -> Result {
+    let next = self
+      .sequence_number_to_sale_entry
+      .range((sequence_number, 0)..=(sequence_number, u32::MAX))?
+      .next_back()
+      .transpose()?
+      .map(|(key, _entry)| key.value().1 + 1)
+      .unwrap_or(0);
+
+    self.event_emitter.emit(Event::InscriptionSold {
+      block_height: self.height,
+      buyer,
+      inscription_id,
+      price: payment.value,
+      seller: self
+        .chain
+        .address_from_script(&payment.script_pubkey)
+        .ok()
+        .map(|address| uncheck(&address)),
+      txid,
+    })?;
+
+    self.sequence_number_to_sale_entry.insert(
+      (sequence_number, next),
+      SaleEntry {
+        buyer: output.script_pubkey.clone(),
+        height: self.height,
+        price: payment.value,
+        seller: payment.script_pubkey,
+        txid,
+      }
+      .store(),
+    )?;
+
+    Ok(())
+  }
+
+  fn record_transfer(
+    &mut self,
+    sequence_number: u32,
+    old_satpoint: Option<SatPoint>,
+    new_satpoint: SatPoint,
+    to: Option<ScriptBuf>,
+    txid: Txid,
+  ) -> Result {
+    let (next, from) = self
+      .sequence_number_to_transfer_entry
+      .range((sequence_number, 0)..=(sequence_number, u32::MAX))?
+      .next_back()
+      .transpose()?
+      .map(|(key, entry)| (key.value().1 + 1, TransferEntry::load(entry.value()).to))
+      .unwrap_or_default();
+
+    self.sequence_number_to_transfer_entry.insert(
+      (sequence_number, next),
+      TransferEntry {
+        from,
+        height: self.height,
+        new_satpoint,
+        old_satpoint,
+        to,
+        txid,
+      }
+      .store(),
+    )?;
+
+    Ok(())
+  }
+
+  /// Recognizes only key path spends, a single 65-byte signature witness
+  /// element, and two element spends whose first element is the signature,
+  /// where the signature's sighash type is SIGHASH_SINGLE|ANYONECANPAY (0x83).
+  fn is_listing(tx_in: &TxIn) -> bool {
+    let signature = match tx_in.witness.len() {
+      1 => tx_in
+        .witness
+        .nth(0)
+        .filter(|signature| signature.len() == 65),
+      2 => tx_in.witness.nth(0),
+      _ => None,
+    };
+
+    signature.and_then(|signature| signature.last()).copied() == Some(SIGHASH_SINGLE_ANYONECANPAY)
+  }
+
   fn calculate_sat(
     input_sat_ranges: Option<&VecDeque<(u64, u64)>>,
     input_offset: u64,
@@ -387,15 +569,170 @@ impl<'a, 'tx> InscriptionUpdater<'a, 'tx> {
     unreachable!()
   }
 
+  fn record_collection_mint(&mut self, parent: u32, content_type: Option<&[u8]>) -> Result {
+    let mut entry = self
+      .collection_to_entry
+      .get(parent)?
+      .map(|entry| CollectionEntry::load(entry.value()))
+      .unwrap_or(CollectionEntry {
+        first_child_height: self.height,
+        ..default()
+      });
+
+    entry.children += 1;
+    entry.last_child_height = self.height;
+
+    self.collection_to_entry.insert(parent, entry.store())?;
+
+    let mints = self
+      .collection_and_height_to_mints
+      .get((parent, self.height))?
+      .map(|mints| mints.value())
+      .unwrap_or_default();
+
+    self
+      .collection_and_height_to_mints
+      .insert((parent, self.height), mints + 1)?;
+
+    let content_type_count = self
+      .collection_and_content_type_to_count
+      .get((parent, content_type))?
+      .map(|count| count.value())
+      .unwrap_or_default();
+
+    self
+      .collection_and_content_type_to_count
+      .insert((parent, content_type), content_type_count + 1)?;
+
+    let grandparents = InscriptionEntry::load(
+      self
+        .sequence_number_to_entry
+        .get(parent)?
+        .ok_or_else(|| anyhow!("no inscription entry for sequence number {parent}"))?
+        .value(),
+    )
+    .parents;
+
+    for grandparent in grandparents {
+      let mut entry = CollectionEntry::load(
+        self
+          .collection_to_entry
+          .get(grandparent)?
+          .ok_or_else(|| anyhow!("no collection entry for sequence number {grandparent}"))?
+          .value(),
+      );
+
+      entry.grandchildren += 1;
+
+      self
+        .collection_to_entry
+        .insert(grandparent, entry.store())?;
+    }
+
+    Ok(())
+  }
+
+  fn update_collection_holder(
+    &mut self,
+    sequence_number: u32,
+    parents: &[u32],
+    holder: Option<&[u8]>,
+  ) -> Result {
+    let old_holder = self
+      .sequence_number_to_holder
+      .get(sequence_number)?
+      .map(|holder| holder.value().to_vec());
+
+    if old_holder.as_deref() == holder {
+      return Ok(());
+    }
+
+    if let Some(holder) = holder {
+      self
+        .sequence_number_to_holder
+        .insert(sequence_number, holder)?;
+    } else {
+      self.sequence_number_to_holder.remove(sequence_number)?;
+    }
+
+    for &parent in parents {
+      let mut entry = CollectionEntry::load(
+        self
+          .collection_to_entry
+          .get(parent)?
+          .ok_or_else(|| anyhow!("no collection entry for sequence number {parent}"))?
+          .value(),
+      );
+
+      if let Some(old_holder) = old_holder.as_deref() {
+        let count = self
+          .collection_and_holder_to_count
+          .get((parent, old_holder))?
+          .map(|count| count.value())
+          .unwrap_or_default();
+
+        match count {
+          0 => bail!("collection {parent} has no children held by previous holder"),
+          1 => {
+            self
+              .collection_and_holder_to_count
+              .remove((parent, old_holder))?;
+            entry.holders -= 1;
+          }
+          _ => {
+            self
+              .collection_and_holder_to_count
+              .insert((parent, old_holder), count - 1)?;
+          }
+        }
+      }
+
+      if let Some(holder) = holder {
+        let count = self
+          .collection_and_holder_to_count
+          .get((parent, holder))?
+          .map(|count| count.value())
+          .unwrap_or_default();
+
+        if count == 0 {
+          entry.holders += 1;
+        }
+
+        self
+          .collection_and_holder_to_count
+          .insert((parent, holder), count + 1)?;
+      }
+
+      self.collection_to_entry.insert(parent, entry.store())?;
+    }
+
+    Ok(())
+  }
+
   fn update_inscription_location(
     &mut self,
     input_sat_ranges: Option<&VecDeque<(u64, u64)>>,
     flotsam: Flotsam,
     new_satpoint: SatPoint,
+    output: Option<&TxOut>,
   ) -> Result {
     let inscription_id = flotsam.inscription_id;
-    let (unbound, sequence_number) = match flotsam.origin {
-      Origin::Old { old_satpoint } => {
+
+    let receiver = output.and_then(|output| {
+      self
+        .chain
+        .address_from_script(&output.script_pubkey)
+        .ok()
+        .map(|address| uncheck(&address))
+    });
+
+    let (unbound, sequence_number, old_satpoint, parents) = match flotsam.origin {
+      Origin::Old {
+        old_satpoint,
+        old_value,
+        sale,
+        sender,
+      } => {
         self
           .satpoint_to_sequence_number
           .remove_all(&old_satpoint.store())?;
@@ -406,22 +743,54 @@ impl<'a, 'tx> InscriptionUpdater<'a, 'tx> {
           .unwrap()
           .value();
 
-        if let Some(sender) = self.event_sender {
-          sender.blocking_send(Event::InscriptionTransferred {
-            block_height: self.height,
-            inscription_id,
-            new_location: new_satpoint,
-            old_location: old_satpoint,
-            sequence_number,
-          })?;
+        let InscriptionEntry {
+          charms, parents, ..
+        } = InscriptionEntry::load(
+          self
+            .sequence_number_to_entry
+            .get(sequence_number)?
+            .unwrap()
+            .value(),
+        );
+
+        self.event_emitter.emit(Event::InscriptionTransferred {
+          block_height: self.height,
+          charms,
+          inscription_id,
+          new_location: new_satpoint,
+          new_value: output.map(|output| output.value),
+          old_location: old_satpoint,
+          old_value,
+          receiver: receiver.clone(),
+          sender,
+          sequence_number,
+          txid: flotsam.txid,
+        })?;
+
+        if let (Some(payment), Some(output)) = (sale, output) {
+          if payment.script_pubkey != output.script_pubkey {
+            self.record_sale(
+              inscription_id,
+              sequence_number,
+              payment,
+              output,
+              receiver,
+              flotsam.txid,
+            )?;
+          }
         }
 
-        (false, sequence_number)
+        (false, sequence_number, Some(old_satpoint), parents)
       }
       Origin::New {
+        body_hash,
+        content_type,
         cursed,
+        delegate,
         fee,
         hidden,
+        metadata,
+        metaprotocol,
         parents,
         pointer: _,
         reinscription,
@@ -498,17 +867,89 @@ impl<'a, 'tx> InscriptionUpdater<'a, 'tx> {
           })
           .collect::<Result<Vec<u32>>>()?;
 
-        if let Some(sender) = self.event_sender {
-          sender.blocking_send(Event::InscriptionCreated {
-            block_height: self.height,
-            charms,
-            inscription_id,
-            location: (!unbound).then_some(new_satpoint),
-            parent_inscription_ids: parents,
-            sequence_number,
-          })?;
+        if let Some(body_hash) = body_hash {
+          self
+            .body_hash_to_sequence_number
+            .insert(&body_hash.to_byte_array(), sequence_number)?;
+        }
+
+        if let Some(delegate) = delegate {
+          self
+            .delegate_to_sequence_numbers
+            .insert(&delegate.store(), sequence_number)?;
+        }
+
+        // inscriptions with a delegate serve the delegate's content, so they are indexed under its type
+        let effective_content_type = match delegate
+          .map(|delegate| self.id_to_sequence_number.get(&delegate.store()))
+          .transpose()?
+          .flatten()
+          .map(|delegate_sequence_number| delegate_sequence_number.value())
+        {
+          Some(delegate_sequence_number) => self
+            .sequence_number_to_content_type
+            .get(delegate_sequence_number)?
+            .map(|content_type| content_type.value().to_vec()),
+          None => content_type.clone().map(String::into_bytes),
+        };
+
+        for parent_sequence_number in &parent_sequence_numbers {
+          self
+            .record_collection_mint(*parent_sequence_number, effective_content_type.as_deref())?;
+        }
+
+        if let Some(effective_content_type) = effective_content_type {
+          self
+            .content_type_and_sequence_number
+            .insert((effective_content_type.as_slice(), sequence_number), ())?;
+
+          self
+            .sequence_number_to_content_type
+            .insert(sequence_number, effective_content_type.as_slice())?;
+        }
+
+        for (key, value) in &metadata {
+          self
+            .metadata_key_and_value_to_sequence_number
+            .insert((key.as_str(), value.as_str()), sequence_number)?;
+        }
+
+        if let Some(metaprotocol) = metaprotocol.as_deref() {
+          self
+            .metaprotocol_to_sequence_number
+            .insert(metaprotocol, sequence_number)?;
+
+          let metaprotocol_count = self
+            .metaprotocol_to_count
+            .get(metaprotocol)?
+            .map(|entry| entry.value())
+            .unwrap_or_default();
+
+          self
+            .metaprotocol_to_count
+            .insert(metaprotocol, metaprotocol_count + 1)?;
         }
 
+        self.event_emitter.emit(Event::InscriptionCreated {
+          block_height: self.height,
+          charms,
+          content_type,
+          delegate,
+          fee,
+          inscription_id,
+          location: (!unbound).then_some(new_satpoint),
+          metaprotocol,
+          parent_inscription_ids: parents,
+          receiver: if unbound { None } else { receiver },
+          sequence_number,
+          txid: flotsam.txid,
+          value: if unbound {
+            None
+          } else {
+            output.map(|output| output.value)
+          },
+        })?;
+
         self.sequence_number_to_entry.insert(
           sequence_number,
           &InscriptionEntry {
@@ -517,7 +958,7 @@ impl<'a, 'tx> InscriptionUpdater<'a, 'tx> {
             height: self.height,
             id: inscription_id,
             inscription_number,
-            parents: parent_sequence_numbers,
+            parents: parent_sequence_numbers.clone(),
             sat,
             sequence_number,
             timestamp: self.timestamp,
@@ -541,21 +982,52 @@ impl<'a, 'tx> InscriptionUpdater<'a, 'tx> {
           }
         }
 
-        (unbound, sequence_number)
+        (unbound, sequence_number, None, parent_sequence_numbers)
       }
     };
 
+    if !parents.is_empty() {
+      self.update_collection_holder(
+        sequence_number,
+        &parents,
+        output
+          .filter(|_| !unbound)
+          .map(|output| output.script_pubkey.as_bytes()),
+      )?;
+    }
+
+    if let (false, Some(output)) = (unbound, output) {
+      self.outpoint_to_txout.insert(
+        &new_satpoint.outpoint.store(),
+        (output.value, output.script_pubkey.as_bytes()),
+      )?;
+    }
+
     let satpoint = if unbound {
       let new_unbound_satpoint = SatPoint {
         outpoint: unbound_outpoint(),
         offset: self.unbound_inscriptions,
       };
       self.unbound_inscriptions += 1;
-      new_unbound_satpoint.store()
+      new_unbound_satpoint
     } else {
-      new_satpoint.store()
+      new_satpoint
     };
 
+    if self.index_transfers {
+      self.record_transfer(
+        sequence_number,
+        old_satpoint,
+        satpoint,
+        output
+          .filter(|_| !unbound)
+          .map(|output| output.script_pubkey.clone()),
+        flotsam.txid,
+      )?;
+    }
+
+    let satpoint = satpoint.store();
+
     self
       .satpoint_to_sequence_number
       .insert(&satpoint, sequence_number)?;
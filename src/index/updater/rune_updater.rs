@@ -1,43 +1,53 @@
-use super::*;
+use {super::*, crate::index::event::Allocation};
 
 pub(super) struct RuneUpdater<'a, 'tx, 'client> {
   pub(super) block_time: u32,
   pub(super) burned: HashMap<RuneId, Lot>,
+  pub(super) chain: Chain,
   pub(super) client: &'client Client,
-  pub(super) event_sender: Option<&'a Sender<Event>>,
+  pub(super) event_emitter: &'a mut EventEmitter<'tx>,
   pub(super) height: u32,
-  pub(super) id_to_entry: &'a mut Table<'tx, RuneIdValue, RuneEntryValue>,
+  pub(super) history: HashMap<RuneId, RuneHistoryEntry>,
+  pub(super) holder_to_balance: &'a mut UndoTable<'tx, (RuneIdValue, &'static [u8]), u128>,
+  pub(super) id_and_balance_to_holder:
+    &'a mut UndoMultimapTable<'tx, (RuneIdValue, u128), &'static [u8]>,
+  pub(super) id_and_height_to_history:
+    &'a mut UndoTable<'tx, (RuneIdValue, u32), RuneHistoryEntryValue>,
+  pub(super) id_to_entry: &'a mut UndoTable<'tx, RuneIdValue, RuneEntryValue>,
+  pub(super) id_to_holder_count: &'a mut UndoTable<'tx, RuneIdValue, u64>,
   pub(super) inscription_id_to_sequence_number: &'a Table<'tx, InscriptionIdValue, u32>,
   pub(super) minimum: Rune,
-  pub(super) outpoint_to_balances: &'a mut Table<'tx, &'static OutPointValue, &'static [u8]>,
-  pub(super) rune_to_id: &'a mut Table<'tx, u128, RuneIdValue>,
+  pub(super) outpoint_to_balances: &'a mut UndoTable<'tx, &'static OutPointValue, &'static [u8]>,
+  pub(super) outpoint_to_txout:
+    &'a mut UndoTable<'tx, &'static OutPointValue, (u64, &'static [u8])>,
+  pub(super) rune_to_id: &'a mut UndoTable<'tx, u128, RuneIdValue>,
   pub(super) runes: u64,
-  pub(super) sequence_number_to_rune_id: &'a mut Table<'tx, u32, RuneIdValue>,
-  pub(super) statistic_to_count: &'a mut Table<'tx, u64, u64>,
-  pub(super) transaction_id_to_rune: &'a mut Table<'tx, &'static TxidValue, u128>,
+  pub(super) sequence_number_to_rune_id: &'a mut UndoTable<'tx, u32, RuneIdValue>,
+  pub(super) statistic_to_count: &'a mut UndoTable<'tx, u64, u64>,
+  pub(super) transaction_id_to_rune: &'a mut UndoTable<'tx, &'static TxidValue, u128>,
 }
 
 impl<'a, 'tx, 'client> RuneUpdater<'a, 'tx, 'client> {
   pub(super) fn index_runes(&mut self, tx_index: u32, tx: &Transaction, txid: Txid) -> Result<()> {
     let artifact = Runestone::decipher(tx);
 
-    let mut unallocated = self.unallocated(tx)?;
+    let (mut unallocated, inputs) = self.unallocated(tx)?;
 
     let mut allocated: Vec<HashMap<RuneId, Lot>> = vec![HashMap::new(); tx.output.len()];
 
+    let mut allocations: Vec<(usize, RuneId, Lot, Allocation)> = Vec::new();
+
     if let Some(artifact) = &artifact {
       if let Some(id) = artifact.mint() {
         if let Some(amount) = self.mint(id)? {
           *unallocated.entry(id).or_default() += amount;
 
-          if let Some(sender) = self.event_sender {
-            sender.blocking_send(Event::RuneMinted {
-              block_height: self.height,
-              txid,
-              rune_id: id,
-              amount: amount.n(),
-            })?;
-          }
+          self.event_emitter.emit(Event::RuneMinted {
+            block_height: self.height,
+            txid,
+            rune_id: id,
+            amount: amount.n(),
+          })?;
         }
       }
 
@@ -49,7 +59,7 @@ impl<'a, 'tx, 'client> RuneUpdater<'a, 'tx, 'client> {
             runestone.etching.unwrap().premine.unwrap_or_default();
         }
 
-        for Edict { id, amount, output } in runestone.edicts.iter().copied() {
+        for (i, Edict { id, amount, output }) in runestone.edicts.iter().copied().enumerate() {
           let amount = Lot(amount);
 
           // edicts with output values greater than the number of outputs
@@ -75,6 +85,7 @@ impl<'a, 'tx, 'client> RuneUpdater<'a, 'tx, 'client> {
             if amount > 0 {
               *balance -= amount;
               *allocated[output].entry(id).or_default() += amount;
+              allocations.push((output, id, amount, Allocation::Edict(i)));
             }
           };
 
@@ -129,7 +140,18 @@ impl<'a, 'tx, 'client> RuneUpdater<'a, 'tx, 'client> {
 
     let mut burned: HashMap<RuneId, Lot> = HashMap::new();
 
-    if let Some(Artifact::Cenotaph(_)) = artifact {
+    if let Some(Artifact::Cenotaph(cenotaph)) = &artifact {
+      self.event_emitter.emit(Event::RuneCenotaph {
+        block_height: self.height,
+        burned: unallocated
+          .iter()
+          .filter(|(_id, balance)| **balance > 0)
+          .map(|(id, balance)| (*id, balance.n()))
+          .collect(),
+        flaws: cenotaph.flaw.into_iter().collect(),
+        txid,
+      })?;
+
       for (id, balance) in unallocated {
         *burned.entry(id).or_default() += balance;
       }
@@ -141,6 +163,12 @@ impl<'a, 'tx, 'client> RuneUpdater<'a, 'tx, 'client> {
         })
         .unwrap_or_default();
 
+      let allocation = if pointer.is_some() {
+        Allocation::Pointer
+      } else {
+        Allocation::Default
+      };
+
       // assign all un-allocated runes to the default output, or the first non
       // OP_RETURN output if there is no default
       if let Some(vout) = pointer
@@ -157,6 +185,7 @@ impl<'a, 'tx, 'client> RuneUpdater<'a, 'tx, 'client> {
         for (id, balance) in unallocated {
           if balance > 0 {
             *allocated[vout].entry(id).or_default() += balance;
+            allocations.push((vout, id, balance, allocation));
           }
         }
       } else {
@@ -197,45 +226,95 @@ impl<'a, 'tx, 'client> RuneUpdater<'a, 'tx, 'client> {
 
       for (id, balance) in balances {
         Index::encode_rune_balance(id, balance.n(), &mut buffer);
-
-        if let Some(sender) = self.event_sender {
-          sender.blocking_send(Event::RuneTransferred {
-            outpoint,
-            block_height: self.height,
-            txid,
-            rune_id: id,
-            amount: balance.0,
-          })?;
-        }
+        self.credit_holder(id, &tx.output[vout].script_pubkey, balance.n())?;
       }
 
       self
         .outpoint_to_balances
         .insert(&outpoint.store(), buffer.as_slice())?;
+
+      self.outpoint_to_txout.insert(
+        &outpoint.store(),
+        (
+          tx.output[vout].value,
+          tx.output[vout].script_pubkey.as_bytes(),
+        ),
+      )?;
+    }
+
+    // emit one transfer event per edict or pointer allocation
+    allocations.sort_by_key(|(vout, id, ..)| (*vout, *id));
+
+    for (vout, id, amount, allocation) in allocations {
+      if tx.output[vout].script_pubkey.is_op_return() {
+        continue;
+      }
+
+      let inputs = inputs.get(&id).cloned().unwrap_or_default();
+
+      let sender = match inputs.first() {
+        Some(input) => self
+          .script_pubkey(*input)?
+          .and_then(|script_pubkey| self.chain.address_from_script(&script_pubkey).ok())
+          .map(|address| uncheck(&address)),
+        None => None,
+      };
+
+      self.event_emitter.emit(Event::RuneTransferred {
+        allocation,
+        amount: amount.n(),
+        block_height: self.height,
+        inputs,
+        outpoint: OutPoint {
+          txid,
+          vout: vout.try_into().unwrap(),
+        },
+        receiver: self
+          .chain
+          .address_from_script(&tx.output[vout].script_pubkey)
+          .ok()
+          .map(|address| uncheck(&address)),
+        rune_id: id,
+        sender,
+        txid,
+      })?;
     }
 
     // increment entries with burned runes
     for (id, amount) in burned {
       *self.burned.entry(id).or_default() += amount;
 
-      if let Some(sender) = self.event_sender {
-        sender.blocking_send(Event::RuneBurned {
-          block_height: self.height,
-          txid,
-          rune_id: id,
-          amount: amount.n(),
-        })?;
-      }
+      self.event_emitter.emit(Event::RuneBurned {
+        block_height: self.height,
+        txid,
+        rune_id: id,
+        amount: amount.n(),
+      })?;
     }
 
     Ok(())
   }
 
-  pub(super) fn update(self) -> Result {
+  pub(super) fn update(mut self) -> Result {
     for (rune_id, burned) in self.burned {
       let mut entry = RuneEntry::load(self.id_to_entry.get(&rune_id.store())?.unwrap().value());
       entry.burned = entry.burned.checked_add(burned.n()).unwrap();
       self.id_to_entry.insert(&rune_id.store(), entry.store())?;
+
+      self.history.entry(rune_id).or_default().burned += burned.n();
+    }
+
+    // running totals are stored with each entry so that supply at a height
+    // can be read from a single entry
+    for (rune_id, mut history) in self.history {
+      let entry = RuneEntry::load(self.id_to_entry.get(&rune_id.store())?.unwrap().value());
+
+      history.total_burned = entry.burned;
+      history.total_mints = entry.mints;
+
+      self
+        .id_and_height_to_history
+        .insert((rune_id.store(), self.height), history.store())?;
     }
 
     Ok(())
@@ -260,6 +339,9 @@ impl<'a, 'tx, 'client> RuneUpdater<'a, 'tx, 'client> {
       .statistic_to_count
       .insert(&Statistic::Runes.into(), self.runes)?;
 
+    // record the etching block so history starts at the premine
+    self.history.entry(id).or_default();
+
     let entry = match artifact {
       Artifact::Cenotaph(_) => RuneEntry {
         block: id.block,
@@ -308,13 +390,11 @@ impl<'a, 'tx, 'client> RuneUpdater<'a, 'tx, 'client> {
 
     self.id_to_entry.insert(id.store(), entry.store())?;
 
-    if let Some(sender) = self.event_sender {
-      sender.blocking_send(Event::RuneEtched {
-        block_height: self.height,
-        txid,
-        rune_id: id,
-      })?;
-    }
+    self.event_emitter.emit(Event::RuneEtched {
+      block_height: self.height,
+      txid,
+      rune_id: id,
+    })?;
 
     let inscription_id = InscriptionId { txid, index: 0 };
 
@@ -396,6 +476,8 @@ impl<'a, 'tx, 'client> RuneUpdater<'a, 'tx, 'client> {
 
     self.id_to_entry.insert(&id.store(), rune_entry.store())?;
 
+    self.history.entry(id).or_default().mints += 1;
+
     Ok(Some(Lot(amount)))
   }
 
@@ -466,12 +548,20 @@ impl<'a, 'tx, 'client> RuneUpdater<'a, 'tx, 'client> {
     Ok(false)
   }
 
-  fn unallocated(&mut self, tx: &Transaction) -> Result<HashMap<RuneId, Lot>> {
+  fn unallocated(
+    &mut self,
+    tx: &Transaction,
+  ) -> Result<(HashMap<RuneId, Lot>, HashMap<RuneId, Vec<OutPoint>>)> {
     // map of rune ID to un-allocated balance of that rune
     let mut unallocated: HashMap<RuneId, Lot> = HashMap::new();
 
+    // map of rune ID to the inputs that carried that rune
+    let mut inputs: HashMap<RuneId, Vec<OutPoint>> = HashMap::new();
+
     // increment unallocated runes with the runes in tx inputs
     for input in &tx.input {
+      let mut balances = Vec::new();
+
       if let Some(guard) = self
         .outpoint_to_balances
         .remove(&input.previous_output.store())?
@@ -481,11 +571,111 @@ impl<'a, 'tx, 'client> RuneUpdater<'a, 'tx, 'client> {
         while i < buffer.len() {
           let ((id, balance), len) = Index::decode_rune_balance(&buffer[i..]).unwrap();
           i += len;
-          *unallocated.entry(id).or_default() += balance;
+          balances.push((id, balance));
         }
       }
+
+      if balances.is_empty() {
+        continue;
+      }
+
+      let script_pubkey = self.script_pubkey(input.previous_output)?;
+
+      for (id, balance) in balances {
+        *unallocated.entry(id).or_default() += balance;
+        inputs.entry(id).or_default().push(input.previous_output);
+        self.history.entry(id).or_default().transferred += balance;
+
+        if let Some(script_pubkey) = &script_pubkey {
+          self.debit_holder(id, script_pubkey, balance)?;
+        }
+      }
+    }
+
+    Ok((unallocated, inputs))
+  }
+
+  fn script_pubkey(&self, outpoint: OutPoint) -> Result<Option<ScriptBuf>> {
+    Ok(
+      self
+        .outpoint_to_txout
+        .get(&outpoint.store())?
+        .map(|txout| ScriptBuf::from_bytes(txout.value().1.to_vec())),
+    )
+  }
+
+  fn credit_holder(&mut self, id: RuneId, script_pubkey: &Script, amount: u128) -> Result {
+    let key = (id.store(), script_pubkey.as_bytes());
+
+    let balance = self
+      .holder_to_balance
+      .get(&key)?
+      .map(|balance| balance.value())
+      .unwrap_or_default();
+
+    if balance == 0 {
+      self.adjust_holder_count(id, 1)?;
+    } else {
+      self
+        .id_and_balance_to_holder
+        .remove(&(id.store(), balance), script_pubkey.as_bytes())?;
     }
 
-    Ok(unallocated)
+    let balance = balance + amount;
+
+    self.holder_to_balance.insert(&key, balance)?;
+    self
+      .id_and_balance_to_holder
+      .insert(&(id.store(), balance), script_pubkey.as_bytes())?;
+
+    Ok(())
+  }
+
+  fn debit_holder(&mut self, id: RuneId, script_pubkey: &Script, amount: u128) -> Result {
+    let key = (id.store(), script_pubkey.as_bytes());
+
+    let balance = self
+      .holder_to_balance
+      .get(&key)?
+      .map(|balance| balance.value())
+      .unwrap_or_default();
+
+    let remaining = balance
+      .checked_sub(amount)
+      .ok_or_else(|| anyhow!("rune {id} holder balance {balance} less than debit {amount}"))?;
+
+    self
+      .id_and_balance_to_holder
+      .remove(&(id.store(), balance), script_pubkey.as_bytes())?;
+
+    if remaining == 0 {
+      self.holder_to_balance.remove(&key)?;
+      self.adjust_holder_count(id, -1)?;
+    } else {
+      self.holder_to_balance.insert(&key, remaining)?;
+      self
+        .id_and_balance_to_holder
+        .insert(&(id.store(), remaining), script_pubkey.as_bytes())?;
+    }
+
+    Ok(())
+  }
+
+  fn adjust_holder_count(&mut self, id: RuneId, delta: i64) -> Result {
+    let count = self
+      .id_to_holder_count
+      .get(&id.store())?
+      .map(|count| count.value())
+      .unwrap_or_default()
+      .checked_add_signed(delta)
+      .ok_or_else(|| anyhow!("rune {id} holder count underflow"))?;
+
+    if count == 0 {
+      self.id_to_holder_count.remove(&id.store())?;
+    } else {
+      self.id_to_holder_count.insert(&id.store(), count)?;
+    }
+
+    Ok(())
   }
 }
@@ -0,0 +1,170 @@
+use {super::*, crate::subcommand::index::verify::Problem};
+
+impl Index {
+  /// Cross-check the index tables against each other, reading them from a
+  /// single read transaction.
+  pub(crate) fn verify(&self) -> Result<(Option<u32>, Vec<Problem>)> {
+    let rtx = rtx::Rtx(self.database.begin_read()?);
+
+    let height = rtx.block_height()?.map(|height| height.n());
+
+    let mut problems = Vec::new();
+
+    Self::verify_inscriptions(&rtx.0, &mut problems)?;
+
+    if self.index_runes {
+      Self::verify_rune_supply(&rtx.0, &mut problems)?;
+    }
+
+    // spent outputs keep their sat ranges with `--index-spent-sats`, so sat
+    // ranges only have to be disjoint without it
+    if self.index_sats && !self.index_spent_sats {
+      Self::verify_sat_ranges(&rtx.0, &mut problems)?;
+    }
+
+    Ok((height, problems))
+  }
+
+  fn verify_inscriptions(rtx: &ReadTransaction, problems: &mut Vec<Problem>) -> Result {
+    let inscription_id_to_sequence_number = rtx.open_table(INSCRIPTION_ID_TO_SEQUENCE_NUMBER)?;
+    let inscription_number_to_sequence_number =
+      rtx.open_table(INSCRIPTION_NUMBER_TO_SEQUENCE_NUMBER)?;
+    let sequence_number_to_inscription_entry =
+      rtx.open_table(SEQUENCE_NUMBER_TO_INSCRIPTION_ENTRY)?;
+    let sequence_number_to_satpoint = rtx.open_table(SEQUENCE_NUMBER_TO_SATPOINT)?;
+
+    for result in sequence_number_to_inscription_entry.iter()? {
+      let (sequence_number, entry) = result?;
+      let sequence_number = sequence_number.value();
+      let entry = InscriptionEntry::load(entry.value());
+
+      if sequence_number_to_satpoint.get(sequence_number)?.is_none() {
+        problems.push(Problem::MissingSatpoint { sequence_number });
+      }
+
+      if inscription_id_to_sequence_number
+        .get(&entry.id.store())?
+        .map(|guard| guard.value())
+        != Some(sequence_number)
+      {
+        problems.push(Problem::InscriptionIdMismatch {
+          inscription_id: entry.id,
+          sequence_number,
+        });
+      }
+
+      if inscription_number_to_sequence_number
+        .get(entry.inscription_number)?
+        .map(|guard| guard.value())
+        != Some(sequence_number)
+      {
+        problems.push(Problem::InscriptionNumberMismatch {
+          inscription_number: entry.inscription_number,
+          sequence_number,
+        });
+      }
+    }
+
+    // entries were checked against the id and number tables above, so only
+    // id and number table rows without a matching entry remain to be found
+
+    for result in inscription_id_to_sequence_number.iter()? {
+      let (inscription_id, sequence_number) = result?;
+      let inscription_id = InscriptionId::load(inscription_id.value());
+      let sequence_number = sequence_number.value();
+
+      if sequence_number_to_inscription_entry
+        .get(sequence_number)?
+        .map(|entry| InscriptionEntry::load(entry.value()).id)
+        != Some(inscription_id)
+      {
+        problems.push(Problem::InscriptionIdMismatch {
+          inscription_id,
+          sequence_number,
+        });
+      }
+    }
+
+    for result in inscription_number_to_sequence_number.iter()? {
+      let (inscription_number, sequence_number) = result?;
+      let inscription_number = inscription_number.value();
+      let sequence_number = sequence_number.value();
+
+      if sequence_number_to_inscription_entry
+        .get(sequence_number)?
+        .map(|entry| InscriptionEntry::load(entry.value()).inscription_number)
+        != Some(inscription_number)
+      {
+        problems.push(Problem::InscriptionNumberMismatch {
+          inscription_number,
+          sequence_number,
+        });
+      }
+    }
+
+    Ok(())
+  }
+
+  fn verify_rune_supply(rtx: &ReadTransaction, problems: &mut Vec<Problem>) -> Result {
+    let mut balances = HashMap::<RuneId, u128>::new();
+
+    for result in rtx.open_table(OUTPOINT_TO_RUNE_BALANCES)?.iter()? {
+      let (_outpoint, buffer) = result?;
+      let buffer = buffer.value();
+
+      let mut i = 0;
+      while i < buffer.len() {
+        let ((id, balance), len) = Self::decode_rune_balance(&buffer[i..])?;
+        i += len;
+
+        let total = balances.entry(id).or_default();
+        *total = total.saturating_add(balance);
+      }
+    }
+
+    for result in rtx.open_table(RUNE_ID_TO_RUNE_ENTRY)?.iter()? {
+      let (id, entry) = result?;
+      let id = RuneId::load(id.value());
+      let entry = RuneEntry::load(entry.value());
+
+      let balances = balances.get(&id).copied().unwrap_or_default();
+
+      if balances.checked_add(entry.burned) != Some(entry.supply()) {
+        problems.push(Problem::RuneSupplyMismatch {
+          rune: entry.spaced_rune,
+          supply: entry.supply(),
+          balances,
+          burned: entry.burned,
+        });
+      }
+    }
+
+    Ok(())
+  }
+
+  fn verify_sat_ranges(rtx: &ReadTransaction, problems: &mut Vec<Problem>) -> Result {
+    let mut ranges = Vec::new();
+
+    for result in rtx.open_table(OUTPOINT_TO_SAT_RANGES)?.iter()? {
+      let (outpoint, sat_ranges) = result?;
+      let outpoint = OutPoint::load(*outpoint.value());
+
+      for chunk in sat_ranges.value().chunks_exact(11) {
+        let (start, end) = SatRange::load(chunk.try_into().unwrap());
+        ranges.push((outpoint, start, end));
+      }
+    }
+
+    ranges.sort_unstable_by_key(|(_outpoint, start, end)| (*start, *end));
+
+    for window in ranges.windows(2) {
+      let (first, second) = (window[0], window[1]);
+
+      if second.1 < first.2 {
+        problems.push(Problem::OverlappingSatRanges { first, second });
+      }
+    }
+
+    Ok(())
+  }
+}
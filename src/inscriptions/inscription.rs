@@ -222,6 +222,12 @@ impl Inscription {
     self.body
   }
 
+  /// Hash of the body as inscribed, before any content encoding is removed, so
+  /// the same content compressed differently hashes differently.
+  pub(crate) fn body_hash(&self) -> Option<sha256::Hash> {
+    Some(sha256::Hash::hash(self.body()?))
+  }
+
   pub(crate) fn content_length(&self) -> Option<usize> {
     Some(self.body()?.len())
   }
@@ -242,6 +248,27 @@ impl Inscription {
     ciborium::from_reader(Cursor::new(self.metadata.as_ref()?)).ok()
   }
 
+  pub(crate) fn metadata_fields(&self) -> Vec<(String, String)> {
+    let Some(Value::Map(map)) = self.metadata() else {
+      return Vec::new();
+    };
+
+    map
+      .iter()
+      .filter_map(|(key, value)| Some((Self::metadata_scalar(key)?, Self::metadata_scalar(value)?)))
+      .collect()
+  }
+
+  fn metadata_scalar(value: &Value) -> Option<String> {
+    match value {
+      Value::Bool(bool) => Some(bool.to_string()),
+      Value::Float(float) => Some(float.to_string()),
+      Value::Integer(integer) => Some(i128::from(*integer).to_string()),
+      Value::Text(text) => Some(text.clone()),
+      _ => None,
+    }
+  }
+
   pub(crate) fn metaprotocol(&self) -> Option<&str> {
     str::from_utf8(self.metaprotocol.as_ref()?).ok()
   }
@@ -682,6 +709,68 @@ mod tests {
     );
   }
 
+  #[test]
+  fn metadata_fields_returns_top_level_scalars() {
+    let mut metadata = Vec::new();
+    ciborium::into_writer(
+      &Value::Map(vec![
+        (Value::Text("name".into()), Value::Text("foo".into())),
+        (Value::Text("edition".into()), Value::Integer((-7).into())),
+        (Value::Text("rare".into()), Value::Bool(true)),
+        (Value::Text("weight".into()), Value::Float(1.5)),
+        (Value::Integer(1.into()), Value::Text("one".into())),
+        (Value::Text("bytes".into()), Value::Bytes(vec![0])),
+        (
+          Value::Text("nested".into()),
+          Value::Map(vec![(Value::Text("a".into()), Value::Text("b".into()))]),
+        ),
+        (Value::Text("null".into()), Value::Null),
+      ]),
+      &mut metadata,
+    )
+    .unwrap();
+
+    assert_eq!(
+      Inscription {
+        metadata: Some(metadata),
+        ..default()
+      }
+      .metadata_fields(),
+      [
+        ("name".into(), "foo".into()),
+        ("edition".into(), "-7".into()),
+        ("rare".into(), "true".into()),
+        ("weight".into(), "1.5".into()),
+        ("1".into(), "one".into()),
+      ],
+    );
+  }
+
+  #[test]
+  fn metadata_fields_ignores_non_map_metadata() {
+    assert_eq!(
+      Inscription {
+        metadata: Some(vec![0x44, 0, 1, 2, 3]),
+        ..default()
+      }
+      .metadata_fields(),
+      Vec::new(),
+    );
+  }
+
+  #[test]
+  fn body_hash() {
+    assert_eq!(
+      inscription("text/plain", "foo")
+        .body_hash()
+        .unwrap()
+        .to_string(),
+      "2c26b46b68ffc68ff99b453c1d30413413422d706483bfa0f98a5e886266e7ae",
+    );
+
+    assert_eq!(Inscription::default().body_hash(), None);
+  }
+
   #[test]
   fn pointer_decode() {
     assert_eq!(
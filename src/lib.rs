@@ -39,7 +39,7 @@ use {
     },
     consensus::{self, Decodable, Encodable},
     hash_types::{BlockHash, TxMerkleNode},
-    hashes::Hash,
+    hashes::{sha256, Hash},
     script, Amount, Block, Network, OutPoint, Script, ScriptBuf, Sequence, Transaction, TxIn,
     TxOut, Txid, Witness,
   },
@@ -51,8 +51,8 @@ use {
   http::HeaderMap,
   lazy_static::lazy_static,
   ordinals::{
-    varint, Artifact, Charm, Edict, Epoch, Etching, Height, Pile, Rarity, Rune, RuneId, Runestone,
-    Sat, SatPoint, SpacedRune, Terms,
+    varint, Artifact, Charm, Classifier, Edict, Epoch, Etching, Height, Pile, Rarity, Rune, RuneId,
+    Runestone, Sat, SatPoint, Satribute, SpacedRune, Terms,
   },
   regex::Regex,
   reqwest::Url,
@@ -128,6 +128,8 @@ const TARGET_POSTAGE: Amount = Amount::from_sat(10_000);
 static SHUTTING_DOWN: AtomicBool = AtomicBool::new(false);
 static LISTENERS: Mutex<Vec<axum_server::Handle>> = Mutex::new(Vec::new());
 static INDEXER: Mutex<Option<thread::JoinHandle<()>>> = Mutex::new(None);
+static MEMPOOL: Mutex<Option<thread::JoinHandle<()>>> = Mutex::new(None);
+static WEBHOOKS: Mutex<Vec<thread::JoinHandle<()>>> = Mutex::new(Vec::new());
 
 #[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
 fn fund_raw_transaction(
@@ -229,6 +231,22 @@ fn gracefully_shutdown_indexer() {
       log::warn!("Index thread panicked; join failed");
     }
   }
+
+  if let Some(mempool) = MEMPOOL.lock().unwrap().take() {
+    SHUTTING_DOWN.store(true, atomic::Ordering::Relaxed);
+    log::info!("Waiting for mempool thread to finish...");
+    if mempool.join().is_err() {
+      log::warn!("Mempool thread panicked; join failed");
+    }
+  }
+
+  for webhook in WEBHOOKS.lock().unwrap().drain(..) {
+    SHUTTING_DOWN.store(true, atomic::Ordering::Relaxed);
+    log::info!("Waiting for webhook thread to finish...");
+    if webhook.join().is_err() {
+      log::warn!("Webhook thread panicked; join failed");
+    }
+  }
 }
 
 pub fn main() {
@@ -47,11 +47,17 @@ pub struct Options {
   pub(crate) height_limit: Option<u32>,
   #[arg(long, help = "Use index at <INDEX>.")]
   pub(crate) index: Option<PathBuf>,
+  #[arg(long, help = "Track unspent outputs by address.")]
+  pub(crate) index_addresses: bool,
   #[arg(
     long,
     help = "Set index cache size to <INDEX_CACHE_SIZE> bytes. [default: 1/4 available RAM]"
   )]
   pub(crate) index_cache_size: Option<usize>,
+  #[arg(long, help = "Store events in index.")]
+  pub(crate) index_events: bool,
+  #[arg(long, help = "Index top-level inscription metadata keys and values.")]
+  pub(crate) index_metadata: bool,
   #[arg(
     long,
     help = "Track location of runes. RUNES ARE IN AN UNFINISHED PRE-ALPHA STATE AND SUBJECT TO CHANGE AT ANY TIME."
@@ -63,8 +69,15 @@ pub struct Options {
   pub(crate) index_spent_sats: bool,
   #[arg(long, help = "Store transactions in index.")]
   pub(crate) index_transactions: bool,
+  #[arg(long, help = "Store inscription transfer history in index.")]
+  pub(crate) index_transfers: bool,
   #[arg(long, help = "Run in integration test mode.")]
   pub(crate) integration_test: bool,
+  #[arg(
+    long,
+    help = "Keep undo records for the last <MAX_REORG_DEPTH> blocks so that reorgs up to that depth can be rolled back. [default: 100]"
+  )]
+  pub(crate) max_reorg_depth: Option<u32>,
   #[clap(long, short, long, help = "Specify output format. [default: json]")]
   pub(crate) format: Option<OutputFormat>,
   #[arg(
@@ -18,16 +18,23 @@ pub struct Settings {
   height_limit: Option<u32>,
   hidden: Option<HashSet<InscriptionId>>,
   index: Option<PathBuf>,
+  index_addresses: bool,
   index_cache_size: Option<usize>,
+  index_events: bool,
+  index_metadata: bool,
   index_runes: bool,
   index_sats: bool,
   index_spent_sats: bool,
   index_transactions: bool,
+  index_transfers: bool,
   integration_test: bool,
+  max_reorg_depth: Option<u32>,
   no_index_inscriptions: bool,
   server_password: Option<String>,
   server_url: Option<String>,
   server_username: Option<String>,
+  webhook_secret: Option<String>,
+  webhooks: Option<Vec<String>>,
 }
 
 impl Settings {
@@ -133,16 +140,23 @@ impl Settings {
           .collect(),
       ),
       index: self.index.or(source.index),
+      index_addresses: self.index_addresses || source.index_addresses,
       index_cache_size: self.index_cache_size.or(source.index_cache_size),
+      index_events: self.index_events || source.index_events,
+      index_metadata: self.index_metadata || source.index_metadata,
       index_runes: self.index_runes || source.index_runes,
       index_sats: self.index_sats || source.index_sats,
       index_spent_sats: self.index_spent_sats || source.index_spent_sats,
       index_transactions: self.index_transactions || source.index_transactions,
+      index_transfers: self.index_transfers || source.index_transfers,
       integration_test: self.integration_test || source.integration_test,
+      max_reorg_depth: self.max_reorg_depth.or(source.max_reorg_depth),
       no_index_inscriptions: self.no_index_inscriptions || source.no_index_inscriptions,
       server_password: self.server_password.or(source.server_password),
       server_url: self.server_url.or(source.server_url),
       server_username: self.server_username.or(source.server_username),
+      webhook_secret: self.webhook_secret.or(source.webhook_secret),
+      webhooks: self.webhooks.or(source.webhooks),
     }
   }
 
@@ -168,16 +182,23 @@ impl Settings {
       height_limit: options.height_limit,
       hidden: None,
       index: options.index,
+      index_addresses: options.index_addresses,
       index_cache_size: options.index_cache_size,
+      index_events: options.index_events,
+      index_metadata: options.index_metadata,
       index_runes: options.index_runes,
       index_sats: options.index_sats,
       index_spent_sats: options.index_spent_sats,
       index_transactions: options.index_transactions,
+      index_transfers: options.index_transfers,
       integration_test: options.integration_test,
+      max_reorg_depth: options.max_reorg_depth,
       no_index_inscriptions: options.no_index_inscriptions,
       server_password: options.server_password,
       server_url: None,
       server_username: options.server_username,
+      webhook_secret: None,
+      webhooks: None,
     }
   }
 
@@ -247,16 +268,24 @@ impl Settings {
       height_limit: get_u32("HEIGHT_LIMIT")?,
       hidden: inscriptions("HIDDEN")?,
       index: get_path("INDEX"),
+      index_addresses: get_bool("INDEX_ADDRESSES"),
       index_cache_size: get_usize("INDEX_CACHE_SIZE")?,
+      index_events: get_bool("INDEX_EVENTS"),
+      index_metadata: get_bool("INDEX_METADATA"),
       index_runes: get_bool("INDEX_RUNES"),
       index_sats: get_bool("INDEX_SATS"),
       index_spent_sats: get_bool("INDEX_SPENT_SATS"),
       index_transactions: get_bool("INDEX_TRANSACTIONS"),
+      index_transfers: get_bool("INDEX_TRANSFERS"),
       integration_test: get_bool("INTEGRATION_TEST"),
+      max_reorg_depth: get_u32("MAX_REORG_DEPTH")?,
       no_index_inscriptions: get_bool("NO_INDEX_INSCRIPTIONS"),
       server_password: get_string("SERVER_PASSWORD"),
       server_url: get_string("SERVER_URL"),
       server_username: get_string("SERVER_USERNAME"),
+      webhook_secret: get_string("WEBHOOK_SECRET"),
+      webhooks: get_string("WEBHOOKS")
+        .map(|webhooks| webhooks.split_whitespace().map(Into::into).collect()),
     })
   }
 
@@ -277,16 +306,23 @@ impl Settings {
       height_limit: None,
       hidden: None,
       index: None,
+      index_addresses: false,
       index_cache_size: None,
+      index_events: false,
+      index_metadata: false,
       index_runes: true,
       index_sats: true,
       index_spent_sats: false,
       index_transactions: false,
+      index_transfers: false,
       integration_test: false,
+      max_reorg_depth: None,
       no_index_inscriptions: false,
       server_password: None,
       server_url: Some(server_url.into()),
       server_username: None,
+      webhook_secret: None,
+      webhooks: None,
     }
   }
 
@@ -350,6 +386,7 @@ impl Settings {
       height_limit: self.height_limit,
       hidden: self.hidden,
       index: Some(index),
+      index_addresses: self.index_addresses,
       index_cache_size: Some(match self.index_cache_size {
         Some(index_cache_size) => index_cache_size,
         None => {
@@ -358,15 +395,21 @@ impl Settings {
           usize::try_from(sys.total_memory() / 4)?
         }
       }),
+      index_events: self.index_events,
+      index_metadata: self.index_metadata,
       index_runes: self.index_runes,
       index_sats: self.index_sats,
       index_spent_sats: self.index_spent_sats,
       index_transactions: self.index_transactions,
+      index_transfers: self.index_transfers,
       integration_test: self.integration_test,
+      max_reorg_depth: Some(self.max_reorg_depth.unwrap_or(100)),
       no_index_inscriptions: self.no_index_inscriptions,
       server_password: self.server_password,
       server_url: self.server_url,
       server_username: self.server_username,
+      webhook_secret: self.webhook_secret,
+      webhooks: self.webhooks,
     })
   }
 
@@ -512,10 +555,22 @@ impl Settings {
     self.index.as_ref().unwrap()
   }
 
+  pub(crate) fn index_addresses(&self) -> bool {
+    self.index_addresses
+  }
+
   pub(crate) fn index_inscriptions(&self) -> bool {
     !self.no_index_inscriptions
   }
 
+  pub(crate) fn index_events(&self) -> bool {
+    self.index_events
+  }
+
+  pub(crate) fn index_metadata(&self) -> bool {
+    self.index_metadata
+  }
+
   pub(crate) fn index_runes(&self) -> bool {
     self.index_runes
   }
@@ -536,10 +591,18 @@ impl Settings {
     self.index_transactions
   }
 
+  pub(crate) fn index_transfers(&self) -> bool {
+    self.index_transfers
+  }
+
   pub(crate) fn integration_test(&self) -> bool {
     self.integration_test
   }
 
+  pub(crate) fn max_reorg_depth(&self) -> u32 {
+    self.max_reorg_depth.unwrap()
+  }
+
   pub(crate) fn is_hidden(&self, inscription_id: InscriptionId) -> bool {
     self
       .hidden
@@ -563,6 +626,14 @@ impl Settings {
   pub(crate) fn server_url(&self) -> Option<&str> {
     self.server_url.as_deref()
   }
+
+  pub(crate) fn webhook_secret(&self) -> Option<&str> {
+    self.webhook_secret.as_deref()
+  }
+
+  pub(crate) fn webhooks(&self) -> &[String] {
+    self.webhooks.as_deref().unwrap_or_default()
+  }
 }
 
 #[cfg(test)]
@@ -895,6 +966,12 @@ mod tests {
     assert_eq!(arguments.options.commit_interval, Some(500));
   }
 
+  #[test]
+  fn setting_max_reorg_depth() {
+    assert_eq!(parse(&[]).max_reorg_depth(), 100);
+    assert_eq!(parse(&["--max-reorg-depth=1000"]).max_reorg_depth(), 1000);
+  }
+
   #[test]
   fn index_runes() {
     assert!(parse(&["--chain=signet", "--index-runes"]).index_runes());
@@ -1002,16 +1079,23 @@ mod tests {
       ("HEIGHT_LIMIT", "3"),
       ("HIDDEN", "6fb976ab49dcec017f1e201e84395983204ae1a7c2abf7ced0a85d692e442799i0 703e5f7c49d82aab99e605af306b9a30e991e57d42f982908a962a81ac439832i0"),
       ("INDEX", "index"),
+      ("INDEX_ADDRESSES", "1"),
       ("INDEX_CACHE_SIZE", "4"),
+      ("INDEX_EVENTS", "1"),
+      ("INDEX_METADATA", "1"),
       ("INDEX_RUNES", "1"),
       ("INDEX_SATS", "1"),
       ("INDEX_SPENT_SATS", "1"),
       ("INDEX_TRANSACTIONS", "1"),
+      ("INDEX_TRANSFERS", "1"),
       ("INTEGRATION_TEST", "1"),
+      ("MAX_REORG_DEPTH", "5"),
       ("NO_INDEX_INSCRIPTIONS", "1"),
       ("SERVER_PASSWORD", "server password"),
       ("SERVER_URL", "server url"),
       ("SERVER_USERNAME", "server username"),
+      ("WEBHOOK_SECRET", "webhook secret"),
+      ("WEBHOOKS", "http://foo.com/events http://bar.com/events"),
     ]
     .into_iter()
     .map(|(key, value)| (key.into(), value.into()))
@@ -1046,16 +1130,26 @@ mod tests {
           .collect()
         ),
         index: Some("index".into()),
+        index_addresses: true,
         index_cache_size: Some(4),
+        index_events: true,
+        index_metadata: true,
         index_runes: true,
         index_sats: true,
         index_spent_sats: true,
         index_transactions: true,
+        index_transfers: true,
         integration_test: true,
+        max_reorg_depth: Some(5),
         no_index_inscriptions: true,
         server_password: Some("server password".into()),
         server_url: Some("server url".into()),
         server_username: Some("server username".into()),
+        webhook_secret: Some("webhook secret".into()),
+        webhooks: Some(vec![
+          "http://foo.com/events".into(),
+          "http://bar.com/events".into()
+        ]),
       }
     );
   }
@@ -1079,13 +1173,18 @@ mod tests {
           "--datadir=/data/dir",
           "--first-inscription-height=2",
           "--height-limit=3",
+          "--index-addresses",
           "--index-cache-size=4",
+          "--index-events",
+          "--index-metadata",
           "--index-runes",
           "--index-sats",
           "--index-spent-sats",
           "--index-transactions",
+          "--index-transfers",
           "--index=index",
           "--integration-test",
+          "--max-reorg-depth=5",
           "--no-index-inscriptions",
           "--server-password=server password",
           "--server-username=server username",
@@ -1108,16 +1207,23 @@ mod tests {
         height_limit: Some(3),
         hidden: None,
         index: Some("index".into()),
+        index_addresses: true,
         index_cache_size: Some(4),
+        index_events: true,
+        index_metadata: true,
         index_runes: true,
         index_sats: true,
         index_spent_sats: true,
         index_transactions: true,
+        index_transfers: true,
         integration_test: true,
+        max_reorg_depth: Some(5),
         no_index_inscriptions: true,
         server_password: Some("server password".into()),
         server_url: None,
         server_username: Some("server username".into()),
+        webhook_secret: None,
+        webhooks: None,
       }
     );
   }
@@ -1,25 +1,41 @@
 use super::*;
 
-mod export;
+mod events;
+pub mod export;
 pub mod info;
+pub mod restore;
+pub mod snapshot;
 mod update;
+pub mod verify;
 
 #[derive(Debug, Parser)]
 pub(crate) enum IndexSubcommand {
+  #[command(about = "Update the index and write events as JSON lines")]
+  Events(events::Events),
   #[command(about = "Write inscription numbers and ids to a tab-separated file")]
   Export(export::Export),
   #[command(about = "Print index statistics")]
   Info(info::Info),
+  #[command(about = "Install an index snapshot taken with `ord index snapshot`")]
+  Restore(restore::Restore),
+  #[command(about = "Write a consistent copy of the index")]
+  Snapshot(snapshot::Snapshot),
   #[command(about = "Update the index", alias = "run")]
   Update,
+  #[command(about = "Check the index for internal consistency")]
+  Verify(verify::Verify),
 }
 
 impl IndexSubcommand {
   pub(crate) fn run(self, settings: Settings) -> SubcommandResult {
     match self {
+      Self::Events(events) => events.run(settings),
       Self::Export(export) => export.run(settings),
       Self::Info(info) => info.run(settings),
+      Self::Restore(restore) => restore.run(settings),
+      Self::Snapshot(snapshot) => snapshot.run(settings),
       Self::Update => update::run(settings),
+      Self::Verify(verify) => verify.run(settings),
     }
   }
 }
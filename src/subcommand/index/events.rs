@@ -0,0 +1,123 @@
+use {super::*, crate::index::event::Event, std::io::Write};
+
+#[cfg(unix)]
+use std::os::unix::net::UnixStream;
+
+#[derive(Debug, Parser)]
+pub(crate) struct Events {
+  #[arg(
+    long,
+    help = "Write events for blocks at or above <FROM_HEIGHT>. Replaying already indexed blocks requires index created with `--index-events` flag."
+  )]
+  from_height: Option<u32>,
+  #[arg(long, help = "Write events to <OUTPUT> instead of stdout.")]
+  output: Option<PathBuf>,
+  #[cfg(unix)]
+  #[arg(
+    long,
+    conflicts_with = "output",
+    help = "Write events to Unix domain socket at <SOCKET> instead of stdout."
+  )]
+  socket: Option<PathBuf>,
+}
+
+impl Events {
+  pub(crate) fn run(self, settings: Settings) -> SubcommandResult {
+    let (event_sender, mut event_receiver) = tokio::sync::mpsc::channel(1024);
+
+    let index = Index::open_with_event_sender(&settings, Some(event_sender))?;
+
+    let mut writer = self.writer()?;
+
+    if let Some(from_height) = self.from_height {
+      if from_height < index.block_count()? {
+        if !index.has_event_index() {
+          bail!("replaying events requires index created with `--index-events` flag");
+        }
+
+        if let Some(mut next) = index.first_event_at_or_above_height(from_height)? {
+          loop {
+            let events = index.events(next, 1000)?;
+
+            let Some((last, _)) = events.last() else {
+              break;
+            };
+
+            next = last + 1;
+
+            for (_, event) in events {
+              if Self::include(&event, from_height) {
+                Self::write(&mut writer, &event)?;
+              }
+            }
+          }
+        }
+      }
+    }
+
+    let from_height = self.from_height.unwrap_or_default();
+
+    let writer = thread::spawn(move || -> Result {
+      while let Some(event) = event_receiver.blocking_recv() {
+        if Self::include(&event, from_height) {
+          Self::write(&mut writer, &event)?;
+        }
+      }
+
+      Ok(())
+    });
+
+    let result = index.update();
+
+    drop(index);
+
+    writer.join().unwrap()?;
+
+    result?;
+
+    Ok(None)
+  }
+
+  /// Reorg events are always written, since a reorg detected after replay
+  /// starts may roll back blocks at or above `from_height` that have already
+  /// been written, and `RolledBack` only records the height rolled back to.
+  fn include(event: &Event, from_height: u32) -> bool {
+    match event {
+      Event::BlockCommitted { block_height, .. }
+      | Event::BlockStarted { block_height, .. }
+      | Event::InscriptionCreated { block_height, .. }
+      | Event::InscriptionSold { block_height, .. }
+      | Event::InscriptionTransferred { block_height, .. }
+      | Event::RuneBurned { block_height, .. }
+      | Event::RuneCenotaph { block_height, .. }
+      | Event::RuneEtched { block_height, .. }
+      | Event::RuneMinted { block_height, .. }
+      | Event::RuneTransferred { block_height, .. } => *block_height >= from_height,
+      Event::ReorgDetected { .. } | Event::RolledBack { .. } => true,
+    }
+  }
+
+  fn write(writer: &mut impl Write, event: &Event) -> Result {
+    serde_json::to_writer(&mut *writer, event)?;
+    writeln!(writer)?;
+    writer.flush()?;
+    Ok(())
+  }
+
+  fn writer(&self) -> Result<Box<dyn Write + Send>> {
+    #[cfg(unix)]
+    if let Some(socket) = &self.socket {
+      return Ok(Box::new(UnixStream::connect(socket).with_context(
+        || format!("failed to connect to socket `{}`", socket.display()),
+      )?));
+    }
+
+    if let Some(output) = &self.output {
+      return Ok(Box::new(fs::File::create(output).with_context(|| {
+        format!("failed to create `{}`", output.display())
+      })?));
+    }
+
+    Ok(Box::new(io::stdout()))
+  }
+}
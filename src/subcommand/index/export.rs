@@ -2,10 +2,84 @@ use super::*;
 
 #[derive(Debug, Parser)]
 pub(crate) struct Export {
-  #[arg(long, help = "Include addresses in export")]
+  #[arg(long, requires = "tsv", help = "Include addresses in export")]
   include_addresses: bool,
-  #[arg(long, help = "Write export to <TSV>")]
-  tsv: String,
+  #[arg(
+    long,
+    required_unless_present = "output_dir",
+    conflicts_with = "output_dir",
+    help = "Write export to <TSV>"
+  )]
+  tsv: Option<String>,
+  #[arg(
+    long,
+    help = "Write one file per table to <OUTPUT_DIR>, stamped with the height and hash of the last indexed block."
+  )]
+  output_dir: Option<PathBuf>,
+  #[clap(
+    value_enum,
+    long,
+    default_value = "jsonl",
+    requires = "output_dir",
+    help = "Write tables as <FORMAT>."
+  )]
+  format: Format,
+  #[clap(
+    value_enum,
+    long = "table",
+    requires = "output_dir",
+    help = "Export <TABLE>. May be given more than once. [default: all indexed tables]"
+  )]
+  tables: Vec<Table>,
+  #[arg(
+    long,
+    requires = "output_dir",
+    help = "Continue interrupted exports of the same block instead of starting over."
+  )]
+  resume: bool,
+}
+
+#[derive(clap::ValueEnum, Debug, Clone, Copy, PartialEq)]
+pub(crate) enum Format {
+  Csv,
+  Jsonl,
+}
+
+impl Format {
+  pub(crate) fn extension(self) -> &'static str {
+    match self {
+      Self::Csv => "csv",
+      Self::Jsonl => "jsonl",
+    }
+  }
+}
+
+#[derive(clap::ValueEnum, Debug, Clone, Copy, PartialEq)]
+pub(crate) enum Table {
+  Inscriptions,
+  Runes,
+  RuneBalances,
+  SatRanges,
+  Children,
+}
+
+impl Table {
+  pub(crate) fn name(self) -> &'static str {
+    match self {
+      Self::Inscriptions => "inscriptions",
+      Self::Runes => "runes",
+      Self::RuneBalances => "rune-balances",
+      Self::SatRanges => "sat-ranges",
+      Self::Children => "children",
+    }
+  }
+}
+
+#[derive(Debug, PartialEq, Serialize, Deserialize)]
+pub struct Output {
+  pub hash: BlockHash,
+  pub height: u32,
+  pub files: Vec<PathBuf>,
 }
 
 impl Export {
@@ -13,8 +87,41 @@ impl Export {
     let index = Index::open(&settings)?;
 
     index.update()?;
-    index.export(&self.tsv, self.include_addresses)?;
 
-    Ok(None)
+    let Some(output_dir) = self.output_dir else {
+      index.export(&self.tsv.unwrap(), self.include_addresses)?;
+      return Ok(None);
+    };
+
+    let tables = if self.tables.is_empty() {
+      [
+        Table::Inscriptions,
+        Table::Runes,
+        Table::RuneBalances,
+        Table::SatRanges,
+        Table::Children,
+      ]
+      .into_iter()
+      .filter(|table| match table {
+        Table::Runes | Table::RuneBalances => index.has_rune_index(),
+        Table::SatRanges => index.has_sat_index(),
+        Table::Inscriptions | Table::Children => true,
+      })
+      .collect()
+    } else {
+      self.tables
+    };
+
+    fs::create_dir_all(&output_dir)
+      .with_context(|| format!("failed to create `{}`", output_dir.display()))?;
+
+    let (height, hash, files) =
+      index.export_tables(&tables, self.format, &output_dir, self.resume)?;
+
+    Ok(Some(Box::new(Output {
+      hash,
+      height,
+      files,
+    })))
   }
 }
@@ -0,0 +1,26 @@
+use super::*;
+
+#[derive(Debug, Parser)]
+pub(crate) struct Restore {
+  #[arg(help = "Restore index from snapshot at <PATH>.")]
+  path: PathBuf,
+}
+
+#[derive(Debug, PartialEq, Serialize, Deserialize)]
+pub struct Output {
+  pub hash: BlockHash,
+  pub height: u32,
+  pub index: PathBuf,
+}
+
+impl Restore {
+  pub(crate) fn run(self, settings: Settings) -> SubcommandResult {
+    let (height, hash) = Index::restore(&settings, &self.path)?;
+
+    Ok(Some(Box::new(Output {
+      hash,
+      height,
+      index: settings.index().into(),
+    })))
+  }
+}
@@ -0,0 +1,71 @@
+use super::*;
+
+#[derive(Debug, Parser)]
+pub(crate) struct Snapshot {
+  #[arg(
+    long,
+    help = "Download the snapshot from the ord server at <SERVER_URL>, which must be run with `--enable-snapshots`, instead of reading the index directly."
+  )]
+  server_url: Option<Url>,
+  #[arg(help = "Write snapshot to <PATH>.")]
+  path: PathBuf,
+}
+
+#[derive(Debug, PartialEq, Serialize, Deserialize)]
+pub struct Output {
+  pub hash: BlockHash,
+  pub height: u32,
+  pub path: PathBuf,
+}
+
+impl Snapshot {
+  pub(crate) fn run(self, settings: Settings) -> SubcommandResult {
+    let (height, hash) = match &self.server_url {
+      Some(server_url) => {
+        let result = Self::download(&settings, server_url, &self.path);
+
+        if result.is_err() {
+          fs::remove_file(&self.path).ok();
+        }
+
+        result?
+      }
+      None => Index::open(&settings)?.snapshot(&self.path)?,
+    };
+
+    Ok(Some(Box::new(Output {
+      hash,
+      height,
+      path: self.path,
+    })))
+  }
+
+  fn download(settings: &Settings, server_url: &Url, path: &Path) -> Result<(u32, BlockHash)> {
+    let mut file = fs::OpenOptions::new()
+      .write(true)
+      .create_new(true)
+      .open(path)
+      .with_context(|| format!("failed to create snapshot `{}`", path.display()))?;
+
+    let mut request = reqwest::blocking::Client::builder()
+      .timeout(None)
+      .build()?
+      .get(server_url.join("/snapshot")?);
+
+    if let Some((username, password)) = settings.credentials() {
+      request = request.basic_auth(username, Some(password));
+    }
+
+    let mut response = request.send()?;
+
+    if !response.status().is_success() {
+      bail!("failed to download snapshot: {}", response.text()?);
+    }
+
+    response.copy_to(&mut file)?;
+
+    file.sync_all()?;
+
+    Index::read_snapshot(path)
+  }
+}
@@ -0,0 +1,46 @@
+use super::*;
+
+#[derive(Debug, Parser)]
+pub(crate) struct Verify {}
+
+#[derive(Debug, PartialEq, Serialize, Deserialize)]
+pub struct Output {
+  pub height: Option<u32>,
+  pub problems: Vec<Problem>,
+}
+
+#[derive(Debug, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case", tag = "problem")]
+pub enum Problem {
+  InscriptionIdMismatch {
+    inscription_id: InscriptionId,
+    sequence_number: u32,
+  },
+  InscriptionNumberMismatch {
+    inscription_number: i32,
+    sequence_number: u32,
+  },
+  MissingSatpoint {
+    sequence_number: u32,
+  },
+  OverlappingSatRanges {
+    first: (OutPoint, u64, u64),
+    second: (OutPoint, u64, u64),
+  },
+  RuneSupplyMismatch {
+    rune: SpacedRune,
+    supply: u128,
+    balances: u128,
+    burned: u128,
+  },
+}
+
+impl Verify {
+  pub(crate) fn run(self, settings: Settings) -> SubcommandResult {
+    let index = Index::open(&settings)?;
+
+    let (height, problems) = index.verify()?;
+
+    Ok(Some(Box::new(Output { height, problems })))
+  }
+}
@@ -3,20 +3,28 @@ use {
     accept_encoding::AcceptEncoding,
     accept_json::AcceptJson,
     error::{OptionExt, ServerError, ServerResult},
+    event_filter::EventFilter,
+    webhook::Webhook,
   },
   super::*,
+  crate::index::event::Event,
   crate::templates::{
-    BlockHtml, BlocksHtml, ChildrenHtml, ClockSvg, CollectionsHtml, HomeHtml, InputHtml,
-    InscriptionHtml, InscriptionsBlockHtml, InscriptionsHtml, OutputHtml, PageContent, PageHtml,
-    ParentsHtml, PreviewAudioHtml, PreviewCodeHtml, PreviewFontHtml, PreviewImageHtml,
-    PreviewMarkdownHtml, PreviewModelHtml, PreviewPdfHtml, PreviewTextHtml, PreviewUnknownHtml,
-    PreviewVideoHtml, RangeHtml, RareTxt, RuneHtml, RunesHtml, SatHtml, TransactionHtml,
+    AddressHtml, BlockHtml, BlocksHtml, ChildrenHtml, ClockSvg, CollectionHtml, CollectionsHtml,
+    HomeHtml, InputHtml, InscriptionFilter, InscriptionHistoryHtml, InscriptionHtml,
+    InscriptionSalesHtml, InscriptionsBlockHtml, InscriptionsHtml, MetaprotocolsHtml, OutputHtml,
+    PageContent, PageHtml, PaginatedInscriptionsHtml, ParentsHtml, PreviewAudioHtml,
+    PreviewCodeHtml, PreviewFontHtml, PreviewImageHtml, PreviewMarkdownHtml, PreviewModelHtml,
+    PreviewPdfHtml, PreviewTextHtml, PreviewUnknownHtml, PreviewVideoHtml, RangeHtml, RareTxt,
+    RuneHtml, RunesHtml, SatHtml, SatributeHtml, TransactionHtml,
   },
   axum::{
-    body,
-    extract::{DefaultBodyLimit, Extension, Json, Path, Query},
+    body::{self, StreamBody},
+    extract::{DefaultBodyLimit, Extension, Json, Path, Query, RawQuery},
     http::{header, HeaderValue, StatusCode, Uri},
-    response::{IntoResponse, Redirect, Response},
+    response::{
+      sse::{self, KeepAlive, Sse},
+      IntoResponse, Redirect, Response,
+    },
     routing::{get, post},
     Router,
   },
@@ -30,9 +38,13 @@ use {
     AcmeConfig,
   },
   std::{cmp::Ordering, str, sync::Arc},
-  tokio_stream::StreamExt,
+  tokio::sync::{broadcast, mpsc},
+  tokio_stream::{wrappers::ReceiverStream, StreamExt},
   tower_http::{
-    compression::CompressionLayer,
+    compression::{
+      predicate::{DefaultPredicate, NotForContentType, Predicate},
+      CompressionLayer,
+    },
     cors::{Any, CorsLayer},
     set_header::SetResponseHeaderLayer,
     validate_request::ValidateRequestHeaderLayer,
@@ -44,8 +56,10 @@ pub(crate) use server_config::ServerConfig;
 mod accept_encoding;
 mod accept_json;
 mod error;
+mod event_filter;
 pub mod query;
 mod server_config;
+mod webhook;
 
 enum SpawnConfig {
   Https(AxumAcceptor),
@@ -58,6 +72,26 @@ struct Search {
   query: String,
 }
 
+#[derive(Deserialize)]
+struct EventsQuery {
+  address: Option<Address<NetworkUnchecked>>,
+  inscription: Option<InscriptionId>,
+  parent: Option<InscriptionId>,
+  rune: Option<DeserializeFromStr<query::Rune>>,
+}
+
+#[derive(Deserialize)]
+struct MetadataQuery {
+  key: String,
+  value: String,
+}
+
+#[derive(Deserialize)]
+struct HistoryQuery {
+  from: Option<u32>,
+  to: Option<u32>,
+}
+
 #[derive(RustEmbed)]
 #[folder = "static"]
 struct StaticAssets;
@@ -103,6 +137,11 @@ pub struct Server {
   pub(crate) decompress: bool,
   #[arg(long, help = "Disable JSON API.")]
   pub(crate) disable_json_api: bool,
+  #[arg(
+    long,
+    help = "Serve consistent snapshots of the index at `/snapshot`. Each request copies the entire index, so only enable this on instances that are not publicly reachable."
+  )]
+  pub(crate) enable_snapshots: bool,
   #[arg(
     long,
     help = "Listen on <HTTP_PORT> for incoming HTTP requests. [default: 80]"
@@ -126,6 +165,11 @@ pub struct Server {
   pub(crate) redirect_http_to_https: bool,
   #[arg(long, alias = "nosync", help = "Do not update the index.")]
   pub(crate) no_sync: bool,
+  #[arg(
+    long,
+    help = "Track pending inscriptions, transfers and runestones in the mempool."
+  )]
+  pub(crate) mempool: bool,
   #[arg(
     long,
     help = "Proxy `/content/INSCRIPTION_ID` requests to `<CONTENT_PROXY>/content/INSCRIPTION_ID` if the inscription is not present on current chain."
@@ -141,9 +185,29 @@ pub struct Server {
 
 impl Server {
   pub fn run(self, settings: Settings, index: Arc<Index>, handle: Handle) -> SubcommandResult {
+    self.run_with_events(settings, index, handle, None)
+  }
+
+  /// Like `run`, but forwards events from `event_receiver` to `/events`
+  /// subscribers. Without a receiver, `/events` streams nothing.
+  pub fn run_with_events(
+    self,
+    settings: Settings,
+    index: Arc<Index>,
+    handle: Handle,
+    event_receiver: Option<mpsc::Receiver<Event>>,
+  ) -> SubcommandResult {
     Runtime::new()?.block_on(async {
+      if !settings.webhooks().is_empty() && !index.has_event_index() {
+        bail!("webhooks require index created with `--index-events` flag");
+      }
+
       let index_clone = index.clone();
-      let integration_test = settings.integration_test();
+      let polling_interval = if settings.integration_test() {
+        Duration::from_millis(100)
+      } else {
+        self.polling_interval.into()
+      };
 
       let index_thread = thread::spawn(move || loop {
         if SHUTTING_DOWN.load(atomic::Ordering::Relaxed) {
@@ -156,15 +220,49 @@ impl Server {
           }
         }
 
-        thread::sleep(if integration_test {
-          Duration::from_millis(100)
-        } else {
-          self.polling_interval.into()
-        });
+        thread::sleep(polling_interval);
       });
 
       INDEXER.lock().unwrap().replace(index_thread);
 
+      if self.mempool && !self.no_sync {
+        let index = index.clone();
+
+        let mempool_thread = thread::spawn(move || loop {
+          if SHUTTING_DOWN.load(atomic::Ordering::Relaxed) {
+            break;
+          }
+
+          if let Err(error) = index.update_mempool() {
+            log::warn!("Updating mempool: {error}");
+          }
+
+          thread::sleep(polling_interval);
+        });
+
+        MEMPOOL.lock().unwrap().replace(mempool_thread);
+      }
+
+      for url in settings.webhooks() {
+        let webhook = Webhook::new(url, settings.webhook_secret(), &settings.data_dir())?;
+        let index = index.clone();
+        WEBHOOKS
+          .lock()
+          .unwrap()
+          .push(thread::spawn(move || webhook.run(&index, polling_interval)));
+      }
+
+      let (event_broadcast, _) = broadcast::channel(1024);
+
+      if let Some(mut event_receiver) = event_receiver {
+        let event_broadcast = event_broadcast.clone();
+        tokio::spawn(async move {
+          while let Some(event) = event_receiver.recv().await {
+            event_broadcast.send(event).ok();
+          }
+        });
+      }
+
       let settings = Arc::new(settings);
       let acme_domains = self.acme_domains()?;
 
@@ -176,10 +274,13 @@ impl Server {
         domain: acme_domains.first().cloned(),
         index_sats: index.has_sat_index(),
         json_api_enabled: !self.disable_json_api,
+        mempool: self.mempool,
+        snapshots: self.enable_snapshots,
       });
 
       let router = Router::new()
         .route("/", get(Self::home))
+        .route("/address/:address", get(Self::address))
         .route("/block/:query", get(Self::block))
         .route("/blockcount", get(Self::block_count))
         .route("/blockhash", get(Self::block_hash))
@@ -194,14 +295,32 @@ impl Server {
           get(Self::children_paginated),
         )
         .route("/clock", get(Self::clock))
+        .route("/collection/:inscription_query", get(Self::collection))
         .route("/collections", get(Self::collections))
         .route("/collections/:page", get(Self::collections_paginated))
         .route("/content/:inscription_id", get(Self::content))
+        .route("/events", get(Self::events))
         .route("/faq", get(Self::faq))
         .route("/favicon.ico", get(Self::favicon))
         .route("/feed.xml", get(Self::feed))
         .route("/input/:block/:transaction/:input", get(Self::input))
         .route("/inscription/:inscription_query", get(Self::inscription))
+        .route(
+          "/inscription/:inscription_query/delegators",
+          get(Self::delegators),
+        )
+        .route(
+          "/inscription/:inscription_query/delegators/:page",
+          get(Self::delegators_paginated),
+        )
+        .route(
+          "/inscription/:inscription_query/history",
+          get(Self::inscription_history),
+        )
+        .route(
+          "/inscription/:inscription_query/sales",
+          get(Self::inscription_sales),
+        )
         .route("/inscriptions", get(Self::inscriptions))
         .route("/inscriptions", post(Self::inscriptions_json))
         .route("/inscriptions/:page", get(Self::inscriptions_paginated))
@@ -213,7 +332,42 @@ impl Server {
           "/inscriptions/block/:height/:page",
           get(Self::inscriptions_in_block_paginated),
         )
+        .route(
+          "/inscriptions/body-hash/:body_hash",
+          get(Self::inscriptions_by_body_hash),
+        )
+        .route(
+          "/inscriptions/body-hash/:body_hash/:page",
+          get(Self::inscriptions_by_body_hash_paginated),
+        )
+        .route(
+          "/inscriptions/content-type/:content_type",
+          get(Self::inscriptions_by_content_type),
+        )
+        .route(
+          "/inscriptions/content-type/:content_type/:page",
+          get(Self::inscriptions_by_content_type_paginated),
+        )
+        .route(
+          "/inscriptions/metadata",
+          get(Self::inscriptions_by_metadata),
+        )
+        .route(
+          "/inscriptions/metadata/:page",
+          get(Self::inscriptions_by_metadata_paginated),
+        )
+        .route(
+          "/inscriptions/metaprotocol/:metaprotocol",
+          get(Self::inscriptions_by_metaprotocol),
+        )
+        .route(
+          "/inscriptions/metaprotocol/:metaprotocol/:page",
+          get(Self::inscriptions_by_metaprotocol_paginated),
+        )
         .route("/install.sh", get(Self::install_script))
+        .route("/mempool/inscriptions", get(Self::mempool_inscriptions))
+        .route("/mempool/runes", get(Self::mempool_runes))
+        .route("/metaprotocols", get(Self::metaprotocols))
         .route("/ordinal/:sat", get(Self::ordinal))
         .route("/output/:output", get(Self::output))
         .route("/outputs", post(Self::outputs))
@@ -253,18 +407,32 @@ impl Server {
         .route("/range/:start/:end", get(Self::range))
         .route("/rare.txt", get(Self::rare_txt))
         .route("/rune/:rune", get(Self::rune))
+        .route("/rune/:rune/history", get(Self::rune_history))
+        .route("/rune/:rune/holders", get(Self::rune_holders))
+        .route(
+          "/rune/:rune/holders/:page",
+          get(Self::rune_holders_paginated),
+        )
         .route("/runes", get(Self::runes))
         .route("/runes/:page", get(Self::runes_paginated))
         .route("/runes/balances", get(Self::runes_balances))
         .route("/sat/:sat", get(Self::sat))
+        .route("/satribute/:satribute", get(Self::satribute))
+        .route(
+          "/satribute/:satribute/:page",
+          get(Self::satribute_paginated),
+        )
+        .route("/satributes", get(Self::satributes))
         .route("/search", get(Self::search_by_query))
         .route("/search/*query", get(Self::search_by_path))
+        .route("/snapshot", get(Self::snapshot))
         .route("/static/*path", get(Self::static_asset))
         .route("/status", get(Self::status))
         .route("/tx/:txid", get(Self::transaction))
         .route("/update", get(Self::update))
         .fallback(Self::fallback)
         .layer(Extension(index))
+        .layer(Extension(event_broadcast))
         .layer(Extension(server_config.clone()))
         .layer(Extension(settings.clone()))
         .layer(SetResponseHeaderLayer::if_not_present(
@@ -280,7 +448,9 @@ impl Server {
             .allow_methods([http::Method::GET])
             .allow_origin(Any),
         )
-        .layer(CompressionLayer::new())
+        .layer(CompressionLayer::new().compress_when(
+          DefaultPredicate::new().and(NotForContentType::const_new("text/event-stream")),
+        ))
         .with_state(server_config.clone());
 
       let router = if server_config.json_api_enabled {
@@ -524,6 +694,42 @@ impl Server {
     })
   }
 
+  async fn address(
+    Extension(server_config): Extension<Arc<ServerConfig>>,
+    Extension(index): Extension<Arc<Index>>,
+    Path(address): Path<Address<NetworkUnchecked>>,
+    AcceptJson(accept_json): AcceptJson,
+  ) -> ServerResult {
+    task::block_in_place(|| {
+      if !index.has_address_index() {
+        return Err(ServerError::NotFound(
+          "this server has no address index".to_string(),
+        ));
+      }
+
+      let address = address
+        .require_network(server_config.chain.network())
+        .map_err(|err| ServerError::BadRequest(err.to_string()))?;
+
+      let info = index.get_address_info(&address)?;
+
+      Ok(if accept_json {
+        Json(info).into_response()
+      } else {
+        AddressHtml {
+          address,
+          cardinal_sat_balance: info.cardinal_sat_balance,
+          inscriptions: info.inscriptions,
+          outputs: info.outputs,
+          runes: info.runes,
+          sat_balance: info.sat_balance,
+        }
+        .page(server_config)
+        .into_response()
+      })
+    })
+  }
+
   async fn sat(
     Extension(server_config): Extension<Arc<ServerConfig>>,
     Extension(index): Extension<Arc<Index>>,
@@ -544,6 +750,8 @@ impl Server {
 
       let charms = sat.charms();
 
+      let satributes = index.satribute_classifier()?.classify(sat);
+
       Ok(if accept_json {
         Json(api::Sat {
           number: sat.0,
@@ -558,6 +766,7 @@ impl Server {
           rarity: sat.rarity(),
           percentile: sat.percentile(),
           satpoint,
+          satributes,
           timestamp: blocktime.timestamp().timestamp(),
           inscriptions,
           charms: Charm::charms(charms),
@@ -567,6 +776,7 @@ impl Server {
         SatHtml {
           sat,
           satpoint,
+          satributes,
           blocktime,
           inscriptions,
         }
@@ -576,10 +786,186 @@ impl Server {
     })
   }
 
+  async fn satribute(
+    Extension(server_config): Extension<Arc<ServerConfig>>,
+    Extension(index): Extension<Arc<Index>>,
+    Path(satribute): Path<DeserializeFromStr<Satribute>>,
+    accept_json: AcceptJson,
+  ) -> ServerResult {
+    Self::satribute_paginated(
+      Extension(server_config),
+      Extension(index),
+      Path((satribute, 0)),
+      accept_json,
+    )
+    .await
+  }
+
+  async fn satribute_paginated(
+    Extension(server_config): Extension<Arc<ServerConfig>>,
+    Extension(index): Extension<Arc<Index>>,
+    Path((DeserializeFromStr(satribute), page_index)): Path<(DeserializeFromStr<Satribute>, usize)>,
+    AcceptJson(accept_json): AcceptJson,
+  ) -> ServerResult {
+    task::block_in_place(|| {
+      if !index.has_sat_index() {
+        return Err(ServerError::NotFound(
+          "this server has no sat index".to_string(),
+        ));
+      }
+
+      let (outputs, more) = index.get_satribute_outputs_paginated(satribute, 100, page_index)?;
+
+      let prev = page_index.checked_sub(1);
+
+      let next = more.then_some(page_index + 1);
+
+      Ok(if accept_json {
+        Json(api::SatributeOutputs {
+          satribute,
+          outputs,
+          more,
+          prev,
+          next,
+        })
+        .into_response()
+      } else {
+        SatributeHtml {
+          satribute,
+          outputs,
+          prev,
+          next,
+        }
+        .page(server_config)
+        .into_response()
+      })
+    })
+  }
+
+  async fn satributes(Extension(index): Extension<Arc<Index>>) -> ServerResult {
+    task::block_in_place(|| {
+      Ok(
+        Json(api::Satributes {
+          ranges: index.satribute_classifier()?.ranges().to_vec(),
+        })
+        .into_response(),
+      )
+    })
+  }
+
+  async fn snapshot(
+    Extension(server_config): Extension<Arc<ServerConfig>>,
+    Extension(settings): Extension<Arc<Settings>>,
+    Extension(index): Extension<Arc<Index>>,
+  ) -> ServerResult {
+    if !server_config.snapshots {
+      return Err(ServerError::NotFound(
+        "this server does not serve snapshots".to_string(),
+      ));
+    }
+
+    task::block_in_place(|| {
+      let tempdir = tempfile::TempDir::new_in(settings.data_dir())
+        .context("failed to create snapshot directory")?;
+
+      let path = tempdir.path().join("index.redb");
+
+      index.snapshot(&path)?;
+
+      let mut file = fs::File::open(path).context("failed to open snapshot")?;
+
+      let (sender, receiver) = mpsc::channel(1);
+
+      thread::spawn(move || {
+        // the snapshot is deleted along with its directory once it's been sent
+        let _tempdir = tempdir;
+
+        let mut buffer = vec![0; 1 << 20];
+
+        loop {
+          match file.read(&mut buffer) {
+            Ok(0) => break,
+            Ok(n) => {
+              if sender.blocking_send(Ok(buffer[..n].to_vec())).is_err() {
+                break;
+              }
+            }
+            Err(err) => {
+              sender.blocking_send(Err(err)).ok();
+              break;
+            }
+          }
+        }
+      });
+
+      Ok(
+        (
+          [(header::CONTENT_TYPE, "application/octet-stream")],
+          StreamBody::new(ReceiverStream::new(receiver)),
+        )
+          .into_response(),
+      )
+    })
+  }
+
   async fn ordinal(Path(sat): Path<String>) -> Redirect {
     Redirect::to(&format!("/sat/{sat}"))
   }
 
+  async fn mempool_inscriptions(
+    Extension(server_config): Extension<Arc<ServerConfig>>,
+    Extension(index): Extension<Arc<Index>>,
+    AcceptJson(accept_json): AcceptJson,
+  ) -> ServerResult {
+    if !server_config.mempool {
+      return Err(ServerError::NotFound(
+        "this server does not track the mempool".to_string(),
+      ));
+    }
+
+    Ok(if accept_json {
+      Json(index.get_mempool_inscriptions()).into_response()
+    } else {
+      StatusCode::NOT_FOUND.into_response()
+    })
+  }
+
+  async fn mempool_runes(
+    Extension(server_config): Extension<Arc<ServerConfig>>,
+    Extension(index): Extension<Arc<Index>>,
+    AcceptJson(accept_json): AcceptJson,
+  ) -> ServerResult {
+    if !server_config.mempool {
+      return Err(ServerError::NotFound(
+        "this server does not track the mempool".to_string(),
+      ));
+    }
+
+    Ok(if accept_json {
+      Json(index.get_mempool_runes()).into_response()
+    } else {
+      StatusCode::NOT_FOUND.into_response()
+    })
+  }
+
+  async fn metaprotocols(
+    Extension(server_config): Extension<Arc<ServerConfig>>,
+    Extension(index): Extension<Arc<Index>>,
+    AcceptJson(accept_json): AcceptJson,
+  ) -> ServerResult {
+    task::block_in_place(|| {
+      let metaprotocols = index.get_metaprotocols()?;
+
+      Ok(if accept_json {
+        Json(api::Metaprotocols { metaprotocols }).into_response()
+      } else {
+        MetaprotocolsHtml { metaprotocols }
+          .page(server_config)
+          .into_response()
+      })
+    })
+  }
+
   async fn output(
     Extension(server_config): Extension<Arc<ServerConfig>>,
     Extension(index): Extension<Arc<Index>>,
@@ -599,6 +985,7 @@ impl Server {
           inscriptions: output_info.inscriptions,
           outpoint,
           output: txout,
+          pending: index.get_mempool_spender(outpoint),
           runes: output_info.runes,
           sat_ranges: output_info.sat_ranges,
           spent: output_info.spent,
@@ -664,15 +1051,7 @@ impl Server {
         ));
       }
 
-      let rune = match rune_query {
-        query::Rune::Spaced(spaced_rune) => spaced_rune.rune,
-        query::Rune::Id(rune_id) => index
-          .get_rune_by_id(rune_id)?
-          .ok_or_not_found(|| format!("rune {rune_id}"))?,
-        query::Rune::Number(number) => index
-          .get_rune_by_number(usize::try_from(number).unwrap())?
-          .ok_or_not_found(|| format!("rune number {number}"))?,
-      };
+      let rune = Self::rune_from_query(&index, rune_query)?;
 
       let (id, entry, parent) = index
         .rune(rune)?
@@ -682,9 +1061,12 @@ impl Server {
 
       let mintable = entry.mintable((block_height.n() + 1).into()).is_ok();
 
+      let holders = index.get_rune_holder_count(id)?;
+
       Ok(if accept_json {
         Json(api::Rune {
           entry,
+          holders,
           id,
           mintable,
           parent,
@@ -693,6 +1075,7 @@ impl Server {
       } else {
         RuneHtml {
           entry,
+          holders,
           id,
           mintable,
           parent,
@@ -703,30 +1086,160 @@ impl Server {
     })
   }
 
-  async fn runes(
+  async fn rune_history(
+    Extension(index): Extension<Arc<Index>>,
+    Path(DeserializeFromStr(rune_query)): Path<DeserializeFromStr<query::Rune>>,
+    Query(query): Query<HistoryQuery>,
+    AcceptJson(accept_json): AcceptJson,
+  ) -> ServerResult {
+    task::block_in_place(|| {
+      if !accept_json {
+        return Ok(StatusCode::NOT_FOUND.into_response());
+      }
+
+      if !index.has_rune_index() {
+        return Err(ServerError::NotFound(
+          "this server has no rune index".to_string(),
+        ));
+      }
+
+      let from = query.from.unwrap_or_default();
+      let to = query.to.unwrap_or(u32::MAX);
+
+      if from > to {
+        return Err(ServerError::BadRequest(
+          "history start greater than history end".to_string(),
+        ));
+      }
+
+      let rune = Self::rune_from_query(&index, rune_query)?;
+
+      let (id, entry, _parent) = index
+        .rune(rune)?
+        .ok_or_not_found(|| format!("rune {rune}"))?;
+
+      let blocks = index
+        .get_rune_history(id, from, to)?
+        .ok_or_not_found(|| format!("rune {rune}"))?;
+
+      let supply = index
+        .get_rune_supply_at_height(id, to)?
+        .ok_or_not_found(|| format!("rune {rune}"))?;
+
+      Ok(
+        Json(api::RuneHistory {
+          blocks,
+          id,
+          rune: entry.spaced_rune,
+          supply,
+        })
+        .into_response(),
+      )
+    })
+  }
+
+  async fn rune_holders(
     Extension(server_config): Extension<Arc<ServerConfig>>,
     Extension(index): Extension<Arc<Index>>,
+    Path(rune_query): Path<DeserializeFromStr<query::Rune>>,
     accept_json: AcceptJson,
-  ) -> ServerResult<Response> {
-    Self::runes_paginated(
+  ) -> ServerResult {
+    Self::rune_holders_paginated(
       Extension(server_config),
       Extension(index),
-      Path(0),
+      Path((rune_query, 0)),
       accept_json,
     )
     .await
   }
 
-  async fn runes_paginated(
+  async fn rune_holders_paginated(
     Extension(server_config): Extension<Arc<ServerConfig>>,
     Extension(index): Extension<Arc<Index>>,
-    Path(page_index): Path<usize>,
+    Path((DeserializeFromStr(rune_query), page_index)): Path<(
+      DeserializeFromStr<query::Rune>,
+      usize,
+    )>,
     AcceptJson(accept_json): AcceptJson,
   ) -> ServerResult {
     task::block_in_place(|| {
-      let (entries, more) = index.runes_paginated(50, page_index)?;
+      if !accept_json {
+        return Ok(StatusCode::NOT_FOUND.into_response());
+      }
 
-      let prev = page_index.checked_sub(1);
+      if !index.has_rune_index() {
+        return Err(ServerError::NotFound(
+          "this server has no rune index".to_string(),
+        ));
+      }
+
+      let rune = Self::rune_from_query(&index, rune_query)?;
+
+      let (id, _entry, _parent) = index
+        .rune(rune)?
+        .ok_or_not_found(|| format!("rune {rune}"))?;
+
+      let (holders, more) = index.get_rune_holders_paginated(id, 100, page_index)?;
+
+      Ok(
+        Json(api::RuneHolders {
+          holders: holders
+            .into_iter()
+            .map(|(script_pubkey, amount)| api::RuneHolder {
+              address: server_config
+                .chain
+                .address_from_script(&script_pubkey)
+                .ok()
+                .map(|address| uncheck(&address)),
+              amount,
+              script_pubkey,
+            })
+            .collect(),
+          more,
+          prev: page_index.checked_sub(1),
+          next: more.then_some(page_index + 1),
+        })
+        .into_response(),
+      )
+    })
+  }
+
+  fn rune_from_query(index: &Index, rune_query: query::Rune) -> ServerResult<Rune> {
+    Ok(match rune_query {
+      query::Rune::Spaced(spaced_rune) => spaced_rune.rune,
+      query::Rune::Id(rune_id) => index
+        .get_rune_by_id(rune_id)?
+        .ok_or_not_found(|| format!("rune {rune_id}"))?,
+      query::Rune::Number(number) => index
+        .get_rune_by_number(usize::try_from(number).unwrap())?
+        .ok_or_not_found(|| format!("rune number {number}"))?,
+    })
+  }
+
+  async fn runes(
+    Extension(server_config): Extension<Arc<ServerConfig>>,
+    Extension(index): Extension<Arc<Index>>,
+    accept_json: AcceptJson,
+  ) -> ServerResult<Response> {
+    Self::runes_paginated(
+      Extension(server_config),
+      Extension(index),
+      Path(0),
+      accept_json,
+    )
+    .await
+  }
+
+  async fn runes_paginated(
+    Extension(server_config): Extension<Arc<ServerConfig>>,
+    Extension(index): Extension<Arc<Index>>,
+    Path(page_index): Path<usize>,
+    AcceptJson(accept_json): AcceptJson,
+  ) -> ServerResult {
+    task::block_in_place(|| {
+      let (entries, more) = index.runes_paginated(50, page_index)?;
+
+      let prev = page_index.checked_sub(1);
 
       let next = more.then_some(page_index + 1);
 
@@ -1113,6 +1626,85 @@ impl Server {
     })
   }
 
+  async fn events(
+    Extension(server_config): Extension<Arc<ServerConfig>>,
+    Extension(index): Extension<Arc<Index>>,
+    Extension(event_broadcast): Extension<broadcast::Sender<Event>>,
+    Query(query): Query<EventsQuery>,
+  ) -> ServerResult {
+    let chain = server_config.chain;
+
+    let filter = task::block_in_place(|| {
+      Ok::<EventFilter, ServerError>(EventFilter {
+        address: query
+          .address
+          .map(|address| address.require_network(chain.network()))
+          .transpose()
+          .map_err(|err| ServerError::BadRequest(err.to_string()))?,
+        inscription: query.inscription,
+        parent: query
+          .parent
+          .map(|parent| {
+            index
+              .get_inscription_entry(parent)?
+              .map(|entry| (parent, entry.sequence_number))
+              .ok_or_not_found(|| format!("inscription {parent}"))
+          })
+          .transpose()?,
+        rune: query
+          .rune
+          .map(|DeserializeFromStr(rune_query)| match rune_query {
+            query::Rune::Spaced(spaced_rune) => index
+              .rune(spaced_rune.rune)?
+              .map(|(id, _entry, _parent)| id)
+              .ok_or_not_found(|| format!("rune {spaced_rune}")),
+            query::Rune::Id(rune_id) => Ok(rune_id),
+            query::Rune::Number(number) => {
+              let rune = index
+                .get_rune_by_number(usize::try_from(number).unwrap())?
+                .ok_or_not_found(|| format!("rune number {number}"))?;
+
+              index
+                .rune(rune)?
+                .map(|(id, _entry, _parent)| id)
+                .ok_or_not_found(|| format!("rune number {number}"))
+            }
+          })
+          .transpose()?,
+      })
+    })?;
+
+    let stream = futures::stream::unfold(
+      (event_broadcast.subscribe(), filter, index),
+      move |(mut receiver, filter, index)| async move {
+        loop {
+          match receiver.recv().await {
+            Ok(event) => match task::block_in_place(|| filter.matches(&index, &event)) {
+              Ok(true) => {
+                return Some((
+                  sse::Event::default().json_data(&event),
+                  (receiver, filter, index),
+                ))
+              }
+              Ok(false) => {}
+              Err(err) => log::warn!("failed to filter event: {err}"),
+            },
+            Err(broadcast::error::RecvError::Lagged(skipped)) => {
+              log::warn!("event subscriber lagged, skipped {skipped} events");
+            }
+            Err(broadcast::error::RecvError::Closed) => return None,
+          }
+        }
+      },
+    );
+
+    Ok(
+      Sse::new(stream)
+        .keep_alive(KeepAlive::default())
+        .into_response(),
+    )
+  }
+
   async fn static_asset(Path(path): Path<String>) -> ServerResult {
     let content = StaticAssets::get(if let Some(stripped) = path.strip_prefix('/') {
       stripped
@@ -1547,14 +2139,17 @@ impl Server {
             acc
           })),
           children: info.children,
+          delegator_count: info.delegator_count,
           fee: info.fee,
           height: info.height,
           inscription,
           id: info.id,
           number: info.number,
           next: info.next,
+          original: info.original,
           output: txout,
           parents: info.parents,
+          pending: index.get_mempool_spender(info.satpoint.outpoint),
           previous: info.previous,
           rune: info.rune,
           sat: info.sat,
@@ -1567,6 +2162,181 @@ impl Server {
     })
   }
 
+  async fn delegators(
+    Extension(server_config): Extension<Arc<ServerConfig>>,
+    Extension(index): Extension<Arc<Index>>,
+    Path(query): Path<DeserializeFromStr<query::Inscription>>,
+    AcceptJson(accept_json): AcceptJson,
+  ) -> ServerResult {
+    Self::delegators_paginated(
+      Extension(server_config),
+      Extension(index),
+      Path((query, 0)),
+      AcceptJson(accept_json),
+    )
+    .await
+  }
+
+  async fn delegators_paginated(
+    Extension(server_config): Extension<Arc<ServerConfig>>,
+    Extension(index): Extension<Arc<Index>>,
+    Path((DeserializeFromStr(query), page_index)): Path<(
+      DeserializeFromStr<query::Inscription>,
+      u32,
+    )>,
+    AcceptJson(accept_json): AcceptJson,
+  ) -> ServerResult {
+    task::block_in_place(|| {
+      let entry = index
+        .get_inscription_entry_by_query(query)?
+        .ok_or_not_found(|| format!("inscription {query}"))?;
+
+      let delegate = entry.id;
+
+      let (delegators, more) = index.get_delegators_paginated(
+        delegate,
+        100,
+        usize::try_from(page_index).unwrap_or(usize::MAX),
+      )?;
+
+      Ok(if accept_json {
+        Json(api::Inscriptions {
+          ids: delegators,
+          page_index,
+          more,
+        })
+        .into_response()
+      } else {
+        PaginatedInscriptionsHtml {
+          filter: InscriptionFilter::Delegate {
+            id: delegate,
+            number: entry.inscription_number,
+          },
+          inscriptions: delegators,
+          next_page: more.then_some(page_index + 1),
+          prev_page: page_index.checked_sub(1),
+        }
+        .page(server_config)
+        .into_response()
+      })
+    })
+  }
+
+  async fn inscription_history(
+    Extension(server_config): Extension<Arc<ServerConfig>>,
+    Extension(index): Extension<Arc<Index>>,
+    Path(DeserializeFromStr(query)): Path<DeserializeFromStr<query::Inscription>>,
+    AcceptJson(accept_json): AcceptJson,
+  ) -> ServerResult {
+    task::block_in_place(|| {
+      if !index.has_transfer_index() {
+        return Err(ServerError::NotFound(
+          "this server has no transfer index".to_string(),
+        ));
+      }
+
+      let entry = index
+        .get_inscription_entry_by_query(query)?
+        .ok_or_not_found(|| format!("inscription {query}"))?;
+
+      let inscription_id = entry.id;
+
+      let address = |script: Option<ScriptBuf>| {
+        script.and_then(|script| {
+          server_config
+            .chain
+            .address_from_script(&script)
+            .ok()
+            .map(|address| uncheck(&address))
+        })
+      };
+
+      let transfers = index
+        .get_inscription_history(inscription_id)?
+        .ok_or_not_found(|| format!("inscription {query}"))?
+        .into_iter()
+        .map(|transfer| api::InscriptionTransfer {
+          from: address(transfer.from),
+          height: transfer.height,
+          new_satpoint: transfer.new_satpoint,
+          old_satpoint: transfer.old_satpoint,
+          to: address(transfer.to),
+          txid: transfer.txid,
+        })
+        .collect::<Vec<api::InscriptionTransfer>>();
+
+      Ok(if accept_json {
+        Json(api::InscriptionHistory {
+          id: inscription_id,
+          number: entry.inscription_number,
+          transfers,
+        })
+        .into_response()
+      } else {
+        InscriptionHistoryHtml {
+          id: inscription_id,
+          index_addresses: index.has_address_index(),
+          number: entry.inscription_number,
+          transfers,
+        }
+        .page(server_config)
+        .into_response()
+      })
+    })
+  }
+
+  async fn inscription_sales(
+    Extension(server_config): Extension<Arc<ServerConfig>>,
+    Extension(index): Extension<Arc<Index>>,
+    Path(DeserializeFromStr(query)): Path<DeserializeFromStr<query::Inscription>>,
+    AcceptJson(accept_json): AcceptJson,
+  ) -> ServerResult {
+    task::block_in_place(|| {
+      let entry = index
+        .get_inscription_entry_by_query(query)?
+        .ok_or_not_found(|| format!("inscription {query}"))?;
+
+      let address = |script: &Script| {
+        server_config
+          .chain
+          .address_from_script(script)
+          .ok()
+          .map(|address| uncheck(&address))
+      };
+
+      let sales = index
+        .get_inscription_sales(entry.id)?
+        .ok_or_not_found(|| format!("inscription {query}"))?
+        .into_iter()
+        .map(|sale| api::Sale {
+          buyer: address(&sale.buyer),
+          height: sale.height,
+          price: sale.price,
+          seller: address(&sale.seller),
+          txid: sale.txid,
+        })
+        .collect::<Vec<api::Sale>>();
+
+      Ok(if accept_json {
+        Json(api::InscriptionSales {
+          id: entry.id,
+          number: entry.inscription_number,
+          sales,
+        })
+        .into_response()
+      } else {
+        InscriptionSalesHtml {
+          id: entry.id,
+          index_addresses: index.has_address_index(),
+          number: entry.inscription_number,
+          sales,
+        }
+        .page(server_config)
+        .into_response()
+      })
+    })
+  }
+
   async fn inscriptions_json(
     Extension(index): Extension<Arc<Index>>,
     AcceptJson(accept_json): AcceptJson,
@@ -1591,6 +2361,29 @@ impl Server {
     })
   }
 
+  async fn collection(
+    Extension(server_config): Extension<Arc<ServerConfig>>,
+    Extension(index): Extension<Arc<Index>>,
+    Path(DeserializeFromStr(query)): Path<DeserializeFromStr<query::Inscription>>,
+    AcceptJson(accept_json): AcceptJson,
+  ) -> ServerResult {
+    task::block_in_place(|| {
+      let entry = index
+        .get_inscription_entry_by_query(query)?
+        .ok_or_not_found(|| format!("inscription {query}"))?;
+
+      let collection = index.get_collection(&entry)?;
+
+      Ok(if accept_json {
+        Json(collection).into_response()
+      } else {
+        CollectionHtml { collection }
+          .page(server_config)
+          .into_response()
+      })
+    })
+  }
+
   async fn collections(
     Extension(server_config): Extension<Arc<ServerConfig>>,
     Extension(index): Extension<Arc<Index>>,
@@ -1695,12 +2488,16 @@ impl Server {
   async fn inscriptions(
     Extension(server_config): Extension<Arc<ServerConfig>>,
     Extension(index): Extension<Arc<Index>>,
+    query: Query<query::Inscriptions>,
+    raw_query: RawQuery,
     accept_json: AcceptJson,
   ) -> ServerResult {
     Self::inscriptions_paginated(
       Extension(server_config),
       Extension(index),
       Path(0),
+      query,
+      raw_query,
       accept_json,
     )
     .await
@@ -1710,10 +2507,16 @@ impl Server {
     Extension(server_config): Extension<Arc<ServerConfig>>,
     Extension(index): Extension<Arc<Index>>,
     Path(page_index): Path<u32>,
+    Query(query): Query<query::Inscriptions>,
+    RawQuery(raw_query): RawQuery,
     AcceptJson(accept_json): AcceptJson,
   ) -> ServerResult {
     task::block_in_place(|| {
-      let (inscriptions, more) = index.get_inscriptions_paginated(100, page_index)?;
+      let (inscriptions, more) = if query.is_empty() {
+        index.get_inscriptions_paginated(100, page_index)?
+      } else {
+        index.get_inscriptions_filtered(&query, 100, page_index)?
+      };
 
       let prev = page_index.checked_sub(1);
 
@@ -1731,6 +2534,109 @@ impl Server {
           inscriptions,
           next,
           prev,
+          query: raw_query
+            .map(|raw_query| format!("?{raw_query}"))
+            .unwrap_or_default(),
+        }
+        .page(server_config)
+        .into_response()
+      })
+    })
+  }
+
+  async fn inscriptions_by_body_hash(
+    Extension(server_config): Extension<Arc<ServerConfig>>,
+    Extension(index): Extension<Arc<Index>>,
+    Path(body_hash): Path<DeserializeFromStr<sha256::Hash>>,
+    AcceptJson(accept_json): AcceptJson,
+  ) -> ServerResult {
+    Self::inscriptions_by_body_hash_paginated(
+      Extension(server_config),
+      Extension(index),
+      Path((body_hash, 0)),
+      AcceptJson(accept_json),
+    )
+    .await
+  }
+
+  async fn inscriptions_by_body_hash_paginated(
+    Extension(server_config): Extension<Arc<ServerConfig>>,
+    Extension(index): Extension<Arc<Index>>,
+    Path((DeserializeFromStr(body_hash), page_index)): Path<(
+      DeserializeFromStr<sha256::Hash>,
+      u32,
+    )>,
+    AcceptJson(accept_json): AcceptJson,
+  ) -> ServerResult {
+    task::block_in_place(|| {
+      let (inscriptions, more) = index.get_inscriptions_by_body_hash_paginated(
+        body_hash,
+        100,
+        usize::try_from(page_index).unwrap_or(usize::MAX),
+      )?;
+
+      Ok(if accept_json {
+        Json(api::Inscriptions {
+          ids: inscriptions,
+          page_index,
+          more,
+        })
+        .into_response()
+      } else {
+        PaginatedInscriptionsHtml {
+          filter: InscriptionFilter::BodyHash(body_hash),
+          inscriptions,
+          next_page: more.then_some(page_index + 1),
+          prev_page: page_index.checked_sub(1),
+        }
+        .page(server_config)
+        .into_response()
+      })
+    })
+  }
+
+  async fn inscriptions_by_content_type(
+    Extension(server_config): Extension<Arc<ServerConfig>>,
+    Extension(index): Extension<Arc<Index>>,
+    Path(content_type): Path<String>,
+    AcceptJson(accept_json): AcceptJson,
+  ) -> ServerResult {
+    Self::inscriptions_by_content_type_paginated(
+      Extension(server_config),
+      Extension(index),
+      Path((content_type, 0)),
+      AcceptJson(accept_json),
+    )
+    .await
+  }
+
+  async fn inscriptions_by_content_type_paginated(
+    Extension(server_config): Extension<Arc<ServerConfig>>,
+    Extension(index): Extension<Arc<Index>>,
+    Path((content_type, page_index)): Path<(String, u32)>,
+    AcceptJson(accept_json): AcceptJson,
+  ) -> ServerResult {
+    task::block_in_place(|| {
+      let query = query::Inscriptions {
+        content_type: Some(content_type.clone()),
+        ..default()
+      };
+
+      let (inscriptions, more) = index.get_inscriptions_filtered(&query, 100, page_index)?;
+
+      Ok(if accept_json {
+        Json(api::Inscriptions {
+          ids: inscriptions,
+          page_index,
+          more,
+        })
+        .into_response()
+      } else {
+        PaginatedInscriptionsHtml {
+          filter: InscriptionFilter::ContentType(content_type),
+          inscriptions,
+          next_page: more.then_some(page_index + 1),
+          prev_page: page_index.checked_sub(1),
         }
         .page(server_config)
         .into_response()
@@ -1799,7 +2705,112 @@ impl Server {
     })
   }
 
-  async fn parents(
+  async fn inscriptions_by_metadata(
+    Extension(server_config): Extension<Arc<ServerConfig>>,
+    Extension(index): Extension<Arc<Index>>,
+    Query(query): Query<MetadataQuery>,
+    AcceptJson(accept_json): AcceptJson,
+  ) -> ServerResult {
+    Self::inscriptions_by_metadata_paginated(
+      Extension(server_config),
+      Extension(index),
+      Path(0),
+      Query(query),
+      AcceptJson(accept_json),
+    )
+    .await
+  }
+
+  async fn inscriptions_by_metadata_paginated(
+    Extension(server_config): Extension<Arc<ServerConfig>>,
+    Extension(index): Extension<Arc<Index>>,
+    Path(page_index): Path<u32>,
+    Query(MetadataQuery { key, value }): Query<MetadataQuery>,
+    AcceptJson(accept_json): AcceptJson,
+  ) -> ServerResult {
+    task::block_in_place(|| {
+      if !index.has_metadata_index() {
+        return Err(ServerError::NotFound(
+          "this server has no metadata index".to_string(),
+        ));
+      }
+
+      let (inscriptions, more) = index.get_inscriptions_by_metadata_paginated(
+        &key,
+        &value,
+        100,
+        usize::try_from(page_index).unwrap_or(usize::MAX),
+      )?;
+
+      Ok(if accept_json {
+        Json(api::Inscriptions {
+          ids: inscriptions,
+          page_index,
+          more,
+        })
+        .into_response()
+      } else {
+        PaginatedInscriptionsHtml {
+          filter: InscriptionFilter::Metadata { key, value },
+          inscriptions,
+          next_page: more.then_some(page_index + 1),
+          prev_page: page_index.checked_sub(1),
+        }
+        .page(server_config)
+        .into_response()
+      })
+    })
+  }
+
+  async fn inscriptions_by_metaprotocol(
+    Extension(server_config): Extension<Arc<ServerConfig>>,
+    Extension(index): Extension<Arc<Index>>,
+    Path(metaprotocol): Path<String>,
+    AcceptJson(accept_json): AcceptJson,
+  ) -> ServerResult {
+    Self::inscriptions_by_metaprotocol_paginated(
+      Extension(server_config),
+      Extension(index),
+      Path((metaprotocol, 0)),
+      AcceptJson(accept_json),
+    )
+    .await
+  }
+
+  async fn inscriptions_by_metaprotocol_paginated(
+    Extension(server_config): Extension<Arc<ServerConfig>>,
+    Extension(index): Extension<Arc<Index>>,
+    Path((metaprotocol, page_index)): Path<(String, u32)>,
+    AcceptJson(accept_json): AcceptJson,
+  ) -> ServerResult {
+    task::block_in_place(|| {
+      let (inscriptions, more) = index.get_inscriptions_by_metaprotocol_paginated(
+        &metaprotocol,
+        100,
+        usize::try_from(page_index).unwrap_or(usize::MAX),
+      )?;
+
+      Ok(if accept_json {
+        Json(api::Inscriptions {
+          ids: inscriptions,
+          page_index,
+          more,
+        })
+        .into_response()
+      } else {
+        PaginatedInscriptionsHtml {
+          filter: InscriptionFilter::Metaprotocol(metaprotocol),
+          inscriptions,
+          next_page: more.then_some(page_index + 1),
+          prev_page: page_index.checked_sub(1),
+        }
+        .page(server_config)
+        .into_response()
+      })
+    })
+  }
+
+  async fn parents(
     Extension(server_config): Extension<Arc<ServerConfig>>,
     Extension(index): Extension<Arc<Index>>,
     Path(inscription_id): Path<InscriptionId>,
@@ -2030,13 +3041,18 @@ mod tests {
         .or_defaults()
         .unwrap();
 
-      let index = Arc::new(Index::open(&settings).unwrap());
+      let (event_sender, event_receiver) = mpsc::channel(1024);
+      let index = Arc::new(Index::open_with_event_sender(&settings, Some(event_sender)).unwrap());
       let ord_server_handle = Handle::new();
 
       {
         let index = index.clone();
         let ord_server_handle = ord_server_handle.clone();
-        thread::spawn(|| server.run(settings, index, ord_server_handle).unwrap());
+        thread::spawn(|| {
+          server
+            .run_with_events(settings, index, ord_server_handle, Some(event_receiver))
+            .unwrap()
+        });
       }
 
       while index.statistic(crate::index::Statistic::Commits) == 0 {
@@ -2888,6 +3904,8 @@ mod tests {
   <dd>100%</dd>
   <dt>burned</dt>
   <dd>0\u{A0}%</dd>
+  <dt>holders</dt>
+  <dd>1</dd>
   <dt>divisibility</dt>
   <dd>0</dd>
   <dt>symbol</dt>
@@ -3215,6 +4233,7 @@ mod tests {
         sat_ranges: None,
         indexed: true,
         inscriptions: Vec::new(),
+        pending: false,
         runes: vec![(
           SpacedRune {
             rune: Rune(RUNE),
@@ -3896,7 +4915,9 @@ mod tests {
 
   #[test]
   fn detect_unrecoverable_reorg() {
-    let test_server = TestServer::new();
+    let test_server = TestServer::builder()
+      .ord_flag("--max-reorg-depth=10")
+      .build();
 
     test_server.mine_blocks(21);
 
@@ -3966,6 +4987,86 @@ mod tests {
       );
   }
 
+  #[test]
+  fn satribute_outputs() {
+    let server = TestServer::builder().index_sats().build();
+
+    let first_coinbase = server.mine_blocks(1)[0].txdata[0].txid();
+
+    let txid = server.core.broadcast_tx(TransactionTemplate {
+      inputs: &[(1, 0, 0, Default::default())],
+      ..default()
+    });
+
+    let second_coinbase = server.mine_blocks(1)[0].txdata[0].txid();
+
+    let outputs = server
+      .get_json::<api::SatributeOutputs>("/satribute/alpha")
+      .outputs
+      .into_iter()
+      .collect::<BTreeSet<OutPoint>>();
+
+    assert_eq!(
+      outputs,
+      [
+        Chain::Mainnet.genesis_coinbase_outpoint(),
+        OutPoint { txid, vout: 0 },
+        OutPoint {
+          txid: second_coinbase,
+          vout: 0
+        },
+      ]
+      .into_iter()
+      .collect()
+    );
+
+    assert!(!outputs.contains(&OutPoint {
+      txid: first_coinbase,
+      vout: 0
+    }));
+
+    server.assert_response_regex(
+      "/satribute/block9",
+      StatusCode::OK,
+      ".*<title>Satribute block9</title>.*<h2>0 Outputs</h2>.*",
+    );
+
+    server.assert_response_regex(
+      "/satribute/vintage",
+      StatusCode::OK,
+      format!(".*<h2>3 Outputs</h2>.*<a href=/output/{txid}:0>.*"),
+    );
+  }
+
+  #[test]
+  fn satribute_requires_sat_index() {
+    TestServer::new().assert_response(
+      "/satribute/alpha",
+      StatusCode::NOT_FOUND,
+      "this server has no sat index",
+    );
+  }
+
+  #[test]
+  fn invalid_satribute() {
+    TestServer::builder().index_sats().build().assert_response(
+      "/satribute/foo",
+      StatusCode::BAD_REQUEST,
+      "Invalid URL: invalid satribute `foo`",
+    );
+  }
+
+  #[test]
+  fn satributes() {
+    pretty_assert_eq!(
+      TestServer::builder()
+        .index_sats()
+        .build()
+        .get_json::<api::Satributes>("/satributes"),
+      api::Satributes { ranges: Vec::new() }
+    );
+  }
+
   #[test]
   fn dont_show_rare_txt_in_header_without_sat_index() {
     TestServer::new().assert_response_regex(
@@ -5050,6 +6151,166 @@ next
     );
   }
 
+  #[test]
+  fn collection() {
+    let server = TestServer::builder().chain(Chain::Regtest).build();
+    server.mine_blocks(1);
+
+    let parent = InscriptionId {
+      txid: server.core.broadcast_tx(TransactionTemplate {
+        inputs: &[(1, 0, 0, inscription("text/plain", "parent").to_witness())],
+        ..default()
+      }),
+      index: 0,
+    };
+
+    server.mine_blocks(1);
+
+    let child = InscriptionId {
+      txid: server.core.broadcast_tx(TransactionTemplate {
+        inputs: &[
+          (
+            2,
+            0,
+            0,
+            Inscription {
+              content_type: Some("text/plain".into()),
+              body: Some("child".into()),
+              parents: vec![parent.value()],
+              ..default()
+            }
+            .to_witness(),
+          ),
+          (2, 1, 0, Default::default()),
+        ],
+        p2tr: true,
+        ..default()
+      }),
+      index: 0,
+    };
+
+    server.mine_blocks(1);
+
+    server.core.broadcast_tx(TransactionTemplate {
+      inputs: &[
+        (
+          3,
+          0,
+          0,
+          Inscription {
+            content_type: Some("image/png".into()),
+            body: Some("child".into()),
+            parents: vec![parent.value()],
+            ..default()
+          }
+          .to_witness(),
+        ),
+        (3, 1, 0, Default::default()),
+      ],
+      outputs: 2,
+      output_values: &[50 * COIN_VALUE, 100 * COIN_VALUE],
+      p2tr: true,
+      ..default()
+    });
+
+    server.mine_blocks(1);
+
+    assert_eq!(
+      server.get_json::<api::Collection>(format!("/collection/{parent}")),
+      api::Collection {
+        children: 2,
+        content_types: vec![
+          (Some("image/png".into()), 1),
+          (Some("text/plain".into()), 1),
+        ],
+        first_child_height: Some(3),
+        grandchildren: 0,
+        holders: 2,
+        id: parent,
+        last_child_height: Some(4),
+        mints_per_block: vec![(3, 1), (4, 1)],
+        number: 0,
+      }
+    );
+
+    server.core.broadcast_tx(TransactionTemplate {
+      inputs: &[
+        (
+          4,
+          0,
+          0,
+          Inscription {
+            content_type: Some("text/plain".into()),
+            body: Some("grandchild".into()),
+            parents: vec![child.value()],
+            ..default()
+          }
+          .to_witness(),
+        ),
+        (4, 1, 0, Default::default()),
+        (4, 1, 1, Default::default()),
+      ],
+      p2tr: true,
+      ..default()
+    });
+
+    server.mine_blocks(1);
+
+    assert_eq!(
+      server.get_json::<api::Collection>("/collection/0"),
+      api::Collection {
+        children: 2,
+        content_types: vec![
+          (Some("image/png".into()), 1),
+          (Some("text/plain".into()), 1),
+        ],
+        first_child_height: Some(3),
+        grandchildren: 1,
+        holders: 1,
+        id: parent,
+        last_child_height: Some(4),
+        mints_per_block: vec![(3, 1), (4, 1)],
+        number: 0,
+      }
+    );
+
+    assert_eq!(
+      server.get_json::<api::Collection>(format!("/collection/{child}")),
+      api::Collection {
+        children: 1,
+        content_types: vec![(Some("text/plain".into()), 1)],
+        first_child_height: Some(5),
+        grandchildren: 0,
+        holders: 1,
+        id: child,
+        last_child_height: Some(5),
+        mints_per_block: vec![(5, 1)],
+        number: 1,
+      }
+    );
+
+    server.assert_response_regex(
+      format!("/collection/{parent}"),
+      StatusCode::OK,
+      format!(
+        ".*<title>Inscription 0 Collection</title>.*
+<h1><a href=/inscription/{parent}>Inscription 0</a> Collection</h1>
+<dl>
+  <dt>children</dt>
+  <dd><a href=/children/{parent}>2</a></dd>
+  <dt>holders</dt>
+  <dd>1</dd>
+.*"
+      ),
+    );
+
+    server.assert_response(
+      "/collection/100",
+      StatusCode::NOT_FOUND,
+      "inscription 100 not found",
+    );
+  }
+
   #[test]
   fn inscriptions_page_shows_max_four_children() {
     let server = TestServer::builder().chain(Chain::Regtest).build();
@@ -6256,4 +7517,1189 @@ next
     let server = TestServer::builder().build();
     server.assert_response("/update", StatusCode::NOT_FOUND, "");
   }
+
+  fn read_events(response: reqwest::blocking::Response, block_height: u32) -> Vec<Event> {
+    use std::io::BufRead;
+
+    assert_eq!(response.status(), StatusCode::OK);
+
+    let mut events = Vec::new();
+
+    for line in io::BufReader::new(response).lines() {
+      let line = line.unwrap();
+
+      let Some(data) = line.strip_prefix("data:") else {
+        continue;
+      };
+
+      let event = serde_json::from_str(data.trim()).unwrap();
+
+      let done = matches!(
+        event,
+        Event::BlockCommitted { block_height: height, .. } if height == block_height
+      );
+
+      events.push(event);
+
+      if done {
+        break;
+      }
+    }
+
+    events
+  }
+
+  #[test]
+  fn inscription_sales() {
+    let server = TestServer::builder().chain(Chain::Regtest).build();
+
+    server.mine_blocks(2);
+
+    let txid = server.core.broadcast_tx(TransactionTemplate {
+      inputs: &[(1, 0, 0, inscription("text/plain", "foo").to_witness())],
+      outputs: 1,
+      ..default()
+    });
+
+    server.mine_blocks(1);
+
+    let inscription_id = InscriptionId { txid, index: 0 };
+
+    let mut signature = vec![0; 64];
+    signature.push(0x83);
+
+    let sale_txid = server.core.broadcast_tx(TransactionTemplate {
+      inputs: &[
+        (2, 0, 0, Default::default()),
+        (3, 1, 0, Witness::from_slice(&[signature])),
+      ],
+      outputs: 2,
+      output_values: &[60 * COIN_VALUE, 40 * COIN_VALUE],
+      p2tr: true,
+      ..default()
+    });
+
+    server.mine_blocks(1);
+
+    let sales = server.get_json::<api::InscriptionSales>("/inscription/0/sales");
+
+    assert_eq!(sales.id, inscription_id);
+    assert_eq!(sales.number, 0);
+    assert_eq!(sales.sales.len(), 1);
+    assert_eq!(sales.sales[0].height, 4);
+    assert_eq!(sales.sales[0].price, 40 * COIN_VALUE);
+    assert_eq!(sales.sales[0].txid, sale_txid);
+
+    server.assert_response_regex(
+      format!("/inscription/{inscription_id}/sales"),
+      StatusCode::OK,
+      format!(
+        ".*<title>Inscription 0 Sales</title>.*
+<h1><a href=/inscription/{inscription_id}>Inscription 0</a> Sales</h1>
+<table>
+.*
+  <tr>
+    <td><a href=/block/4>4</a></td>
+    <td><a class=monospace href=/tx/{sale_txid}>{sale_txid}</a></td>
+    <td>4000000000</td>
+    <td class=monospace>bcrt1p.*</td>
+    <td class=monospace>bcrt1p.*</td>
+  </tr>
+</table>.*"
+      ),
+    );
+  }
+
+  #[test]
+  fn inscription_history_json() {
+    let server = TestServer::builder()
+      .chain(Chain::Regtest)
+      .ord_flag("--index-transfers")
+      .build();
+
+    server.mine_blocks(1);
+
+    let txid = server.core.broadcast_tx(TransactionTemplate {
+      inputs: &[(1, 0, 0, inscription("text/plain", "foo").to_witness())],
+      ..default()
+    });
+
+    server.mine_blocks(1);
+
+    let inscription_id = InscriptionId { txid, index: 0 };
+
+    let transfer_txid = server.core.broadcast_tx(TransactionTemplate {
+      inputs: &[(2, 1, 0, Default::default())],
+      p2tr: true,
+      ..default()
+    });
+
+    server.mine_blocks(1);
+
+    let receiver = Chain::Regtest
+      .address_from_script(&server.core.tx_by_id(transfer_txid).output[0].script_pubkey)
+      .unwrap();
+
+    let history =
+      server.get_json::<api::InscriptionHistory>(format!("/inscription/{inscription_id}/history"));
+
+    assert_eq!(history.id, inscription_id);
+    assert_eq!(history.number, 0);
+    assert_eq!(history.transfers.len(), 2);
+    assert_eq!(history.transfers[0].height, 2);
+    assert_eq!(history.transfers[0].old_satpoint, None);
+    assert_eq!(history.transfers[0].txid, txid);
+    assert_eq!(
+      history.transfers[1],
+      api::InscriptionTransfer {
+        from: history.transfers[0].to.clone(),
+        height: 3,
+        new_satpoint: SatPoint {
+          outpoint: OutPoint {
+            txid: transfer_txid,
+            vout: 0,
+          },
+          offset: 0,
+        },
+        old_satpoint: Some(SatPoint {
+          outpoint: OutPoint { txid, vout: 0 },
+          offset: 0,
+        }),
+        to: Some(uncheck(&receiver)),
+        txid: transfer_txid,
+      }
+    );
+
+    assert_eq!(
+      server.get_json::<api::InscriptionHistory>("/inscription/0/history"),
+      history
+    );
+  }
+
+  #[test]
+  fn inscription_history_page() {
+    let server = TestServer::builder()
+      .chain(Chain::Regtest)
+      .ord_flag("--index-addresses")
+      .ord_flag("--index-transfers")
+      .build();
+
+    server.mine_blocks(1);
+
+    let txid = server.core.broadcast_tx(TransactionTemplate {
+      inputs: &[(1, 0, 0, inscription("text/plain", "foo").to_witness())],
+      ..default()
+    });
+
+    server.mine_blocks(1);
+
+    server.assert_response_regex(
+      format!("/inscription/{txid}i0/history"),
+      StatusCode::OK,
+      format!(
+        ".*<title>Inscription 0 History</title>.*
+<h1><a href=/inscription/{txid}i0>Inscription 0</a> History</h1>
+<table>
+.*
+  <tr>
+    <td><a href=/block/2>2</a></td>
+    <td><a class=monospace href=/tx/{txid}>{txid}</a></td>
+    <td>created</td>
+    <td><a class=monospace href=/address/bcrt1q.*>bcrt1q.*</a></td>
+    <td><a class=monospace href=/output/{txid}:0>{txid}:0:0</a></td>
+  </tr>
+</table>.*"
+      ),
+    );
+  }
+
+  #[test]
+  fn inscription_history_not_found() {
+    TestServer::builder()
+      .chain(Chain::Regtest)
+      .ord_flag("--index-transfers")
+      .build()
+      .assert_response_regex(
+        format!("/inscription/{}/history", inscription_id(1)),
+        StatusCode::NOT_FOUND,
+        ".*",
+      );
+  }
+
+  #[test]
+  fn inscription_history_requires_transfer_index() {
+    let server = TestServer::builder().chain(Chain::Regtest).build();
+
+    server.mine_blocks(1);
+
+    let txid = server.core.broadcast_tx(TransactionTemplate {
+      inputs: &[(1, 0, 0, inscription("text/plain", "foo").to_witness())],
+      ..default()
+    });
+
+    server.mine_blocks(1);
+
+    server.assert_response(
+      format!("/inscription/{txid}i0/history"),
+      StatusCode::NOT_FOUND,
+      "this server has no transfer index",
+    );
+  }
+
+  #[test]
+  fn delegators() {
+    let server = TestServer::builder().chain(Chain::Regtest).build();
+
+    server.mine_blocks(1);
+
+    let delegate = server.core.broadcast_tx(TransactionTemplate {
+      inputs: &[(1, 0, 0, inscription("text/plain", "foo").to_witness())],
+      ..default()
+    });
+
+    server.mine_blocks(1);
+
+    let delegate = InscriptionId {
+      txid: delegate,
+      index: 0,
+    };
+
+    let delegator = server.core.broadcast_tx(TransactionTemplate {
+      inputs: &[(
+        2,
+        1,
+        0,
+        Inscription {
+          delegate: Some(delegate.value()),
+          ..default()
+        }
+        .to_witness(),
+      )],
+      ..default()
+    });
+
+    server.mine_blocks(1);
+
+    let delegator = InscriptionId {
+      txid: delegator,
+      index: 0,
+    };
+
+    assert_eq!(
+      server.get_json::<api::Inscriptions>(format!("/inscription/{delegate}/delegators")),
+      api::Inscriptions {
+        ids: vec![delegator],
+        more: false,
+        page_index: 0,
+      }
+    );
+
+    assert_eq!(
+      server.get_json::<api::Inscriptions>("/inscription/0/delegators/0"),
+      api::Inscriptions {
+        ids: vec![delegator],
+        more: false,
+        page_index: 0,
+      }
+    );
+
+    assert_eq!(
+      server
+        .get_json::<api::Inscription>(format!("/inscription/{delegate}"))
+        .delegator_count,
+      1
+    );
+
+    server.assert_response_regex(
+      format!("/inscription/{delegate}"),
+      StatusCode::OK,
+      format!(
+        ".*<dt>delegators</dt>
+  <dd><a href=/inscription/{delegate}/delegators>1</a></dd>.*"
+      ),
+    );
+
+    server.assert_response_regex(
+      format!("/inscription/{delegate}/delegators"),
+      StatusCode::OK,
+      format!(
+        ".*<title>Inscription 0 Delegators</title>.*
+<h1><a href=/inscription/{delegate}>Inscription 0</a> Delegators</h1>
+<div class=thumbnails>
+  <a href=/inscription/{delegator}>.*</a>
+</div>.*"
+      ),
+    );
+
+    server.assert_response_regex(
+      format!("/inscription/{}/delegators", inscription_id(1)),
+      StatusCode::NOT_FOUND,
+      ".*",
+    );
+  }
+
+  #[test]
+  fn inscriptions_by_body_hash() {
+    let server = TestServer::builder().chain(Chain::Regtest).build();
+
+    server.mine_blocks(2);
+
+    let first = server.core.broadcast_tx(TransactionTemplate {
+      inputs: &[(1, 0, 0, inscription("text/plain", "foo").to_witness())],
+      ..default()
+    });
+
+    server.mine_blocks(1);
+
+    let second = server.core.broadcast_tx(TransactionTemplate {
+      inputs: &[(2, 0, 0, inscription("text/plain", "foo").to_witness())],
+      ..default()
+    });
+
+    server.mine_blocks(1);
+
+    let first = InscriptionId {
+      txid: first,
+      index: 0,
+    };
+
+    let second = InscriptionId {
+      txid: second,
+      index: 0,
+    };
+
+    let hash = "2c26b46b68ffc68ff99b453c1d30413413422d706483bfa0f98a5e886266e7ae";
+
+    assert_eq!(
+      server.get_json::<api::Inscriptions>(format!("/inscriptions/body-hash/{hash}")),
+      api::Inscriptions {
+        ids: vec![first, second],
+        more: false,
+        page_index: 0,
+      }
+    );
+
+    let inscription = server.get_json::<api::Inscription>(format!("/inscription/{second}"));
+
+    assert_eq!(inscription.body_hash, Some(hash.parse().unwrap()));
+    assert_eq!(inscription.original, Some(first));
+
+    assert_eq!(
+      server
+        .get_json::<api::Inscription>(format!("/inscription/{first}"))
+        .original,
+      None
+    );
+
+    server.assert_response_regex(
+      format!("/inscriptions/body-hash/{hash}"),
+      StatusCode::OK,
+      format!(
+        ".*<title>Body Hash {hash}</title>.*
+<div class=thumbnails>
+  <a href=/inscription/{first}>.*</a>
+  <a href=/inscription/{second}>.*</a>
+</div>.*"
+      ),
+    );
+
+    server.assert_response_regex(
+      format!("/inscription/{second}"),
+      StatusCode::OK,
+      format!(
+        ".*<dt>body hash</dt>
+  <dd><a class=monospace href=/inscriptions/body-hash/{hash}>{hash}</a></dd>
+  <dt>original</dt>
+  <dd><a class=monospace href=/inscription/{first}>{first}</a></dd>.*"
+      ),
+    );
+
+    server.assert_response_regex("/inscriptions/body-hash/foo", StatusCode::BAD_REQUEST, ".*");
+  }
+
+  #[test]
+  fn inscriptions_by_content_type() {
+    let server = TestServer::builder().chain(Chain::Regtest).build();
+
+    server.mine_blocks(2);
+
+    let png = server.core.broadcast_tx(TransactionTemplate {
+      inputs: &[(1, 0, 0, inscription("image/png", "foo").to_witness())],
+      ..default()
+    });
+
+    server.mine_blocks(1);
+
+    let text = server.core.broadcast_tx(TransactionTemplate {
+      inputs: &[(2, 0, 0, inscription("text/plain", "bar").to_witness())],
+      ..default()
+    });
+
+    server.mine_blocks(1);
+
+    assert_eq!(
+      server.get_json::<api::Inscriptions>("/inscriptions/content-type/image%2Fpng"),
+      api::Inscriptions {
+        ids: vec![InscriptionId {
+          txid: png,
+          index: 0
+        }],
+        more: false,
+        page_index: 0,
+      }
+    );
+
+    assert_eq!(
+      server.get_json::<api::Inscriptions>("/inscriptions?content_type=text%2Fplain"),
+      api::Inscriptions {
+        ids: vec![InscriptionId {
+          txid: text,
+          index: 0
+        }],
+        more: false,
+        page_index: 0,
+      }
+    );
+
+    assert_eq!(
+      server.get_json::<api::Inscriptions>("/inscriptions?order=oldest&min_height=3"),
+      api::Inscriptions {
+        ids: vec![
+          InscriptionId {
+            txid: png,
+            index: 0
+          },
+          InscriptionId {
+            txid: text,
+            index: 0
+          }
+        ],
+        more: false,
+        page_index: 0,
+      }
+    );
+
+    server.assert_response_regex(
+      "/inscriptions/content-type/image%2Fpng",
+      StatusCode::OK,
+      format!(
+        ".*<title>Content Type image/png</title>.*
+<h1>Content Type image/png</h1>
+<div class=thumbnails>
+  <a href=/inscription/{png}i0>.*</a>
+</div>.*"
+      ),
+    );
+
+    server.assert_response_regex(
+      "/inscriptions?charm=cursed",
+      StatusCode::OK,
+      ".*<h1>All Inscriptions</h1>
+<div class=thumbnails>
+</div>.*",
+    );
+
+    server.assert_response(
+      "/inscriptions?charm=foo",
+      StatusCode::BAD_REQUEST,
+      "Failed to deserialize query string: invalid charm `foo`",
+    );
+  }
+
+  #[test]
+  fn inscriptions_by_metaprotocol() {
+    let server = TestServer::builder().chain(Chain::Regtest).build();
+
+    server.mine_blocks(1);
+
+    let txid = server.core.broadcast_tx(TransactionTemplate {
+      inputs: &[(
+        1,
+        0,
+        0,
+        Inscription {
+          content_type: Some("text/plain".into()),
+          body: Some("hello".into()),
+          metaprotocol: Some("foo bar".into()),
+          ..default()
+        }
+        .to_witness(),
+      )],
+      ..default()
+    });
+
+    server.mine_blocks(1);
+
+    assert_eq!(
+      server.get_json::<api::Metaprotocols>("/metaprotocols"),
+      api::Metaprotocols {
+        metaprotocols: vec![("foo bar".into(), 1)],
+      }
+    );
+
+    assert_eq!(
+      server.get_json::<api::Inscriptions>("/inscriptions/metaprotocol/foo%20bar"),
+      api::Inscriptions {
+        ids: vec![InscriptionId { txid, index: 0 }],
+        more: false,
+        page_index: 0,
+      }
+    );
+
+    assert_eq!(
+      server.get_json::<api::Inscriptions>("/inscriptions/metaprotocol/foo%20bar/1"),
+      api::Inscriptions {
+        ids: Vec::new(),
+        more: false,
+        page_index: 1,
+      }
+    );
+
+    server.assert_response_regex(
+      "/metaprotocols",
+      StatusCode::OK,
+      ".*<title>Metaprotocols</title>.*
+  <tr>
+    <td><a href=/inscriptions/metaprotocol/foo%20bar>foo bar</a></td>
+    <td>1</td>
+  </tr>.*",
+    );
+
+    server.assert_response_regex(
+      "/inscriptions/metaprotocol/foo%20bar",
+      StatusCode::OK,
+      format!(
+        ".*<title>Metaprotocol foo bar</title>.*
+<h1>Metaprotocol foo bar</h1>
+<div class=thumbnails>
+  <a href=/inscription/{txid}i0>.*</a>
+</div>.*"
+      ),
+    );
+  }
+
+  #[test]
+  fn inscriptions_by_metadata() {
+    let server = TestServer::builder()
+      .chain(Chain::Regtest)
+      .ord_flag("--index-metadata")
+      .build();
+
+    server.mine_blocks(1);
+
+    let mut metadata = Vec::new();
+    ciborium::into_writer(
+      &Value::Map(vec![
+        (Value::Text("name".into()), Value::Text("foo bar".into())),
+        (Value::Text("rank".into()), Value::Integer(7.into())),
+      ]),
+      &mut metadata,
+    )
+    .unwrap();
+
+    let txid = server.core.broadcast_tx(TransactionTemplate {
+      inputs: &[(
+        1,
+        0,
+        0,
+        Inscription {
+          content_type: Some("text/plain".into()),
+          body: Some("hello".into()),
+          metadata: Some(metadata),
+          ..default()
+        }
+        .to_witness(),
+      )],
+      ..default()
+    });
+
+    server.mine_blocks(1);
+
+    assert_eq!(
+      server.get_json::<api::Inscriptions>("/inscriptions/metadata?key=name&value=foo%20bar"),
+      api::Inscriptions {
+        ids: vec![InscriptionId { txid, index: 0 }],
+        more: false,
+        page_index: 0,
+      }
+    );
+
+    assert_eq!(
+      server.get_json::<api::Inscriptions>("/inscriptions/metadata?key=rank&value=7"),
+      api::Inscriptions {
+        ids: vec![InscriptionId { txid, index: 0 }],
+        more: false,
+        page_index: 0,
+      }
+    );
+
+    assert_eq!(
+      server.get_json::<api::Inscriptions>("/inscriptions/metadata/1?key=rank&value=7"),
+      api::Inscriptions {
+        ids: Vec::new(),
+        more: false,
+        page_index: 1,
+      }
+    );
+
+    assert_eq!(
+      server.get_json::<api::Inscriptions>("/inscriptions/metadata?key=rank&value=8"),
+      api::Inscriptions {
+        ids: Vec::new(),
+        more: false,
+        page_index: 0,
+      }
+    );
+
+    server.assert_response_regex(
+      "/inscriptions/metadata?key=name&value=foo%20bar",
+      StatusCode::OK,
+      format!(
+        ".*<title>Metadata name = foo bar</title>.*
+<h1>Metadata name = foo bar</h1>
+<div class=thumbnails>
+  <a href=/inscription/{txid}i0>.*</a>
+</div>.*"
+      ),
+    );
+  }
+
+  #[test]
+  fn inscriptions_by_metadata_requires_metadata_index() {
+    let server = TestServer::builder().chain(Chain::Regtest).build();
+
+    server.assert_response(
+      "/inscriptions/metadata?key=name&value=foo",
+      StatusCode::NOT_FOUND,
+      "this server has no metadata index",
+    );
+  }
+
+  #[test]
+  fn mempool_inscriptions_json() {
+    let server = TestServer::builder()
+      .chain(Chain::Regtest)
+      .server_flag("--mempool")
+      .build();
+
+    server.mine_blocks(1);
+
+    let txid = server.core.broadcast_tx(TransactionTemplate {
+      inputs: &[(1, 0, 0, inscription("text/plain", "foo").to_witness())],
+      ..default()
+    });
+
+    server.index.update_mempool().unwrap();
+
+    assert_eq!(
+      server.get_json::<api::MempoolInscriptions>("/mempool/inscriptions"),
+      api::MempoolInscriptions {
+        inscriptions: vec![api::MempoolInscription {
+          content_length: Some(3),
+          content_type: Some("text/plain".into()),
+          id: InscriptionId { txid, index: 0 },
+        }],
+        transfers: Vec::new(),
+      }
+    );
+
+    server.assert_response("/mempool/inscriptions", StatusCode::NOT_FOUND, "");
+  }
+
+  #[test]
+  fn mempool_runes_json() {
+    let server = TestServer::builder()
+      .chain(Chain::Regtest)
+      .index_runes()
+      .server_flag("--mempool")
+      .build();
+
+    server.mine_blocks(1);
+
+    let runestone = Runestone {
+      pointer: Some(0),
+      ..default()
+    };
+
+    let txid = server.core.broadcast_tx(TransactionTemplate {
+      inputs: &[(1, 0, 0, Witness::new())],
+      op_return: Some(runestone.encipher()),
+      ..default()
+    });
+
+    server.index.update_mempool().unwrap();
+
+    assert_eq!(
+      server.get_json::<Vec<api::MempoolRune>>("/mempool/runes"),
+      vec![api::MempoolRune {
+        artifact: Artifact::Runestone(runestone),
+        txid,
+      }]
+    );
+  }
+
+  #[test]
+  fn mempool_endpoints_require_mempool_flag() {
+    let server = TestServer::builder().chain(Chain::Regtest).build();
+
+    for path in ["/mempool/inscriptions", "/mempool/runes"] {
+      server.assert_response(
+        path,
+        StatusCode::NOT_FOUND,
+        "this server does not track the mempool",
+      );
+    }
+  }
+
+  #[test]
+  fn snapshot_endpoint_requires_enable_snapshots_flag() {
+    TestServer::new().assert_response(
+      "/snapshot",
+      StatusCode::NOT_FOUND,
+      "this server does not serve snapshots",
+    );
+  }
+
+  #[test]
+  fn pending_transfers_are_flagged() {
+    let server = TestServer::builder()
+      .chain(Chain::Regtest)
+      .server_flag("--mempool")
+      .build();
+
+    server.mine_blocks(1);
+
+    let txid = server.core.broadcast_tx(TransactionTemplate {
+      inputs: &[(1, 0, 0, inscription("text/plain", "foo").to_witness())],
+      ..default()
+    });
+
+    server.mine_blocks(1);
+
+    let inscription_id = InscriptionId { txid, index: 0 };
+
+    assert!(
+      !server
+        .get_json::<api::Inscription>(format!("/inscription/{inscription_id}"))
+        .pending
+    );
+
+    let transfer_txid = server.core.broadcast_tx(TransactionTemplate {
+      inputs: &[(2, 1, 0, Default::default())],
+      ..default()
+    });
+
+    server.index.update_mempool().unwrap();
+
+    assert!(
+      server
+        .get_json::<api::Inscription>(format!("/inscription/{inscription_id}"))
+        .pending
+    );
+
+    assert!(
+      server
+        .get_json::<api::Output>(format!("/output/{txid}:0"))
+        .pending
+    );
+
+    server.assert_response_regex(
+      format!("/inscription/{inscription_id}"),
+      StatusCode::OK,
+      format!(
+        ".*<dt>pending</dt>
+  <dd><a class=monospace href=/tx/{transfer_txid}>{transfer_txid}</a></dd>.*"
+      ),
+    );
+
+    server.assert_response_regex(
+      format!("/output/{txid}:0"),
+      StatusCode::OK,
+      format!(
+        ".*<dt>pending</dt><dd><a class=monospace href=/tx/{transfer_txid}>{transfer_txid}</a></dd>.*"
+      ),
+    );
+  }
+
+  #[test]
+  fn address_json() {
+    let server = TestServer::builder()
+      .chain(Chain::Regtest)
+      .index_runes()
+      .ord_flag("--index-addresses")
+      .build();
+
+    server.mine_blocks(1);
+
+    let rune = Rune(RUNE);
+
+    let (txid, _id) = server.etch(
+      Runestone {
+        etching: Some(Etching {
+          divisibility: Some(1),
+          rune: Some(rune),
+          premine: Some(1000),
+          ..default()
+        }),
+        ..default()
+      },
+      1,
+      None,
+    );
+
+    let address = Chain::Regtest
+      .address_from_script(&ScriptBuf::new_v0_p2wpkh(&WPubkeyHash::all_zeros()))
+      .unwrap();
+
+    let value = server.core.tx_by_id(txid).output[0].value;
+
+    pretty_assert_eq!(
+      server.get_json::<api::AddressInfo>(format!("/address/{address}")),
+      api::AddressInfo {
+        cardinal_sat_balance: 0,
+        inscriptions: Vec::new(),
+        outputs: vec![OutPoint { txid, vout: 0 }],
+        runes: [(
+          SpacedRune { rune, spacers: 0 },
+          Pile {
+            amount: 1000,
+            divisibility: 1,
+            symbol: None,
+          }
+        )]
+        .into_iter()
+        .collect(),
+        sat_balance: value,
+      }
+    );
+  }
+
+  #[test]
+  fn address_page() {
+    let server = TestServer::builder()
+      .chain(Chain::Regtest)
+      .ord_flag("--index-addresses")
+      .build();
+
+    server.mine_blocks(1);
+
+    let txid = server.core.broadcast_tx(TransactionTemplate {
+      inputs: &[(1, 0, 0, inscription("text/plain", "foo").to_witness())],
+      outputs: 2,
+      ..default()
+    });
+
+    server.mine_blocks(1);
+
+    let address = Chain::Regtest
+      .address_from_script(&ScriptBuf::new_v0_p2wpkh(&WPubkeyHash::all_zeros()))
+      .unwrap();
+
+    server.assert_response_regex(
+      format!("/address/{address}"),
+      StatusCode::OK,
+      format!(
+        ".*<title>Address {address}</title>.*<h1>Address <span class=monospace>{address}</span></h1>
+<dl>
+  <dt>sat balance</dt><dd>{}</dd>
+  <dt>cardinal sat balance</dt><dd>{}</dd>
+  <dt>inscriptions</dt>
+  <dd class=thumbnails>
+    <a href=/inscription/{txid}i0>.*</a>
+  </dd>
+</dl>
+<h2>2 Outputs</h2>
+<ul class=monospace>
+  <li><a href=/output/{txid}:0>{txid}:0</a></li>
+  <li><a href=/output/{txid}:1>{txid}:1</a></li>
+</ul>.*",
+        50 * COIN_VALUE,
+        25 * COIN_VALUE,
+      ),
+    );
+  }
+
+  #[test]
+  fn address_requires_address_index() {
+    let server = TestServer::builder().chain(Chain::Regtest).build();
+
+    let address = Chain::Regtest
+      .address_from_script(&ScriptBuf::new_v0_p2wpkh(&WPubkeyHash::all_zeros()))
+      .unwrap();
+
+    server.assert_response(
+      format!("/address/{address}"),
+      StatusCode::NOT_FOUND,
+      "this server has no address index",
+    );
+  }
+
+  #[test]
+  fn address_on_wrong_network_is_bad_request() {
+    let server = TestServer::builder()
+      .chain(Chain::Regtest)
+      .ord_flag("--index-addresses")
+      .build();
+
+    server.assert_response_regex(
+      "/address/bc1qw508d6qejxtdg4y5r3zarvary0c5xw7kv8f3t4",
+      StatusCode::BAD_REQUEST,
+      ".*network.*",
+    );
+  }
+
+  #[test]
+  fn rune_holders_json() {
+    let server = TestServer::builder()
+      .chain(Chain::Regtest)
+      .index_runes()
+      .build();
+
+    server.mine_blocks(1);
+
+    let rune = Rune(RUNE);
+
+    let (_txid, id) = server.etch(
+      Runestone {
+        etching: Some(Etching {
+          rune: Some(rune),
+          premine: Some(1000),
+          ..default()
+        }),
+        ..default()
+      },
+      1,
+      None,
+    );
+
+    let script_pubkey = ScriptBuf::new_v0_p2wpkh(&WPubkeyHash::all_zeros());
+
+    let expected = api::RuneHolders {
+      holders: vec![api::RuneHolder {
+        address: Some(uncheck(
+          &Chain::Regtest.address_from_script(&script_pubkey).unwrap(),
+        )),
+        amount: 1000,
+        script_pubkey,
+      }],
+      more: false,
+      prev: None,
+      next: None,
+    };
+
+    pretty_assert_eq!(
+      server.get_json::<api::RuneHolders>(format!("/rune/{rune}/holders")),
+      expected
+    );
+
+    pretty_assert_eq!(
+      server.get_json::<api::RuneHolders>(format!("/rune/{id}/holders/0")),
+      expected
+    );
+
+    assert_eq!(
+      server
+        .get_json::<api::Rune>(format!("/rune/{rune}"))
+        .holders,
+      1
+    );
+
+    server.assert_response(format!("/rune/{rune}/holders"), StatusCode::NOT_FOUND, "");
+  }
+
+  #[test]
+  fn rune_history_json() {
+    let server = TestServer::builder()
+      .chain(Chain::Regtest)
+      .index_runes()
+      .build();
+
+    server.mine_blocks(1);
+
+    let rune = Rune(RUNE);
+
+    let (_txid, id) = server.etch(
+      Runestone {
+        etching: Some(Etching {
+          rune: Some(rune),
+          premine: Some(1000),
+          ..default()
+        }),
+        ..default()
+      },
+      1,
+      None,
+    );
+
+    let history = api::RuneHistory {
+      blocks: vec![api::RuneHistoryBlock {
+        burned: 0,
+        height: id.block.try_into().unwrap(),
+        mints: 0,
+        supply: 1000,
+        total_burned: 0,
+        transferred: 0,
+      }],
+      id,
+      rune: SpacedRune { rune, spacers: 0 },
+      supply: 1000,
+    };
+
+    pretty_assert_eq!(
+      server.get_json::<api::RuneHistory>(format!("/rune/{rune}/history")),
+      history
+    );
+
+    pretty_assert_eq!(
+      server.get_json::<api::RuneHistory>(format!(
+        "/rune/{id}/history?from={}&to={}",
+        id.block, id.block
+      )),
+      history
+    );
+
+    let before_etching =
+      server.get_json::<api::RuneHistory>(format!("/rune/{rune}/history?to={}", id.block - 1));
+
+    assert!(before_etching.blocks.is_empty());
+    assert_eq!(before_etching.supply, 0);
+
+    server.assert_response(format!("/rune/{rune}/history"), StatusCode::NOT_FOUND, "");
+  }
+
+  #[test]
+  fn rune_history_with_inverted_range_is_bad_request() {
+    let server = TestServer::builder()
+      .chain(Chain::Regtest)
+      .index_runes()
+      .build();
+
+    let response = reqwest::blocking::Client::new()
+      .get(server.join_url(&format!("/rune/{}/history?from=2&to=1", Rune(RUNE))))
+      .header(header::ACCEPT, "application/json")
+      .send()
+      .unwrap();
+
+    assert_eq!(response.status(), StatusCode::BAD_REQUEST);
+    assert_eq!(
+      response.text().unwrap(),
+      "history start greater than history end"
+    );
+  }
+
+  #[test]
+  fn inscription_sales_not_found() {
+    TestServer::builder()
+      .chain(Chain::Regtest)
+      .build()
+      .assert_response_regex(
+        format!("/inscription/{}/sales", inscription_id(1)),
+        StatusCode::NOT_FOUND,
+        ".*",
+      );
+  }
+
+  #[test]
+  fn events_can_be_filtered_by_inscription() {
+    let server = TestServer::builder().chain(Chain::Regtest).build();
+
+    server.mine_blocks(2);
+
+    let txid = server.core.broadcast_tx(TransactionTemplate {
+      inputs: &[(1, 0, 0, inscription("text/plain", "foo").to_witness())],
+      ..default()
+    });
+
+    server.core.broadcast_tx(TransactionTemplate {
+      inputs: &[(2, 0, 0, inscription("text/plain", "bar").to_witness())],
+      ..default()
+    });
+
+    let inscription_id = InscriptionId { txid, index: 0 };
+
+    let response =
+      reqwest::blocking::get(server.join_url(&format!("/events?inscription={inscription_id}")))
+        .unwrap();
+
+    server.mine_blocks(1);
+
+    let events = read_events(response, 3);
+
+    assert_eq!(events.len(), 3);
+
+    assert_matches!(
+      events[0].clone(),
+      Event::BlockStarted {
+        block_height: 3,
+        ..
+      }
+    );
+
+    assert_matches!(
+      events[1].clone(),
+      Event::InscriptionCreated {
+        inscription_id: id,
+        ..
+      } if id == inscription_id
+    );
+
+    assert_matches!(
+      events[2].clone(),
+      Event::BlockCommitted {
+        block_height: 3,
+        inscription_count: 2,
+        ..
+      }
+    );
+  }
+
+  #[test]
+  fn events_can_be_filtered_by_address() {
+    let server = TestServer::builder().chain(Chain::Regtest).build();
+
+    server.mine_blocks(2);
+
+    let txid = server.core.broadcast_tx(TransactionTemplate {
+      inputs: &[(1, 0, 0, inscription("text/plain", "foo").to_witness())],
+      ..default()
+    });
+
+    server.core.broadcast_tx(TransactionTemplate {
+      inputs: &[(2, 0, 0, inscription("text/plain", "bar").to_witness())],
+      p2tr: true,
+      ..default()
+    });
+
+    let address = Chain::Regtest
+      .address_from_script(&ScriptBuf::new_v0_p2wpkh(&WPubkeyHash::all_zeros()))
+      .unwrap();
+
+    let response =
+      reqwest::blocking::get(server.join_url(&format!("/events?address={address}"))).unwrap();
+
+    server.mine_blocks(1);
+
+    let events = read_events(response, 3);
+
+    assert_eq!(events.len(), 3);
+
+    assert_matches!(
+      events[1].clone(),
+      Event::InscriptionCreated {
+        inscription_id: InscriptionId { txid: id, index: 0 },
+        ..
+      } if id == txid
+    );
+  }
+
+  #[test]
+  fn events_with_unknown_parent_returns_not_found() {
+    let server = TestServer::builder().chain(Chain::Regtest).build();
+
+    server.assert_response(
+      format!("/events?parent={}", inscription_id(1)),
+      StatusCode::NOT_FOUND,
+      &format!("inscription {} not found", inscription_id(1)),
+    );
+  }
 }
@@ -0,0 +1,81 @@
+use {super::*, crate::index::event::Event};
+
+#[derive(Default)]
+pub(super) struct EventFilter {
+  pub(super) address: Option<Address>,
+  pub(super) inscription: Option<InscriptionId>,
+  pub(super) parent: Option<(InscriptionId, u32)>,
+  pub(super) rune: Option<RuneId>,
+}
+
+impl EventFilter {
+  pub(super) fn matches(&self, index: &Index, event: &Event) -> Result<bool> {
+    let (inscription_id, rune_id) = match event {
+      Event::BlockCommitted { .. }
+      | Event::BlockStarted { .. }
+      | Event::ReorgDetected { .. }
+      | Event::RolledBack { .. } => return Ok(true),
+      Event::InscriptionCreated { inscription_id, .. }
+      | Event::InscriptionSold { inscription_id, .. }
+      | Event::InscriptionTransferred { inscription_id, .. } => (Some(*inscription_id), None),
+      Event::RuneBurned { rune_id, .. }
+      | Event::RuneEtched { rune_id, .. }
+      | Event::RuneMinted { rune_id, .. }
+      | Event::RuneTransferred { rune_id, .. } => (None, Some(*rune_id)),
+      Event::RuneCenotaph { burned, .. } => {
+        (None, self.rune.filter(|rune| burned.contains_key(rune)))
+      }
+    };
+
+    if self.inscription.is_some() && self.inscription != inscription_id {
+      return Ok(false);
+    }
+
+    if self.rune.is_some() && self.rune != rune_id {
+      return Ok(false);
+    }
+
+    if let Some((parent_id, parent_sequence_number)) = self.parent {
+      let child = match event {
+        Event::InscriptionCreated {
+          parent_inscription_ids,
+          ..
+        } => parent_inscription_ids.contains(&parent_id),
+        Event::InscriptionSold { inscription_id, .. }
+        | Event::InscriptionTransferred { inscription_id, .. } => index
+          .get_inscription_entry(*inscription_id)?
+          .map(|entry| entry.parents.contains(&parent_sequence_number))
+          .unwrap_or_default(),
+        _ => false,
+      };
+
+      if !child {
+        return Ok(false);
+      }
+    }
+
+    if let Some(address) = &self.address {
+      let address = uncheck(address);
+
+      let involved = match event {
+        Event::InscriptionCreated { receiver, .. } => receiver.as_ref() == Some(&address),
+        Event::InscriptionSold { buyer, seller, .. } => {
+          buyer.as_ref() == Some(&address) || seller.as_ref() == Some(&address)
+        }
+        Event::InscriptionTransferred {
+          receiver, sender, ..
+        } => receiver.as_ref() == Some(&address) || sender.as_ref() == Some(&address),
+        Event::RuneTransferred {
+          receiver, sender, ..
+        } => receiver.as_ref() == Some(&address) || sender.as_ref() == Some(&address),
+        _ => false,
+      };
+
+      if !involved {
+        return Ok(false);
+      }
+    }
+
+    Ok(true)
+  }
+}
@@ -70,3 +70,31 @@ impl FromStr for Rune {
     }
   }
 }
+
+#[derive(Default, Deserialize)]
+pub(crate) struct Inscriptions {
+  pub(crate) charm: Option<Charm>,
+  pub(crate) content_type: Option<String>,
+  pub(crate) max_height: Option<u32>,
+  pub(crate) min_height: Option<u32>,
+  #[serde(default)]
+  pub(crate) order: Order,
+}
+
+impl Inscriptions {
+  pub(super) fn is_empty(&self) -> bool {
+    self.charm.is_none()
+      && self.content_type.is_none()
+      && self.max_height.is_none()
+      && self.min_height.is_none()
+      && self.order == Order::Newest
+  }
+}
+
+#[derive(Clone, Copy, Debug, Default, Deserialize, PartialEq)]
+#[serde(rename_all = "lowercase")]
+pub(crate) enum Order {
+  #[default]
+  Newest,
+  Oldest,
+}
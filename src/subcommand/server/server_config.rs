@@ -9,6 +9,8 @@ pub(crate) struct ServerConfig {
   pub(crate) domain: Option<String>,
   pub(crate) index_sats: bool,
   pub(crate) json_api_enabled: bool,
+  pub(crate) mempool: bool,
+  pub(crate) snapshots: bool,
 }
 
 impl ServerConfig {
@@ -0,0 +1,432 @@
+use {
+  super::*,
+  crate::index::event::Event,
+  bitcoin::hashes::{
+    hmac::{Hmac, HmacEngine},
+    sha256, HashEngine,
+  },
+  reqwest::blocking::Client,
+};
+
+const BATCH_SIZE: usize = 1000;
+const MAX_BACKOFF: Duration = Duration::from_secs(300);
+
+pub(crate) const SIGNATURE_HEADER: &str = "X-Ord-Signature";
+
+#[derive(Debug, PartialEq, Serialize, Deserialize)]
+pub(crate) struct Delivery {
+  pub(crate) number: u64,
+  pub(crate) event: Event,
+}
+
+#[derive(Debug, PartialEq, Serialize, Deserialize)]
+struct Cursor {
+  index: String,
+  next: u64,
+}
+
+pub(crate) struct Webhook {
+  client: Client,
+  cursor_path: PathBuf,
+  secret: Option<String>,
+  url: Url,
+}
+
+impl Webhook {
+  pub(crate) fn new(url: &str, secret: Option<&str>, data_dir: &std::path::Path) -> Result<Self> {
+    let url = url
+      .parse::<Url>()
+      .with_context(|| format!("invalid webhook URL `{url}`"))?;
+
+    let cursor_path = data_dir
+      .join("webhooks")
+      .join(sha256::Hash::hash(url.as_str().as_bytes()).to_string());
+
+    Ok(Self {
+      client: Client::builder().timeout(Duration::from_secs(30)).build()?,
+      cursor_path,
+      secret: secret.map(Into::into),
+      url,
+    })
+  }
+
+  pub(crate) fn run(self, index: &Index, interval: Duration) {
+    let mut failures = 0;
+
+    loop {
+      if SHUTTING_DOWN.load(atomic::Ordering::Relaxed) {
+        break;
+      }
+
+      match self.deliver(index) {
+        Ok(0) => {
+          failures = 0;
+          Self::sleep(interval);
+        }
+        Ok(_) => failures = 0,
+        Err(err) => {
+          failures += 1;
+          let backoff = Self::backoff(interval, failures);
+          log::warn!(
+            "Delivering events to webhook {} failed, retrying in {}: {err}",
+            self.url,
+            humantime::format_duration(backoff),
+          );
+          Self::sleep(backoff);
+        }
+      }
+    }
+  }
+
+  pub(crate) fn deliver(&self, index: &Index) -> Result<usize> {
+    let Some(identity) = index.identity()? else {
+      return Ok(0);
+    };
+
+    let events = index.events(self.cursor(&identity)?, BATCH_SIZE)?;
+
+    let Some((last, _)) = events.last() else {
+      return Ok(0);
+    };
+
+    let next = last + 1;
+
+    let deliveries = events
+      .into_iter()
+      .map(|(number, event)| Delivery { number, event })
+      .collect::<Vec<Delivery>>();
+
+    let body = serde_json::to_vec(&deliveries)?;
+
+    let mut request = self
+      .client
+      .post(self.url.clone())
+      .header(reqwest::header::CONTENT_TYPE, "application/json");
+
+    if let Some(secret) = &self.secret {
+      request = request.header(SIGNATURE_HEADER, Self::signature(secret, &body));
+    }
+
+    request.body(body).send()?.error_for_status()?;
+
+    self.save_cursor(identity, next)?;
+
+    Ok(deliveries.len())
+  }
+
+  pub(crate) fn signature(secret: &str, body: &[u8]) -> String {
+    let mut engine = HmacEngine::<sha256::Hash>::new(secret.as_bytes());
+    engine.input(body);
+    format!("sha256={}", Hmac::<sha256::Hash>::from_engine(engine))
+  }
+
+  fn backoff(interval: Duration, failures: u32) -> Duration {
+    interval
+      .saturating_mul(1 << failures.min(16))
+      .min(MAX_BACKOFF)
+  }
+
+  // cursors saved for a different index, for example one that was rebuilt,
+  // start over from the first event
+  fn cursor(&self, identity: &str) -> Result<u64> {
+    match fs::read(&self.cursor_path) {
+      Ok(cursor) => {
+        let cursor = serde_json::from_slice::<Cursor>(&cursor)
+          .with_context(|| format!("invalid webhook cursor in `{}`", self.cursor_path.display()))?;
+
+        if cursor.index == identity {
+          Ok(cursor.next)
+        } else {
+          log::info!(
+            "Webhook {} cursor was saved for index {}, redelivering events from start",
+            self.url,
+            cursor.index,
+          );
+          Ok(0)
+        }
+      }
+      Err(err) if err.kind() == io::ErrorKind::NotFound => Ok(0),
+      Err(err) => Err(err.into()),
+    }
+  }
+
+  fn save_cursor(&self, index: String, next: u64) -> Result {
+    fs::create_dir_all(self.cursor_path.parent().unwrap())?;
+    let tmp = self.cursor_path.with_extension("tmp");
+    fs::write(&tmp, serde_json::to_vec(&Cursor { index, next })?)?;
+    fs::rename(&tmp, &self.cursor_path)?;
+    Ok(())
+  }
+
+  // sleep in short steps so that shutdown isn't held up by a long backoff
+  fn sleep(duration: Duration) {
+    let deadline = Instant::now() + duration;
+
+    while !SHUTTING_DOWN.load(atomic::Ordering::Relaxed) {
+      let now = Instant::now();
+
+      if now >= deadline {
+        break;
+      }
+
+      thread::sleep((deadline - now).min(Duration::from_millis(100)));
+    }
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use {
+    super::*,
+    crate::index::testing::Context,
+    axum::http::HeaderMap,
+    std::{
+      net::TcpListener,
+      sync::{Arc, Mutex},
+    },
+  };
+
+  #[derive(Clone, Default)]
+  struct Receiver {
+    requests: Arc<Mutex<Vec<(HeaderMap, Vec<u8>)>>>,
+    status: Arc<Mutex<Option<StatusCode>>>,
+  }
+
+  impl Receiver {
+    fn spawn() -> (Self, String) {
+      let receiver = Self::default();
+
+      let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+      let url = format!("http://{}/events", listener.local_addr().unwrap());
+
+      let router = Router::new()
+        .route(
+          "/events",
+          post(
+            |Extension(receiver): Extension<Receiver>,
+             headers: HeaderMap,
+             body: body::Bytes| async move {
+              let status = receiver.status.lock().unwrap().unwrap_or(StatusCode::OK);
+              if status.is_success() {
+                receiver
+                  .requests
+                  .lock()
+                  .unwrap()
+                  .push((headers, body.to_vec()));
+              }
+              status
+            },
+          ),
+        )
+        .layer(Extension(receiver.clone()));
+
+      thread::spawn(move || {
+        Runtime::new().unwrap().block_on(async {
+          axum::Server::from_tcp(listener)
+            .unwrap()
+            .serve(router.into_make_service())
+            .await
+            .unwrap();
+        })
+      });
+
+      (receiver, url)
+    }
+
+    fn deliveries(&self) -> Vec<Vec<Delivery>> {
+      self
+        .requests
+        .lock()
+        .unwrap()
+        .iter()
+        .map(|(_, body)| serde_json::from_slice(body).unwrap())
+        .collect()
+    }
+  }
+
+  #[test]
+  fn events_are_delivered_with_signature() {
+    let context = Context::builder().arg("--index-events").build();
+
+    context.mine_blocks(1);
+
+    let (receiver, url) = Receiver::spawn();
+
+    let webhook = Webhook::new(&url, Some("secret"), context.tempdir.path()).unwrap();
+
+    assert_eq!(webhook.deliver(&context.index).unwrap(), 4);
+    assert_eq!(webhook.deliver(&context.index).unwrap(), 0);
+
+    let requests = receiver.requests.lock().unwrap();
+
+    assert_eq!(requests.len(), 1);
+
+    let (headers, body) = &requests[0];
+
+    assert_eq!(
+      headers[SIGNATURE_HEADER].to_str().unwrap(),
+      Webhook::signature("secret", body),
+    );
+
+    assert_eq!(headers["content-type"], "application/json");
+
+    let deliveries: Vec<Delivery> = serde_json::from_slice(body).unwrap();
+
+    assert_eq!(
+      deliveries
+        .iter()
+        .map(|delivery| delivery.number)
+        .collect::<Vec<u64>>(),
+      [0, 1, 2, 3],
+    );
+
+    assert_matches!(
+      deliveries[3].event.clone(),
+      Event::BlockCommitted {
+        block_height: 1,
+        ..
+      }
+    );
+  }
+
+  #[test]
+  fn unsigned_events_have_no_signature_header() {
+    let context = Context::builder().arg("--index-events").build();
+
+    let (receiver, url) = Receiver::spawn();
+
+    let webhook = Webhook::new(&url, None, context.tempdir.path()).unwrap();
+
+    assert_eq!(webhook.deliver(&context.index).unwrap(), 2);
+
+    assert!(!receiver.requests.lock().unwrap()[0]
+      .0
+      .contains_key(SIGNATURE_HEADER));
+  }
+
+  #[test]
+  fn delivery_resumes_from_persisted_cursor() {
+    let context = Context::builder().arg("--index-events").build();
+
+    let (receiver, url) = Receiver::spawn();
+
+    assert_eq!(
+      Webhook::new(&url, None, context.tempdir.path())
+        .unwrap()
+        .deliver(&context.index)
+        .unwrap(),
+      2
+    );
+
+    context.mine_blocks(1);
+
+    assert_eq!(
+      Webhook::new(&url, None, context.tempdir.path())
+        .unwrap()
+        .deliver(&context.index)
+        .unwrap(),
+      2
+    );
+
+    let deliveries = receiver.deliveries();
+
+    assert_eq!(deliveries.len(), 2);
+    assert_eq!(deliveries[1][0].number, 2);
+  }
+
+  #[test]
+  fn cursor_for_another_index_is_reset() {
+    let context = Context::builder().arg("--index-events").build();
+
+    let (receiver, url) = Receiver::spawn();
+
+    context.mine_blocks(1);
+
+    assert_eq!(
+      Webhook::new(&url, None, context.tempdir.path())
+        .unwrap()
+        .deliver(&context.index)
+        .unwrap(),
+      4
+    );
+
+    let rebuilt = Context::builder().arg("--index-events").build();
+
+    assert_eq!(
+      Webhook::new(&url, None, context.tempdir.path())
+        .unwrap()
+        .deliver(&rebuilt.index)
+        .unwrap(),
+      2
+    );
+
+    let deliveries = receiver.deliveries();
+
+    assert_eq!(deliveries.len(), 2);
+    assert_eq!(deliveries[1][0].number, 0);
+  }
+
+  #[test]
+  fn failed_delivery_does_not_advance_cursor() {
+    let context = Context::builder().arg("--index-events").build();
+
+    let (receiver, url) = Receiver::spawn();
+
+    let webhook = Webhook::new(&url, None, context.tempdir.path()).unwrap();
+
+    *receiver.status.lock().unwrap() = Some(StatusCode::INTERNAL_SERVER_ERROR);
+
+    assert!(webhook.deliver(&context.index).is_err());
+    assert!(webhook.deliver(&context.index).is_err());
+
+    *receiver.status.lock().unwrap() = None;
+
+    assert_eq!(webhook.deliver(&context.index).unwrap(), 2);
+
+    let deliveries = receiver.deliveries();
+
+    assert_eq!(deliveries.len(), 1);
+    assert_eq!(deliveries[0][0].number, 0);
+  }
+
+  #[test]
+  fn webhooks_have_separate_cursors() {
+    let context = Context::builder().arg("--index-events").build();
+
+    let (a, a_url) = Receiver::spawn();
+    let (b, b_url) = Receiver::spawn();
+
+    Webhook::new(&a_url, None, context.tempdir.path())
+      .unwrap()
+      .deliver(&context.index)
+      .unwrap();
+
+    Webhook::new(&b_url, None, context.tempdir.path())
+      .unwrap()
+      .deliver(&context.index)
+      .unwrap();
+
+    assert_eq!(a.deliveries(), b.deliveries());
+  }
+
+  #[test]
+  fn invalid_url_is_an_error() {
+    assert_eq!(
+      Webhook::new("foo", None, std::path::Path::new("."))
+        .err()
+        .unwrap()
+        .to_string(),
+      "invalid webhook URL `foo`",
+    );
+  }
+
+  #[test]
+  fn backoff_doubles_up_to_maximum() {
+    let interval = Duration::from_secs(1);
+    assert_eq!(Webhook::backoff(interval, 1), Duration::from_secs(2));
+    assert_eq!(Webhook::backoff(interval, 2), Duration::from_secs(4));
+    assert_eq!(Webhook::backoff(interval, 3), Duration::from_secs(8));
+    assert_eq!(Webhook::backoff(interval, 9), MAX_BACKOFF);
+    assert_eq!(Webhook::backoff(interval, u32::MAX), MAX_BACKOFF);
+  }
+}
@@ -18,6 +18,7 @@ pub struct Output {
   pub period: u32,
   pub offset: u64,
   pub rarity: Rarity,
+  pub satributes: Vec<Satribute>,
 }
 
 impl Traits {
@@ -33,6 +34,7 @@ impl Traits {
       period: self.sat.period(),
       offset: self.sat.third(),
       rarity: self.sat.rarity(),
+      satributes: self.sat.satributes(),
     })))
   }
 }
@@ -7,6 +7,12 @@ pub(crate) struct Sats {
     help = "Find satoshis listed in first column of tab-separated value file <TSV>."
   )]
   tsv: Option<PathBuf>,
+  #[arg(
+    long,
+    conflicts_with = "tsv",
+    help = "List sat ranges with satributes such as vintage or palindrome."
+  )]
+  satributes: bool,
 }
 
 #[derive(Serialize, Deserialize)]
@@ -23,6 +29,15 @@ pub struct OutputRare {
   pub rarity: Rarity,
 }
 
+#[derive(Serialize, Deserialize)]
+pub struct OutputSatributes {
+  pub output: OutPoint,
+  pub offset: u64,
+  pub start: u64,
+  pub end: u64,
+  pub satributes: Vec<Satribute>,
+}
+
 impl Sats {
   pub(crate) fn run(&self, wallet: Wallet) -> SubcommandResult {
     ensure!(
@@ -47,6 +62,23 @@ impl Sats {
         .collect();
 
       Ok(Some(Box::new(OutputTsv { found, lost })))
+    } else if self.satributes {
+      let classifier = wallet.get_satribute_classifier()?;
+
+      let output = Self::satribute_ranges(haystacks, &classifier)
+        .into_iter()
+        .map(
+          |(output, offset, start, end, satributes)| OutputSatributes {
+            output,
+            offset,
+            start,
+            end,
+            satributes,
+          },
+        )
+        .collect::<Vec<OutputSatributes>>();
+
+      Ok(Some(Box::new(output)))
     } else {
       let mut output = Vec::new();
       for (outpoint, sat, offset, rarity) in Self::rare_sats(haystacks) {
@@ -126,6 +158,28 @@ impl Sats {
     Ok(needles)
   }
 
+  fn satribute_ranges(
+    haystacks: Vec<(OutPoint, Vec<(u64, u64)>)>,
+    classifier: &Classifier,
+  ) -> Vec<(OutPoint, u64, u64, u64, Vec<Satribute>)> {
+    haystacks
+      .into_iter()
+      .flat_map(|(outpoint, sat_ranges)| {
+        let mut offset = 0;
+        sat_ranges.into_iter().filter_map(move |(start, end)| {
+          let satributes = classifier.classify_range(start, end);
+          let start_offset = offset;
+          offset += end - start;
+          if satributes.is_empty() {
+            None
+          } else {
+            Some((outpoint, start_offset, start, end, satributes))
+          }
+        })
+      })
+      .collect()
+  }
+
   fn rare_sats(haystacks: Vec<(OutPoint, Vec<(u64, u64)>)>) -> Vec<(OutPoint, Sat, u64, Rarity)> {
     haystacks
       .into_iter()
@@ -201,6 +255,41 @@ mod tests {
     )
   }
 
+  #[test]
+  fn identify_satributes() {
+    let mut classifier = Classifier::default();
+
+    classifier.add_range(Satribute::Pizza, 200, 300);
+
+    let modern = Height(1001).starting_sat().n();
+
+    assert_eq!(
+      Sats::satribute_ranges(
+        vec![
+          (outpoint(1), vec![(modern + 1, modern + 10), (260, 262)]),
+          (outpoint(2), vec![(0, 10)]),
+        ],
+        &classifier,
+      ),
+      vec![
+        (
+          outpoint(1),
+          9,
+          260,
+          262,
+          vec![Satribute::Pizza, Satribute::Vintage]
+        ),
+        (
+          outpoint(2),
+          0,
+          0,
+          10,
+          vec![Satribute::Alpha, Satribute::Palindrome, Satribute::Vintage]
+        ),
+      ]
+    )
+  }
+
   #[track_caller]
   fn case(tsv: &str, haystacks: &[(OutPoint, Vec<(u64, u64)>)], expected: &[(&str, SatPoint)]) {
     assert_eq!(
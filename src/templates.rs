@@ -2,18 +2,24 @@ use {super::*, boilerplate::Boilerplate};
 
 pub(crate) use {
   crate::subcommand::server::ServerConfig,
+  address::AddressHtml,
   block::BlockHtml,
   children::ChildrenHtml,
   clock::ClockSvg,
+  collection::CollectionHtml,
   collections::CollectionsHtml,
   home::HomeHtml,
   iframe::Iframe,
   input::InputHtml,
   inscription::InscriptionHtml,
+  inscription_history::InscriptionHistoryHtml,
+  inscription_sales::InscriptionSalesHtml,
   inscriptions::InscriptionsHtml,
   inscriptions_block::InscriptionsBlockHtml,
   metadata::MetadataHtml,
+  metaprotocols::MetaprotocolsHtml,
   output::OutputHtml,
+  paginated_inscriptions::{InscriptionFilter, PaginatedInscriptionsHtml},
   parents::ParentsHtml,
   preview::{
     PreviewAudioHtml, PreviewCodeHtml, PreviewFontHtml, PreviewImageHtml, PreviewMarkdownHtml,
@@ -22,6 +28,7 @@ pub(crate) use {
   range::RangeHtml,
   rare::RareTxt,
   sat::SatHtml,
+  satribute::SatributeHtml,
 };
 
 pub use {
@@ -29,19 +36,25 @@ pub use {
   transaction::TransactionHtml,
 };
 
+mod address;
 pub mod block;
 pub mod blocks;
 mod children;
 mod clock;
+mod collection;
 pub mod collections;
 mod home;
 mod iframe;
 mod input;
 pub mod inscription;
+mod inscription_history;
+mod inscription_sales;
 pub mod inscriptions;
 mod inscriptions_block;
 mod metadata;
+mod metaprotocols;
 pub mod output;
+mod paginated_inscriptions;
 mod parents;
 mod preview;
 mod range;
@@ -49,6 +62,7 @@ mod rare;
 pub mod rune;
 pub mod runes;
 pub mod sat;
+mod satribute;
 pub mod status;
 pub mod transaction;
 
@@ -0,0 +1,110 @@
+use super::*;
+
+#[derive(Boilerplate)]
+pub(crate) struct AddressHtml {
+  pub(crate) address: Address,
+  pub(crate) cardinal_sat_balance: u64,
+  pub(crate) inscriptions: Vec<InscriptionId>,
+  pub(crate) outputs: Vec<OutPoint>,
+  pub(crate) runes: BTreeMap<SpacedRune, Pile>,
+  pub(crate) sat_balance: u64,
+}
+
+impl PageContent for AddressHtml {
+  fn title(&self) -> String {
+    format!("Address {}", self.address)
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  fn address() -> Address {
+    "bc1qw508d6qejxtdg4y5r3zarvary0c5xw7kv8f3t4"
+      .parse::<Address<NetworkUnchecked>>()
+      .unwrap()
+      .assume_checked()
+  }
+
+  #[test]
+  fn empty() {
+    assert_regex_match!(
+      AddressHtml {
+        address: address(),
+        cardinal_sat_balance: 0,
+        inscriptions: Vec::new(),
+        outputs: Vec::new(),
+        runes: BTreeMap::new(),
+        sat_balance: 0,
+      },
+      "
+        <h1>Address <span class=monospace>bc1qw508d6qejxtdg4y5r3zarvary0c5xw7kv8f3t4</span></h1>
+        <dl>
+          <dt>sat balance</dt><dd>0</dd>
+          <dt>cardinal sat balance</dt><dd>0</dd>
+        </dl>
+        <h2>0 Outputs</h2>
+        <ul class=monospace>
+        </ul>
+      "
+      .unindent()
+    );
+  }
+
+  #[test]
+  fn with_inscriptions_and_runes() {
+    assert_regex_match!(
+      AddressHtml {
+        address: address(),
+        cardinal_sat_balance: 1,
+        inscriptions: vec![inscription_id(1)],
+        outputs: vec![outpoint(1), outpoint(2)],
+        runes: [(
+          SpacedRune {
+            rune: Rune(0),
+            spacers: 0,
+          },
+          Pile {
+            amount: 11,
+            divisibility: 1,
+            symbol: Some('%'),
+          }
+        )]
+        .into_iter()
+        .collect(),
+        sat_balance: 3,
+      },
+      "
+        <h1>Address <span class=monospace>bc1qw508d6qejxtdg4y5r3zarvary0c5xw7kv8f3t4</span></h1>
+        <dl>
+          <dt>sat balance</dt><dd>3</dd>
+          <dt>cardinal sat balance</dt><dd>1</dd>
+          <dt>inscriptions</dt>
+          <dd class=thumbnails>
+            <a href=/inscription/1{64}i1><iframe .* src=/preview/1{64}i1></iframe></a>
+          </dd>
+          <dt>runes</dt>
+          <dd>
+            <table>
+              <tr>
+                <th>rune</th>
+                <th>balance</th>
+              </tr>
+              <tr>
+                <td><a href=/rune/A>A</a></td>
+                <td>1.1\u{A0}%</td>
+              </tr>
+            </table>
+          </dd>
+        </dl>
+        <h2>2 Outputs</h2>
+        <ul class=monospace>
+          <li><a href=/output/1{64}:1>1{64}:1</a></li>
+          <li><a href=/output/2{64}:2>2{64}:2</a></li>
+        </ul>
+      "
+      .unindent()
+    );
+  }
+}
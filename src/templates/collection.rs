@@ -0,0 +1,107 @@
+use super::*;
+
+#[derive(Boilerplate)]
+pub(crate) struct CollectionHtml {
+  pub(crate) collection: api::Collection,
+}
+
+impl PageContent for CollectionHtml {
+  fn title(&self) -> String {
+    format!("Inscription {} Collection", self.collection.number)
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  #[test]
+  fn collection() {
+    assert_regex_match!(
+      CollectionHtml {
+        collection: api::Collection {
+          children: 3,
+          content_types: vec![(Some("text/plain".into()), 2), (None, 1)],
+          first_child_height: Some(5),
+          grandchildren: 1,
+          holders: 2,
+          id: inscription_id(1),
+          last_child_height: Some(6),
+          mints_per_block: vec![(5, 1), (6, 2)],
+          number: 0,
+        },
+      },
+      "
+        <h1><a href=/inscription/1{64}i1>Inscription 0</a> Collection</h1>
+        <dl>
+          <dt>children</dt>
+          <dd><a href=/children/1{64}i1>3</a></dd>
+          <dt>holders</dt>
+          <dd>2</dd>
+          <dt>grandchildren</dt>
+          <dd>1</dd>
+          <dt>first child height</dt>
+          <dd><a href=/block/5>5</a></dd>
+          <dt>last child height</dt>
+          <dd><a href=/block/6>6</a></dd>
+          <dt>content types</dt>
+          <dd>
+            <dl>
+              <dt>text/plain</dt>
+              <dd>2</dd>
+              <dt><em>none</em></dt>
+              <dd>1</dd>
+            </dl>
+          </dd>
+        </dl>
+        <h2>Mints per Block</h2>
+        <table>
+          <tr>
+            <th>height</th>
+            <th>mints</th>
+          </tr>
+          <tr>
+            <td><a href=/block/5>5</a></td>
+            <td>1</td>
+          </tr>
+          <tr>
+            <td><a href=/block/6>6</a></td>
+            <td>2</td>
+          </tr>
+        </table>
+      "
+      .unindent()
+    );
+  }
+
+  #[test]
+  fn empty_collection() {
+    assert_regex_match!(
+      CollectionHtml {
+        collection: api::Collection {
+          children: 0,
+          content_types: Vec::new(),
+          first_child_height: None,
+          grandchildren: 0,
+          holders: 0,
+          id: inscription_id(1),
+          last_child_height: None,
+          mints_per_block: Vec::new(),
+          number: 0,
+        },
+      },
+      "
+        <h1><a href=/inscription/1{64}i1>Inscription 0</a> Collection</h1>
+        <dl>
+          <dt>children</dt>
+          <dd><a href=/children/1{64}i1>0</a></dd>
+          <dt>holders</dt>
+          <dd>0</dd>
+          <dt>grandchildren</dt>
+          <dd>0</dd>
+        </dl>
+      "
+      .unindent()
+    );
+  }
+}
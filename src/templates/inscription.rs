@@ -5,14 +5,17 @@ pub(crate) struct InscriptionHtml {
   pub(crate) chain: Chain,
   pub(crate) charms: u16,
   pub(crate) children: Vec<InscriptionId>,
+  pub(crate) delegator_count: u64,
   pub(crate) fee: u64,
   pub(crate) height: u32,
   pub(crate) inscription: Inscription,
   pub(crate) id: InscriptionId,
   pub(crate) number: i32,
   pub(crate) next: Option<InscriptionId>,
+  pub(crate) original: Option<InscriptionId>,
   pub(crate) output: Option<TxOut>,
   pub(crate) parents: Vec<InscriptionId>,
+  pub(crate) pending: Option<Txid>,
   pub(crate) previous: Option<InscriptionId>,
   pub(crate) rune: Option<SpacedRune>,
   pub(crate) sat: Option<Sat>,
@@ -57,6 +60,8 @@ mod tests {
           <dd><a href=/content/1{64}i1>link</a></dd>
           <dt>content length</dt>
           <dd>10 bytes</dd>
+          <dt>body hash</dt>
+          <dd><a class=monospace href=/inscriptions/body-hash/0b21b7db59cd154904fac6336fa7d2be1bab38d632794f281549584068cdcb74>0b21b7db59cd154904fac6336fa7d2be1bab38d632794f281549584068cdcb74</a></dd>
           <dt>content type</dt>
           <dd>text/plain;charset=utf-8</dd>
           <dt>timestamp</dt>
@@ -141,6 +146,57 @@ mod tests {
     );
   }
 
+  #[test]
+  fn with_delegators() {
+    assert_regex_match!(
+      InscriptionHtml {
+        delegator_count: 2,
+        fee: 1,
+        inscription: inscription("text/plain;charset=utf-8", "HELLOWORLD"),
+        id: inscription_id(1),
+        number: 1,
+        satpoint: satpoint(1, 0),
+        ..default()
+      },
+      "
+        .*
+        <dl>
+          <dt>delegators</dt>
+          <dd><a href=/inscription/1{64}i1/delegators>2</a></dd>
+          <dt>id</dt>
+        .*
+      "
+      .unindent()
+    );
+  }
+
+  #[test]
+  fn with_original() {
+    assert_regex_match!(
+      InscriptionHtml {
+        fee: 1,
+        inscription: inscription("text/plain;charset=utf-8", "HELLOWORLD"),
+        id: inscription_id(2),
+        number: 1,
+        original: Some(inscription_id(1)),
+        satpoint: satpoint(1, 0),
+        ..default()
+      },
+      "
+        .*
+          <dt>content length</dt>
+          <dd>10 bytes</dd>
+          <dt>body hash</dt>
+          <dd><a class=monospace href=/inscriptions/body-hash/0b21b7db59cd154904fac6336fa7d2be1bab38d632794f281549584068cdcb74>0b21b7db59cd154904fac6336fa7d2be1bab38d632794f281549584068cdcb74</a></dd>
+          <dt>original</dt>
+          <dd><a class=monospace href=/inscription/1{64}i1>1{64}i1</a></dd>
+          <dt>content type</dt>
+        .*
+      "
+      .unindent()
+    );
+  }
+
   #[test]
   fn with_prev_and_next() {
     assert_regex_match!(
@@ -238,6 +294,8 @@ mod tests {
           <dd><a href=/content/1{64}i1>link</a></dd>
           <dt>content length</dt>
           <dd>10 bytes</dd>
+          <dt>body hash</dt>
+          <dd><a class=monospace href=/inscriptions/body-hash/0b21b7db59cd154904fac6336fa7d2be1bab38d632794f281549584068cdcb74>0b21b7db59cd154904fac6336fa7d2be1bab38d632794f281549584068cdcb74</a></dd>
           <dt>content type</dt>
           <dd>text/plain;charset=utf-8</dd>
           <dt>timestamp</dt>
@@ -300,6 +358,8 @@ mod tests {
           <dd><a href=/content/1{64}i1>link</a></dd>
           <dt>content length</dt>
           <dd>10 bytes</dd>
+          <dt>body hash</dt>
+          <dd><a class=monospace href=/inscriptions/body-hash/0b21b7db59cd154904fac6336fa7d2be1bab38d632794f281549584068cdcb74>0b21b7db59cd154904fac6336fa7d2be1bab38d632794f281549584068cdcb74</a></dd>
           <dt>content type</dt>
           <dd>text/plain;charset=utf-8</dd>
           <dt>timestamp</dt>
@@ -361,6 +421,8 @@ mod tests {
           <dd><a href=/content/1{64}i1>link</a></dd>
           <dt>content length</dt>
           <dd>10 bytes</dd>
+          <dt>body hash</dt>
+          <dd><a class=monospace href=/inscriptions/body-hash/0b21b7db59cd154904fac6336fa7d2be1bab38d632794f281549584068cdcb74>0b21b7db59cd154904fac6336fa7d2be1bab38d632794f281549584068cdcb74</a></dd>
           <dt>content type</dt>
           <dd>text/plain;charset=utf-8</dd>
           <dt>timestamp</dt>
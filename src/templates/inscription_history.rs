@@ -0,0 +1,100 @@
+use super::*;
+
+#[derive(Boilerplate)]
+pub(crate) struct InscriptionHistoryHtml {
+  pub(crate) id: InscriptionId,
+  pub(crate) index_addresses: bool,
+  pub(crate) number: i32,
+  pub(crate) transfers: Vec<api::InscriptionTransfer>,
+}
+
+impl PageContent for InscriptionHistoryHtml {
+  fn title(&self) -> String {
+    format!("Inscription {} History", self.number)
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  fn transfers() -> Vec<api::InscriptionTransfer> {
+    let address = "bc1qw508d6qejxtdg4y5r3zarvary0c5xw7kv8f3t4"
+      .parse::<Address<NetworkUnchecked>>()
+      .unwrap();
+
+    vec![
+      api::InscriptionTransfer {
+        from: None,
+        height: 2,
+        new_satpoint: satpoint(1, 0),
+        old_satpoint: None,
+        to: Some(address.clone()),
+        txid: txid(1),
+      },
+      api::InscriptionTransfer {
+        from: Some(address),
+        height: 3,
+        new_satpoint: satpoint(2, 0),
+        old_satpoint: Some(satpoint(1, 0)),
+        to: None,
+        txid: txid(2),
+      },
+    ]
+  }
+
+  #[test]
+  fn history() {
+    assert_regex_match!(
+      InscriptionHistoryHtml {
+        id: inscription_id(1),
+        index_addresses: true,
+        number: 0,
+        transfers: transfers(),
+      },
+      "
+        <h1><a href=/inscription/1{64}i1>Inscription 0</a> History</h1>
+        <table>
+          <tr>
+            <th>height</th>
+            <th>transaction</th>
+            <th>from</th>
+            <th>to</th>
+            <th>location</th>
+          </tr>
+          <tr>
+            <td><a href=/block/2>2</a></td>
+            <td><a class=monospace href=/tx/1{64}>1{64}</a></td>
+            <td>created</td>
+            <td><a class=monospace href=/address/bc1qw508d6qejxtdg4y5r3zarvary0c5xw7kv8f3t4>bc1qw508d6qejxtdg4y5r3zarvary0c5xw7kv8f3t4</a></td>
+            <td><a class=monospace href=/output/1{64}:1>1{64}:1:0</a></td>
+          </tr>
+          <tr>
+            <td><a href=/block/3>3</a></td>
+            <td><a class=monospace href=/tx/2{64}>2{64}</a></td>
+            <td><a class=monospace href=/address/bc1qw508d6qejxtdg4y5r3zarvary0c5xw7kv8f3t4>bc1qw508d6qejxtdg4y5r3zarvary0c5xw7kv8f3t4</a></td>
+            <td>none</td>
+            <td><a class=monospace href=/output/2{64}:2>2{64}:2:0</a></td>
+          </tr>
+        </table>
+      "
+      .unindent()
+    );
+  }
+
+  #[test]
+  fn addresses_are_not_linked_without_address_index() {
+    assert_regex_match!(
+      InscriptionHistoryHtml {
+        id: inscription_id(1),
+        index_addresses: false,
+        number: 0,
+        transfers: transfers(),
+      },
+      ".*<td>created</td>
+    <td class=monospace>bc1qw508d6qejxtdg4y5r3zarvary0c5xw7kv8f3t4</td>.*
+    <td class=monospace>bc1qw508d6qejxtdg4y5r3zarvary0c5xw7kv8f3t4</td>
+    <td>none</td>.*"
+    );
+  }
+}
@@ -0,0 +1,96 @@
+use super::*;
+
+#[derive(Boilerplate)]
+pub(crate) struct InscriptionSalesHtml {
+  pub(crate) id: InscriptionId,
+  pub(crate) index_addresses: bool,
+  pub(crate) number: i32,
+  pub(crate) sales: Vec<api::Sale>,
+}
+
+impl PageContent for InscriptionSalesHtml {
+  fn title(&self) -> String {
+    format!("Inscription {} Sales", self.number)
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  fn sale() -> api::Sale {
+    api::Sale {
+      buyer: Some(
+        "bc1qw508d6qejxtdg4y5r3zarvary0c5xw7kv8f3t4"
+          .parse()
+          .unwrap(),
+      ),
+      height: 3,
+      price: 1000,
+      seller: None,
+      txid: txid(1),
+    }
+  }
+
+  #[test]
+  fn sales() {
+    assert_regex_match!(
+      InscriptionSalesHtml {
+        id: inscription_id(1),
+        index_addresses: false,
+        number: 0,
+        sales: vec![sale()],
+      },
+      "
+        <h1><a href=/inscription/1{64}i1>Inscription 0</a> Sales</h1>
+        <table>
+          <tr>
+            <th>height</th>
+            <th>transaction</th>
+            <th>price</th>
+            <th>seller</th>
+            <th>buyer</th>
+          </tr>
+          <tr>
+            <td><a href=/block/3>3</a></td>
+            <td><a class=monospace href=/tx/1{64}>1{64}</a></td>
+            <td>1000</td>
+            <td>none</td>
+            <td class=monospace>bc1qw508d6qejxtdg4y5r3zarvary0c5xw7kv8f3t4</td>
+          </tr>
+        </table>
+      "
+      .unindent()
+    );
+  }
+
+  #[test]
+  fn addresses_are_linked_when_indexed() {
+    assert_regex_match!(
+      InscriptionSalesHtml {
+        id: inscription_id(1),
+        index_addresses: true,
+        number: 0,
+        sales: vec![sale()],
+      },
+      ".*<td><a class=monospace href=/address/bc1qw508d6qejxtdg4y5r3zarvary0c5xw7kv8f3t4>bc1qw508d6qejxtdg4y5r3zarvary0c5xw7kv8f3t4</a></td>.*"
+    );
+  }
+
+  #[test]
+  fn no_sales() {
+    assert_regex_match!(
+      InscriptionSalesHtml {
+        id: inscription_id(1),
+        index_addresses: false,
+        number: 0,
+        sales: Vec::new(),
+      },
+      "
+        <h1><a href=/inscription/1{64}i1>Inscription 0</a> Sales</h1>
+        <h3>No sales</h3>
+      "
+      .unindent()
+    );
+  }
+}
@@ -5,6 +5,7 @@ pub(crate) struct InscriptionsHtml {
   pub(crate) inscriptions: Vec<InscriptionId>,
   pub(crate) prev: Option<u32>,
   pub(crate) next: Option<u32>,
+  pub(crate) query: String,
 }
 
 impl PageContent for InscriptionsHtml {
@@ -24,6 +25,7 @@ mod tests {
         inscriptions: vec![inscription_id(1), inscription_id(2)],
         prev: None,
         next: None,
+        query: String::new(),
       },
       "
         <h1>All Inscriptions</h1>
@@ -47,6 +49,7 @@ mod tests {
         inscriptions: vec![inscription_id(1), inscription_id(2)],
         prev: Some(1),
         next: Some(2),
+        query: String::new(),
       },
       "
         <h1>All Inscriptions</h1>
@@ -62,4 +65,24 @@ mod tests {
       .unindent()
     );
   }
+
+  #[test]
+  fn with_query() {
+    assert_regex_match!(
+      InscriptionsHtml {
+        inscriptions: vec![inscription_id(1)],
+        prev: Some(1),
+        next: Some(2),
+        query: "?content_type=image%2Fpng&order=oldest".into(),
+      },
+      "
+        <h1>All Inscriptions</h1>
+        .*
+        <a class=prev href=/inscriptions/1\\?content_type=image%2Fpng&amp;order=oldest>prev</a>
+        <a class=next href=/inscriptions/2\\?content_type=image%2Fpng&amp;order=oldest>next</a>
+        .*
+      "
+      .unindent()
+    );
+  }
 }
@@ -0,0 +1,58 @@
+use super::*;
+
+#[derive(Boilerplate)]
+pub(crate) struct MetaprotocolsHtml {
+  pub(crate) metaprotocols: Vec<(String, u64)>,
+}
+
+impl PageContent for MetaprotocolsHtml {
+  fn title(&self) -> String {
+    "Metaprotocols".to_string()
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  #[test]
+  fn metaprotocols() {
+    assert_regex_match!(
+      MetaprotocolsHtml {
+        metaprotocols: vec![("brc-20".into(), 2), ("foo bar".into(), 1)],
+      },
+      "
+        <h1>Metaprotocols</h1>
+        <table>
+          <tr>
+            <th>metaprotocol</th>
+            <th>inscriptions</th>
+          </tr>
+          <tr>
+            <td><a href=/inscriptions/metaprotocol/brc-20>brc-20</a></td>
+            <td>2</td>
+          </tr>
+          <tr>
+            <td><a href=/inscriptions/metaprotocol/foo%20bar>foo bar</a></td>
+            <td>1</td>
+          </tr>
+        </table>
+      "
+      .unindent()
+    );
+  }
+
+  #[test]
+  fn empty() {
+    assert_regex_match!(
+      MetaprotocolsHtml {
+        metaprotocols: Vec::new(),
+      },
+      "
+        <h1>Metaprotocols</h1>
+        <h3>No metaprotocols</h3>
+      "
+      .unindent()
+    );
+  }
+}
@@ -6,6 +6,7 @@ pub(crate) struct OutputHtml {
   pub(crate) inscriptions: Vec<InscriptionId>,
   pub(crate) outpoint: OutPoint,
   pub(crate) output: TxOut,
+  pub(crate) pending: Option<Txid>,
   pub(crate) runes: BTreeMap<SpacedRune, Pile>,
   pub(crate) sat_ranges: Option<Vec<(u64, u64)>>,
   pub(crate) spent: bool,
@@ -32,6 +33,7 @@ mod tests {
         inscriptions: Vec::new(),
         outpoint: outpoint(1),
         output: TxOut { value: 3, script_pubkey: ScriptBuf::new_p2pkh(&PubkeyHash::all_zeros()), },
+        pending: None,
         runes: BTreeMap::new(),
         sat_ranges: Some(vec![(0, 1), (1, 3)]),
         spent: false,
@@ -66,6 +68,7 @@ mod tests {
           value: 1,
           script_pubkey: script::Builder::new().push_int(0).into_script(),
         },
+        pending: None,
         runes: BTreeMap::new(),
         sat_ranges: None,
         spent: true,
@@ -83,6 +86,36 @@ mod tests {
     );
   }
 
+  #[test]
+  fn pending_output() {
+    assert_regex_match!(
+      OutputHtml {
+        chain: Chain::Mainnet,
+        inscriptions: Vec::new(),
+        outpoint: outpoint(1),
+        output: TxOut {
+          value: 1,
+          script_pubkey: script::Builder::new().push_int(0).into_script(),
+        },
+        pending: Some(txid(2)),
+        runes: BTreeMap::new(),
+        sat_ranges: None,
+        spent: false,
+      },
+      "
+        <h1>Output <span class=monospace>1{64}:1</span></h1>
+        <dl>
+          <dt>value</dt><dd>1</dd>
+          <dt>script pubkey</dt><dd class=monospace>OP_0</dd>
+          <dt>transaction</dt><dd><a class=monospace href=/tx/1{64}>1{64}</a></dd>
+          <dt>spent</dt><dd>false</dd>
+          <dt>pending</dt><dd><a class=monospace href=/tx/2{64}>2{64}</a></dd>
+        </dl>
+      "
+      .unindent()
+    );
+  }
+
   #[test]
   fn spent_output_with_ranges() {
     assert_regex_match!(
@@ -91,6 +124,7 @@ mod tests {
         inscriptions: Vec::new(),
         outpoint: outpoint(1),
         output: TxOut { value: 3, script_pubkey: ScriptBuf::new_p2pkh(&PubkeyHash::all_zeros()), },
+        pending: None,
         runes: BTreeMap::new(),
         sat_ranges: Some(vec![(0, 1), (1, 3)]),
         spent: true,
@@ -122,6 +156,7 @@ mod tests {
         inscriptions: Vec::new(),
         outpoint: outpoint(1),
         output: TxOut { value: 3, script_pubkey: ScriptBuf::new_p2pkh(&PubkeyHash::all_zeros()), },
+        pending: None,
         runes: BTreeMap::new(),
         sat_ranges: None,
         spent: false,
@@ -152,6 +187,7 @@ mod tests {
           value: 3,
           script_pubkey: ScriptBuf::new_p2pkh(&PubkeyHash::all_zeros()),
         },
+        pending: None,
         runes: BTreeMap::new(),
         sat_ranges: None,
         spent: false,
@@ -181,6 +217,7 @@ mod tests {
           value: 3,
           script_pubkey: ScriptBuf::new_p2pkh(&PubkeyHash::all_zeros()),
         },
+        pending: None,
         runes: vec![(
           SpacedRune {
             rune: Rune(26),
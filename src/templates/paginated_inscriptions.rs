@@ -0,0 +1,162 @@
+use super::*;
+
+pub(crate) enum InscriptionFilter {
+  BodyHash(sha256::Hash),
+  ContentType(String),
+  Delegate { id: InscriptionId, number: i32 },
+  Metadata { key: String, value: String },
+  Metaprotocol(String),
+}
+
+impl InscriptionFilter {
+  fn page_path(&self, page: u32) -> String {
+    match self {
+      Self::BodyHash(body_hash) => format!("/inscriptions/body-hash/{body_hash}/{page}"),
+      Self::ContentType(content_type) => format!(
+        "/inscriptions/content-type/{}/{page}",
+        urlencoding::encode(content_type)
+      ),
+      Self::Delegate { id, .. } => format!("/inscription/{id}/delegators/{page}"),
+      Self::Metadata { key, value } => format!(
+        "/inscriptions/metadata/{page}?key={}&value={}",
+        urlencoding::encode(key),
+        urlencoding::encode(value)
+      ),
+      Self::Metaprotocol(metaprotocol) => format!(
+        "/inscriptions/metaprotocol/{}/{page}",
+        urlencoding::encode(metaprotocol)
+      ),
+    }
+  }
+}
+
+#[derive(Boilerplate)]
+pub(crate) struct PaginatedInscriptionsHtml {
+  pub(crate) filter: InscriptionFilter,
+  pub(crate) inscriptions: Vec<InscriptionId>,
+  pub(crate) next_page: Option<u32>,
+  pub(crate) prev_page: Option<u32>,
+}
+
+impl PageContent for PaginatedInscriptionsHtml {
+  fn title(&self) -> String {
+    match &self.filter {
+      InscriptionFilter::BodyHash(body_hash) => format!("Body Hash {body_hash}"),
+      InscriptionFilter::ContentType(content_type) => format!("Content Type {content_type}"),
+      InscriptionFilter::Delegate { number, .. } => format!("Inscription {number} Delegators"),
+      InscriptionFilter::Metadata { key, value } => format!("Metadata {key} = {value}"),
+      InscriptionFilter::Metaprotocol(metaprotocol) => format!("Metaprotocol {metaprotocol}"),
+    }
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  #[test]
+  fn without_prev_and_next() {
+    assert_regex_match!(
+      PaginatedInscriptionsHtml {
+        filter: InscriptionFilter::Metaprotocol("brc-20".into()),
+        inscriptions: vec![inscription_id(1), inscription_id(2)],
+        next_page: None,
+        prev_page: None,
+      },
+      "
+        <h1>Metaprotocol brc-20</h1>
+        <div class=thumbnails>
+          <a href=/inscription/1{64}i1><iframe .* src=/preview/1{64}i1></iframe></a>
+          <a href=/inscription/2{64}i2><iframe .* src=/preview/2{64}i2></iframe></a>
+        </div>
+        .*
+        prev
+        next
+        .*
+      "
+      .unindent()
+    );
+  }
+
+  #[test]
+  fn empty() {
+    assert_regex_match!(
+      PaginatedInscriptionsHtml {
+        filter: InscriptionFilter::Metaprotocol("brc-20".into()),
+        inscriptions: Vec::new(),
+        next_page: None,
+        prev_page: None,
+      },
+      "
+        <h1>Metaprotocol brc-20</h1>
+        <h3>No inscriptions</h3>
+      "
+      .unindent()
+    );
+  }
+
+  #[test]
+  fn with_prev_and_next() {
+    for (filter, heading, path) in [
+      (
+        InscriptionFilter::BodyHash(sha256::Hash::all_zeros()),
+        "<h1>Body Hash <span class=monospace>0{64}</span></h1>",
+        "/inscriptions/body-hash/0{64}",
+      ),
+      (
+        InscriptionFilter::ContentType("text/plain".into()),
+        "<h1>Content Type text/plain</h1>",
+        "/inscriptions/content-type/text%2Fplain",
+      ),
+      (
+        InscriptionFilter::Delegate {
+          id: inscription_id(1),
+          number: 0,
+        },
+        "<h1><a href=/inscription/1{64}i1>Inscription 0</a> Delegators</h1>",
+        "/inscription/1{64}i1/delegators",
+      ),
+      (
+        InscriptionFilter::Metaprotocol("foo bar".into()),
+        "<h1>Metaprotocol foo bar</h1>",
+        "/inscriptions/metaprotocol/foo%20bar",
+      ),
+    ] {
+      assert_regex_match!(
+        PaginatedInscriptionsHtml {
+          filter,
+          inscriptions: vec![inscription_id(2)],
+          next_page: Some(3),
+          prev_page: Some(1),
+        },
+        format!(
+          "{heading}
+.*
+  <a class=prev href={path}/1>prev</a>
+  <a class=next href={path}/3>next</a>
+.*"
+        )
+      );
+    }
+  }
+
+  #[test]
+  fn metadata_pages_carry_query() {
+    assert_regex_match!(
+      PaginatedInscriptionsHtml {
+        filter: InscriptionFilter::Metadata {
+          key: "name".into(),
+          value: "foo bar".into(),
+        },
+        inscriptions: vec![inscription_id(2)],
+        next_page: Some(3),
+        prev_page: Some(1),
+      },
+      "<h1>Metadata name = foo bar</h1>
+.*
+  <a class=prev href=/inscriptions/metadata/1\\?key=name&amp;value=foo%20bar>prev</a>
+  <a class=next href=/inscriptions/metadata/3\\?key=name&amp;value=foo%20bar>next</a>
+.*"
+    );
+  }
+}
@@ -3,6 +3,7 @@ use super::*;
 #[derive(Boilerplate, Debug, PartialEq, Serialize, Deserialize)]
 pub struct RuneHtml {
   pub entry: RuneEntry,
+  pub holders: u64,
   pub id: RuneId,
   pub mintable: bool,
   pub parent: Option<InscriptionId>,
@@ -44,6 +45,7 @@ mod tests {
           timestamp: 0,
           turbo: true,
         },
+        holders: 3,
         id: RuneId { block: 10, tx: 9 },
         mintable: true,
         parent: Some(InscriptionId {
@@ -91,6 +93,8 @@ mod tests {
   <dd>0.12%</dd>
   <dt>burned</dt>
   <dd>123456789.123456789\u{A0}%</dd>
+  <dt>holders</dt>
+  <dd>3</dd>
   <dt>divisibility</dt>
   <dd>9</dd>
   <dt>symbol</dt>
@@ -127,6 +131,7 @@ mod tests {
           timestamp: 0,
           turbo: false,
         },
+        holders: 3,
         id: RuneId { block: 10, tx: 9 },
         mintable: false,
         parent: None,
@@ -161,6 +166,7 @@ mod tests {
           timestamp: 0,
           turbo: false,
         },
+        holders: 3,
         id: RuneId { block: 10, tx: 9 },
         mintable: false,
         parent: None,
@@ -200,6 +206,7 @@ mod tests {
           timestamp: 0,
           turbo: false,
         },
+        holders: 3,
         id: RuneId { block: 10, tx: 9 },
         mintable: false,
         parent: None,
@@ -6,6 +6,7 @@ pub(crate) struct SatHtml {
   pub(crate) inscriptions: Vec<InscriptionId>,
   pub(crate) sat: Sat,
   pub(crate) satpoint: Option<SatPoint>,
+  pub(crate) satributes: Vec<Satribute>,
 }
 
 impl PageContent for SatHtml {
@@ -24,6 +25,7 @@ mod tests {
       SatHtml {
         sat: Sat(0),
         satpoint: None,
+        satributes: vec![Satribute::Alpha, Satribute::Palindrome, Satribute::Vintage],
         blocktime: Blocktime::confirmed(0),
         inscriptions: Vec::new(),
       },
@@ -46,6 +48,12 @@ mod tests {
             <span title=coin>🪙</span>
             <span title=mythic>🎃</span>
           </dd>
+          <dt>satributes</dt>
+          <dd>
+            <a href=/satribute/alpha>alpha</a>
+            <a href=/satribute/palindrome>palindrome</a>
+            <a href=/satribute/vintage>vintage</a>
+          </dd>
         </dl>
         .*
         prev
@@ -62,6 +70,7 @@ mod tests {
       SatHtml {
         sat: Sat(2099999997689999),
         satpoint: None,
+        satributes: Vec::new(),
         blocktime: Blocktime::confirmed(0),
         inscriptions: Vec::new(),
       },
@@ -99,6 +108,7 @@ mod tests {
       SatHtml {
         sat: Sat(1),
         satpoint: None,
+        satributes: Vec::new(),
         blocktime: Blocktime::confirmed(0),
         inscriptions: Vec::new(),
       },
@@ -112,6 +122,7 @@ mod tests {
       SatHtml {
         sat: Sat(0),
         satpoint: None,
+        satributes: Vec::new(),
         blocktime: Blocktime::confirmed(0),
         inscriptions: vec![inscription_id(1)],
       },
@@ -133,6 +144,7 @@ mod tests {
       SatHtml {
         sat: Sat(0),
         satpoint: None,
+        satributes: Vec::new(),
         blocktime: Blocktime::confirmed(0),
         inscriptions: vec![inscription_id(1), inscription_id(2)],
       },
@@ -155,6 +167,7 @@ mod tests {
       SatHtml {
         sat: Sat::LAST,
         satpoint: None,
+        satributes: Vec::new(),
         blocktime: Blocktime::confirmed(0),
         inscriptions: Vec::new(),
       },
@@ -168,6 +181,7 @@ mod tests {
       SatHtml {
         sat: Sat(0),
         satpoint: Some(satpoint(1, 0)),
+        satributes: Vec::new(),
         blocktime: Blocktime::confirmed(0),
         inscriptions: Vec::new(),
       },
@@ -0,0 +1,65 @@
+use super::*;
+
+#[derive(Boilerplate)]
+pub(crate) struct SatributeHtml {
+  pub(crate) satribute: Satribute,
+  pub(crate) outputs: Vec<OutPoint>,
+  pub(crate) prev: Option<usize>,
+  pub(crate) next: Option<usize>,
+}
+
+impl PageContent for SatributeHtml {
+  fn title(&self) -> String {
+    format!("Satribute {}", self.satribute)
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  #[test]
+  fn without_prev_and_next() {
+    assert_regex_match!(
+      SatributeHtml {
+        satribute: Satribute::Alpha,
+        outputs: vec![outpoint(1), outpoint(2)],
+        prev: None,
+        next: None,
+      },
+      "
+        <h1>Satribute alpha</h1>
+        <h2>2 Outputs</h2>
+        <ul class=monospace>
+          <li><a href=/output/1{64}:1>1{64}:1</a></li>
+          <li><a href=/output/2{64}:2>2{64}:2</a></li>
+        </ul>
+        <div class=center>
+        prev
+        next
+        </div>
+      "
+      .unindent()
+    );
+  }
+
+  #[test]
+  fn with_prev_and_next() {
+    assert_regex_match!(
+      SatributeHtml {
+        satribute: Satribute::Block9,
+        outputs: vec![outpoint(1)],
+        prev: Some(1),
+        next: Some(3),
+      },
+      "
+        <h1>Satribute block9</h1>
+        .*
+        <a class=prev href=/satribute/block9/1>prev</a>
+        <a class=next href=/satribute/block9/3>next</a>
+        .*
+      "
+      .unindent()
+    );
+  }
+}
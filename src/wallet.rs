@@ -263,6 +263,27 @@ impl Wallet {
     Ok(Some((rune_json.id, rune_json.entry, rune_json.parent)))
   }
 
+  pub(crate) fn get_satribute_classifier(&self) -> Result<Classifier> {
+    let response = self
+      .ord_client
+      .get(self.rpc_url.join("/satributes").unwrap())
+      .send()?;
+
+    if !response.status().is_success() {
+      bail!("failed to fetch satributes: {}", response.text()?);
+    }
+
+    let satributes: api::Satributes = serde_json::from_str(&response.text()?)?;
+
+    let mut classifier = Classifier::default();
+
+    for (satribute, start, end) in satributes.ranges {
+      classifier.add_range(satribute, start, end);
+    }
+
+    Ok(classifier)
+  }
+
   pub(crate) fn get_change_address(&self) -> Result<Address> {
     Ok(
       self
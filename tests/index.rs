@@ -90,3 +90,470 @@ fn export_inscription_number_to_id_tsv() {
     &ord::Object::InscriptionId(inscription),
   );
 }
+
+#[test]
+fn events_are_written_as_json_lines() {
+  let core = mockcore::spawn();
+  core.mine_blocks(1);
+
+  let output = CommandBuilder::new("index events")
+    .core(&core)
+    .stdout_regex(".*")
+    .run_and_extract_stdout();
+
+  let events = output
+    .lines()
+    .map(|line| serde_json::from_str(line).unwrap())
+    .collect::<Vec<serde_json::Value>>();
+
+  assert_eq!(events.len(), 4);
+  assert_eq!(events[0]["BlockStarted"]["block_height"], 0);
+  assert_eq!(events[1]["BlockStarted"]["block_height"], 1);
+  assert_eq!(events[2]["BlockCommitted"]["block_height"], 0);
+  assert_eq!(events[3]["BlockCommitted"]["block_height"], 1);
+}
+
+#[test]
+fn events_can_be_written_to_file() {
+  let core = mockcore::spawn();
+  core.mine_blocks(1);
+
+  let output = CommandBuilder::new("index events --output events.jsonl")
+    .core(&core)
+    .run_and_extract_file("events.jsonl");
+
+  assert_eq!(output.lines().count(), 4);
+}
+
+#[test]
+fn events_from_height_replays_event_log() {
+  let core = mockcore::spawn();
+  core.mine_blocks(2);
+
+  let tempdir = TempDir::new().unwrap();
+
+  let index_path = tempdir.path().join("foo.redb");
+
+  CommandBuilder::new(format!(
+    "--index {} --index-events index update",
+    index_path.display()
+  ))
+  .core(&core)
+  .run_and_extract_stdout();
+
+  core.mine_blocks(1);
+
+  let output = CommandBuilder::new(format!(
+    "--index {} --index-events index events --from-height 2",
+    index_path.display()
+  ))
+  .core(&core)
+  .stdout_regex(".*")
+  .run_and_extract_stdout();
+
+  let events = output
+    .lines()
+    .map(|line| serde_json::from_str(line).unwrap())
+    .collect::<Vec<serde_json::Value>>();
+
+  assert_eq!(events.len(), 4);
+  assert_eq!(events[0]["BlockStarted"]["block_height"], 2);
+  assert_eq!(events[1]["BlockCommitted"]["block_height"], 2);
+  assert_eq!(events[2]["BlockStarted"]["block_height"], 3);
+  assert_eq!(events[3]["BlockCommitted"]["block_height"], 3);
+}
+
+#[test]
+fn events_from_height_requires_event_log_to_replay() {
+  let core = mockcore::spawn();
+  core.mine_blocks(2);
+
+  let tempdir = TempDir::new().unwrap();
+
+  let index_path = tempdir.path().join("foo.redb");
+
+  CommandBuilder::new(format!("--index {} index update", index_path.display()))
+    .core(&core)
+    .run_and_extract_stdout();
+
+  CommandBuilder::new(format!(
+    "--index {} index events --from-height 1",
+    index_path.display()
+  ))
+  .core(&core)
+  .expected_stderr("error: replaying events requires index created with `--index-events` flag\n")
+  .expected_exit_code(1)
+  .run_and_extract_stdout();
+}
+
+#[cfg(unix)]
+#[test]
+fn events_can_be_written_to_unix_socket() {
+  use std::{io::Read, os::unix::net::UnixListener};
+
+  let core = mockcore::spawn();
+  core.mine_blocks(1);
+
+  let tempdir = TempDir::new().unwrap();
+
+  let socket = tempdir.path().join("events.sock");
+
+  let listener = UnixListener::bind(&socket).unwrap();
+
+  let reader = thread::spawn(move || {
+    let mut output = String::new();
+    listener
+      .accept()
+      .unwrap()
+      .0
+      .read_to_string(&mut output)
+      .unwrap();
+    output
+  });
+
+  CommandBuilder::new(format!("index events --socket {}", socket.display()))
+    .core(&core)
+    .run_and_extract_stdout();
+
+  assert_eq!(reader.join().unwrap().lines().count(), 4);
+}
+
+#[test]
+fn snapshot_can_be_restored() {
+  let core = mockcore::spawn();
+  core.mine_blocks(2);
+
+  let tempdir = TempDir::new().unwrap();
+
+  let index_path = tempdir.path().join("foo.redb");
+  let snapshot_path = tempdir.path().join("snapshot.redb");
+  let restored_path = tempdir.path().join("bar.redb");
+
+  CommandBuilder::new(format!("--index {} index update", index_path.display()))
+    .core(&core)
+    .run_and_extract_stdout();
+
+  let snapshot = CommandBuilder::new(format!(
+    "--index {} index snapshot {}",
+    index_path.display(),
+    snapshot_path.display(),
+  ))
+  .core(&core)
+  .run_and_deserialize_output::<ord::subcommand::index::snapshot::Output>();
+
+  assert_eq!(snapshot.height, 2);
+  assert_eq!(snapshot.path, snapshot_path);
+
+  let restore = CommandBuilder::new(format!(
+    "--index {} index restore {}",
+    restored_path.display(),
+    snapshot_path.display(),
+  ))
+  .core(&core)
+  .run_and_deserialize_output::<ord::subcommand::index::restore::Output>();
+
+  assert_eq!(restore.hash, snapshot.hash);
+  assert_eq!(restore.height, 2);
+  assert_eq!(restore.index, restored_path);
+
+  core.mine_blocks(1);
+
+  CommandBuilder::new(format!("--index {} index update", restored_path.display()))
+    .core(&core)
+    .run_and_extract_stdout();
+}
+
+#[test]
+fn snapshot_can_be_downloaded_from_server() {
+  let core = mockcore::spawn();
+
+  let ord = TestServer::spawn_with_server_args(&core, &[], &["--enable-snapshots"]);
+
+  core.mine_blocks(1);
+
+  ord.sync_server();
+
+  let tempdir = TempDir::new().unwrap();
+
+  let snapshot_path = tempdir.path().join("snapshot.redb");
+
+  let snapshot = CommandBuilder::new(format!(
+    "index snapshot --server-url {} {}",
+    ord.url(),
+    snapshot_path.display(),
+  ))
+  .core(&core)
+  .run_and_deserialize_output::<ord::subcommand::index::snapshot::Output>();
+
+  assert_eq!(snapshot.height, 1);
+  assert_eq!(snapshot.path, snapshot_path);
+
+  let restore = CommandBuilder::new(format!(
+    "--index {} index restore {}",
+    tempdir.path().join("index.redb").display(),
+    snapshot_path.display(),
+  ))
+  .core(&core)
+  .run_and_deserialize_output::<ord::subcommand::index::restore::Output>();
+
+  assert_eq!(restore.hash, snapshot.hash);
+}
+
+#[test]
+fn restore_requires_matching_indexed_features() {
+  let core = mockcore::spawn();
+  core.mine_blocks(1);
+
+  let tempdir = TempDir::new().unwrap();
+
+  let index_path = tempdir.path().join("foo.redb");
+  let snapshot_path = tempdir.path().join("snapshot.redb");
+
+  CommandBuilder::new(format!("--index {} index update", index_path.display()))
+    .core(&core)
+    .run_and_extract_stdout();
+
+  CommandBuilder::new(format!(
+    "--index {} index snapshot {}",
+    index_path.display(),
+    snapshot_path.display(),
+  ))
+  .core(&core)
+  .run_and_deserialize_output::<ord::subcommand::index::snapshot::Output>();
+
+  CommandBuilder::new(format!(
+    "--index {} --index-sats index restore {}",
+    tempdir.path().join("bar.redb").display(),
+    snapshot_path.display(),
+  ))
+  .core(&core)
+  .expected_stderr(
+    "error: snapshot indexed features do not match settings: --index-sats is set but the snapshot was taken without it\n",
+  )
+  .expected_exit_code(1)
+  .run_and_extract_stdout();
+}
+
+#[test]
+fn restore_does_not_overwrite_index() {
+  let core = mockcore::spawn();
+  core.mine_blocks(1);
+
+  let tempdir = TempDir::new().unwrap();
+
+  let index_path = tempdir.path().join("foo.redb");
+  let snapshot_path = tempdir.path().join("snapshot.redb");
+
+  CommandBuilder::new(format!("--index {} index update", index_path.display()))
+    .core(&core)
+    .run_and_extract_stdout();
+
+  CommandBuilder::new(format!(
+    "--index {} index snapshot {}",
+    index_path.display(),
+    snapshot_path.display(),
+  ))
+  .core(&core)
+  .run_and_deserialize_output::<ord::subcommand::index::snapshot::Output>();
+
+  CommandBuilder::new(format!(
+    "--index {} index restore {}",
+    index_path.display(),
+    snapshot_path.display(),
+  ))
+  .core(&core)
+  .expected_stderr(format!(
+    "error: index `{}` already exists, remove it before restoring a snapshot\n",
+    index_path.display()
+  ))
+  .expected_exit_code(1)
+  .run_and_extract_stdout();
+}
+
+#[test]
+fn verify_reports_no_problems() {
+  let core = mockcore::spawn();
+  let ord = TestServer::spawn(&core);
+
+  create_wallet(&core, &ord);
+
+  inscribe(&core, &ord);
+
+  let tempdir = TempDir::new().unwrap();
+
+  let index_path = tempdir.path().join("foo.redb");
+
+  CommandBuilder::new(format!(
+    "--index {} --index-runes --index-sats index update",
+    index_path.display()
+  ))
+  .core(&core)
+  .run_and_extract_stdout();
+
+  let output = CommandBuilder::new(format!("--index {} index verify", index_path.display()))
+    .core(&core)
+    .run_and_deserialize_output::<ord::subcommand::index::verify::Output>();
+
+  assert_eq!(
+    output,
+    ord::subcommand::index::verify::Output {
+      height: Some(2),
+      problems: Vec::new(),
+    }
+  );
+}
+
+#[test]
+fn export_tables_as_jsonl() {
+  let core = mockcore::spawn();
+  let ord = TestServer::spawn_with_args(&core, &["--index-runes", "--index-sats"]);
+
+  create_wallet(&core, &ord);
+
+  let (inscription, _) = inscribe(&core, &ord);
+
+  let tempdir = TempDir::new().unwrap();
+
+  let output = CommandBuilder::new(format!(
+    "--index-runes --index-sats index export --output-dir {}",
+    tempdir.path().display()
+  ))
+  .core(&core)
+  .run_and_deserialize_output::<ord::subcommand::index::export::Output>();
+
+  assert_eq!(output.height, 2);
+
+  assert_eq!(
+    output.files,
+    [
+      "inscriptions.jsonl",
+      "runes.jsonl",
+      "rune-balances.jsonl",
+      "sat-ranges.jsonl",
+      "children.jsonl",
+    ]
+    .map(|file| tempdir.path().join(file))
+  );
+
+  let inscriptions = fs::read_to_string(tempdir.path().join("inscriptions.jsonl")).unwrap();
+
+  let lines = inscriptions
+    .lines()
+    .map(|line| serde_json::from_str(line).unwrap())
+    .collect::<Vec<serde_json::Value>>();
+
+  assert_eq!(lines.len(), 2);
+  assert_eq!(lines[0]["height"], 2);
+  assert_eq!(lines[0]["hash"], output.hash.to_string());
+  assert_eq!(lines[1]["id"], inscription.to_string());
+  assert_eq!(lines[1]["inscription_number"], 0);
+  assert_eq!(lines[1]["height"], 2);
+
+  let sat_ranges = fs::read_to_string(tempdir.path().join("sat-ranges.jsonl")).unwrap();
+
+  assert!(sat_ranges.lines().count() > 1);
+}
+
+#[test]
+fn export_tables_as_csv() {
+  let core = mockcore::spawn();
+  let ord = TestServer::spawn(&core);
+
+  create_wallet(&core, &ord);
+
+  let (inscription, _) = inscribe(&core, &ord);
+
+  let tempdir = TempDir::new().unwrap();
+
+  let output = CommandBuilder::new(format!(
+    "index export --output-dir {} --format csv --table inscriptions",
+    tempdir.path().display()
+  ))
+  .core(&core)
+  .run_and_deserialize_output::<ord::subcommand::index::export::Output>();
+
+  assert_eq!(output.files, [tempdir.path().join("inscriptions.csv")]);
+
+  let csv = fs::read_to_string(tempdir.path().join("inscriptions.csv")).unwrap();
+
+  let lines = csv.lines().collect::<Vec<&str>>();
+
+  assert_eq!(
+    lines[0],
+    format!("# export at block height 2 hash {}", output.hash)
+  );
+  assert_eq!(
+    lines[1],
+    "sequence_number,inscription_number,id,satpoint,sat,height,timestamp,fee,charms,parents"
+  );
+  assert!(lines[2].starts_with(&format!("0,0,{inscription},{}:0:0,,2,", inscription.txid)));
+  assert_eq!(lines.len(), 3);
+}
+
+#[test]
+fn export_can_be_resumed() {
+  let core = mockcore::spawn();
+  core.mine_blocks(3);
+
+  let tempdir = TempDir::new().unwrap();
+
+  let index_path = tempdir.path().join("foo.redb");
+  let output_dir = tempdir.path().join("export");
+  let sat_ranges = output_dir.join("sat-ranges.jsonl");
+
+  CommandBuilder::new(format!(
+    "--index {} --index-sats index export --output-dir {} --table sat-ranges",
+    index_path.display(),
+    output_dir.display(),
+  ))
+  .core(&core)
+  .run_and_deserialize_output::<ord::subcommand::index::export::Output>();
+
+  let complete = fs::read_to_string(&sat_ranges).unwrap();
+
+  let partial = &complete[..complete.len() - complete.lines().last().unwrap().len() / 2 - 1];
+
+  fs::write(&sat_ranges, partial).unwrap();
+
+  CommandBuilder::new(format!(
+    "--index {} --index-sats index export --output-dir {} --table sat-ranges --resume",
+    index_path.display(),
+    output_dir.display(),
+  ))
+  .core(&core)
+  .run_and_deserialize_output::<ord::subcommand::index::export::Output>();
+
+  pretty_assert_eq!(fs::read_to_string(&sat_ranges).unwrap(), complete);
+
+  core.mine_blocks(1);
+
+  CommandBuilder::new(format!(
+    "--index {} --index-sats index export --output-dir {} --table sat-ranges --resume",
+    index_path.display(),
+    output_dir.display(),
+  ))
+  .core(&core)
+  .expected_stderr(format!(
+    "error: `{}` was not exported at the current block, remove it to start over\n",
+    sat_ranges.display()
+  ))
+  .expected_exit_code(1)
+  .run_and_extract_stdout();
+}
+
+#[test]
+fn export_of_unindexed_table_fails() {
+  let core = mockcore::spawn();
+  core.mine_blocks(1);
+
+  let tempdir = TempDir::new().unwrap();
+
+  CommandBuilder::new(format!(
+    "index export --output-dir {} --table runes",
+    tempdir.path().display()
+  ))
+  .core(&core)
+  .expected_stderr("error: exporting runes requires index created with `--index-runes` flag\n")
+  .expected_exit_code(1)
+  .run_and_extract_stdout();
+}
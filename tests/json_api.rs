@@ -29,6 +29,7 @@ fn get_sat_without_sat_index() {
       rarity: Rarity::Uncommon,
       percentile: "100%".into(),
       satpoint: None,
+      satributes: vec![Satribute::Black],
       timestamp: 0,
       inscriptions: Vec::new(),
       charms: vec![Charm::Uncommon],
@@ -67,6 +68,7 @@ fn get_sat_with_inscription_and_sat_index() {
       rarity: Rarity::Uncommon,
       percentile: "0.00023809523835714296%".into(),
       satpoint: Some(SatPoint::from_str(&format!("{}:{}:{}", reveal, 0, 0)).unwrap()),
+      satributes: vec![Satribute::Alpha, Satribute::Vintage],
       timestamp: 1,
       inscriptions: vec![inscription_id],
       charms: vec![Charm::Coin, Charm::Uncommon],
@@ -123,6 +125,7 @@ fn get_sat_with_inscription_on_common_sat_and_more_inscriptions() {
       rarity: Rarity::Common,
       percentile: "0.000714285715119048%".into(),
       satpoint: Some(SatPoint::from_str(&format!("{}:{}:{}", reveal, 0, 0)).unwrap()),
+      satributes: vec![Satribute::Vintage],
       timestamp: 3,
       inscriptions: vec![inscription_id],
       charms: Vec::new(),
@@ -153,18 +156,26 @@ fn get_inscription() {
     inscription_json,
     api::Inscription {
       address: None,
+      body_hash: Some(
+        "9520437ce8902eb379a7d8aaa98fc4c94eeb07b6684854868fa6f72bf34b0fd3"
+          .parse()
+          .unwrap()
+      ),
       charms: vec![Charm::Coin, Charm::Uncommon],
       children: Vec::new(),
       content_length: Some(3),
       content_type: Some("text/plain;charset=utf-8".to_string()),
+      delegator_count: 0,
       effective_content_type: Some("text/plain;charset=utf-8".to_string()),
       fee: 138,
       height: 2,
       id: inscription_id,
       number: 0,
       next: None,
+      original: None,
       value: Some(10000),
       parents: Vec::new(),
+      pending: false,
       previous: None,
       rune: None,
       sat: Some(Sat(50 * COIN_VALUE)),
@@ -343,6 +354,7 @@ fn get_output() {
         InscriptionId { txid, index: 2 },
       ],
       indexed: true,
+      pending: false,
       runes: BTreeMap::new(),
       sat_ranges: Some(vec![
         (5000000000, 10000000000,),
@@ -549,6 +561,7 @@ fn get_runes() {
         timestamp: 10,
         turbo: false,
       },
+      holders: 1,
       id: RuneId { block: 10, tx: 1 },
       mintable: false,
       parent: Some(InscriptionId {
@@ -16,7 +16,8 @@ use {
     InscriptionId, RuneEntry,
   },
   ordinals::{
-    Artifact, Charm, Edict, Pile, Rarity, Rune, RuneId, Runestone, Sat, SatPoint, SpacedRune,
+    Artifact, Charm, Edict, Pile, Rarity, Rune, RuneId, Runestone, Sat, SatPoint, Satribute,
+    SpacedRune,
   },
   pretty_assertions::assert_eq as pretty_assert_eq,
   regex::Regex,
@@ -321,6 +322,8 @@ fn batch(core: &mockcore::Handle, ord: &TestServer, batchfile: batch::File) -> E
 
   let RuneId { block, tx } = id;
 
+  let holders = u64::from(premine.to_integer(divisibility).unwrap() > 0);
+
   ord.assert_response_regex(
     format!("/rune/{rune}"),
     format!(
@@ -340,6 +343,8 @@ fn batch(core: &mockcore::Handle, ord: &TestServer, batchfile: batch::File) -> E
   <dd>.*</dd>
   <dt>burned</dt>
   <dd>0 {symbol}</dd>
+  <dt>holders</dt>
+  <dd>{holders}</dd>
   <dt>divisibility</dt>
   <dd>{divisibility}</dd>
   <dt>symbol</dt>
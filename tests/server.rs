@@ -69,6 +69,8 @@ fn inscription_page() {
   <dd><a href=/content/{inscription}>link</a></dd>
   <dt>content length</dt>
   <dd>3 bytes</dd>
+  <dt>body hash</dt>
+  <dd><a class=monospace href=/inscriptions/body-hash/9520437ce8902eb379a7d8aaa98fc4c94eeb07b6684854868fa6f72bf34b0fd3>9520437ce8902eb379a7d8aaa98fc4c94eeb07b6684854868fa6f72bf34b0fd3</a></dd>
   <dt>content type</dt>
   <dd>text/plain;charset=utf-8</dd>
   <dt>timestamp</dt>
@@ -418,6 +420,18 @@ fn missing_credentials() {
     .run_and_extract_stdout();
 }
 
+#[test]
+fn webhooks_require_event_index() {
+  let core = mockcore::spawn();
+
+  CommandBuilder::new("server --no-sync --address 127.0.0.1 --http-port 0")
+    .core(&core)
+    .env("ORD_WEBHOOKS", "http://localhost:9000/events")
+    .expected_exit_code(1)
+    .expected_stderr("error: webhooks require index created with `--index-events` flag\n")
+    .run_and_extract_stdout();
+}
+
 #[test]
 fn all_endpoints_in_recursive_directory_return_json() {
   let core = mockcore::spawn();
@@ -21,16 +21,23 @@ fn default() {
   "height_limit": null,
   "hidden": \[\],
   "index": ".*index\.redb",
+  "index_addresses": false,
   "index_cache_size": \d+,
+  "index_events": false,
+  "index_metadata": false,
   "index_runes": false,
   "index_sats": false,
   "index_spent_sats": false,
   "index_transactions": false,
+  "index_transfers": false,
   "integration_test": false,
+  "max_reorg_depth": 100,
   "no_index_inscriptions": false,
   "server_password": null,
   "server_url": null,
-  "server_username": null
+  "server_username": null,
+  "webhook_secret": null,
+  "webhooks": null
 \}
 "#,
     )
@@ -50,13 +50,18 @@ impl TestServer {
       ord_server_args.join(" "),
     ));
 
-    let index = Arc::new(Index::open(&settings).unwrap());
+    let (event_sender, event_receiver) = tokio::sync::mpsc::channel(1024);
+    let index = Arc::new(Index::open_with_event_sender(&settings, Some(event_sender)).unwrap());
     let ord_server_handle = Handle::new();
 
     {
       let index = index.clone();
       let ord_server_handle = ord_server_handle.clone();
-      thread::spawn(|| server.run(settings, index, ord_server_handle).unwrap());
+      thread::spawn(|| {
+        server
+          .run_with_events(settings, index, ord_server_handle, Some(event_receiver))
+          .unwrap()
+      });
     }
 
     for i in 0.. {
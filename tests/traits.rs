@@ -15,6 +15,7 @@ fn traits_command_prints_sat_traits() {
       period: 0,
       offset: 0,
       rarity: Rarity::Mythic,
+      satributes: vec![Satribute::Alpha, Satribute::Palindrome, Satribute::Vintage],
     }
   );
 }
@@ -33,6 +34,7 @@ fn traits_command_for_last_sat() {
       period: 3437,
       offset: 0,
       rarity: Rarity::Uncommon,
+      satributes: vec![Satribute::Black],
     }
   );
 }
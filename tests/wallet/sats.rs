@@ -1,6 +1,6 @@
 use {
   super::*,
-  ord::subcommand::wallet::sats::{OutputRare, OutputTsv},
+  ord::subcommand::wallet::sats::{OutputRare, OutputSatributes, OutputTsv},
 };
 
 #[test]
@@ -38,6 +38,38 @@ fn sats() {
   assert_eq!(output[0].output.to_string(), format!("{second_coinbase}:0"));
 }
 
+#[test]
+fn sats_with_satributes() {
+  let core = mockcore::spawn();
+
+  let ord = TestServer::spawn_with_server_args(&core, &["--index-sats"], &[]);
+
+  create_wallet(&core, &ord);
+
+  let second_coinbase = core.mine_blocks(1)[0].txdata[0].txid();
+
+  let output = CommandBuilder::new("--index-sats wallet sats --satributes")
+    .core(&core)
+    .ord(&ord)
+    .run_and_deserialize_output::<Vec<OutputSatributes>>();
+
+  assert_eq!(output.len(), 1);
+  assert_eq!(output[0].output.to_string(), format!("{second_coinbase}:0"));
+  assert_eq!(output[0].offset, 0);
+  assert_eq!(output[0].start, 50 * COIN_VALUE);
+  assert_eq!(output[0].end, 100 * COIN_VALUE);
+  assert_eq!(
+    output[0].satributes,
+    [
+      Satribute::Alpha,
+      Satribute::Black,
+      Satribute::Omega,
+      Satribute::Palindrome,
+      Satribute::Vintage,
+    ]
+  );
+}
+
 #[test]
 fn sats_from_tsv_success() {
   let core = mockcore::spawn();
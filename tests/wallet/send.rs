@@ -35,6 +35,8 @@ fn inscriptions_can_be_sent() {
       ".*<h1>Inscription 0</h1>.*<dl>.*
   <dt>content length</dt>
   <dd>3 bytes</dd>
+  <dt>body hash</dt>
+  <dd><a class=monospace href=/inscriptions/body-hash/9520437ce8902eb379a7d8aaa98fc4c94eeb07b6684854868fa6f72bf34b0fd3>9520437ce8902eb379a7d8aaa98fc4c94eeb07b6684854868fa6f72bf34b0fd3</a></dd>
   <dt>content type</dt>
   <dd>text/plain;charset=utf-8</dd>
   .*
@@ -362,6 +364,7 @@ inscriptions:
         },
       ],
       indexed: true,
+      pending: false,
       runes: BTreeMap::new(),
       sat_ranges: Some(vec![(5_000_000_000, 5_000_030_000)]),
       script_pubkey: destination.payload.script_pubkey().to_asm_string(),